@@ -0,0 +1,41 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use webrtc_media::audio::{
+    buffer::{layout::Interleaved, Buffer},
+    resample::{Interpolation, Resampler},
+};
+
+fn benchmark_process(c: &mut Criterion) {
+    type Sample = f32;
+    let channels = 2;
+    let frames = 4_410;
+    let buffer: Buffer<Sample, Interleaved> = {
+        let samples = (0..(channels * frames))
+            .map(|i| ((i as f32) * 0.01).sin())
+            .collect();
+        Buffer::new(samples, channels)
+    };
+
+    let scenarios = [
+        ("linear", Interpolation::Linear),
+        ("sinc", Interpolation::Sinc { zero_crossings: 16 }),
+    ];
+
+    for (name, interpolation) in scenarios {
+        for (input_rate, output_rate) in [(44_100, 48_000), (48_000, 16_000)] {
+            let mut resampler = Resampler::new(input_rate, output_rate, interpolation);
+
+            c.bench_function(
+                &format!("Resampler ({}): {} => {}", name, input_rate, output_rate),
+                |b| {
+                    b.iter(|| {
+                        black_box(resampler.process::<Sample, Interleaved>(buffer.as_ref()));
+                    })
+                },
+            );
+        }
+    }
+}
+
+criterion_group!(benches, benchmark_process);
+criterion_main!(benches);
@@ -0,0 +1,70 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use webrtc_media::audio::simd::{Backend, Element};
+
+const FRAMES: usize = 960;
+
+fn backends() -> Vec<Backend> {
+    let mut backends = vec![Backend::Scalar];
+    let detected = Backend::detect();
+    if detected != Backend::Scalar {
+        backends.push(detected);
+    }
+    backends
+}
+
+fn bench_layouts<T>(c: &mut Criterion, name: &str)
+where
+    T: Element + From<u8>,
+{
+    let mut group = c.benchmark_group(format!("simd/{}", name));
+
+    for channels in [1, 2, 6, 8] {
+        let input: Vec<T> = (0..(channels * FRAMES)).map(|i| T::from(i as u8)).collect();
+        let mut output = vec![T::default(); input.len()];
+
+        for backend in backends() {
+            let id = format!("{:?}/{} channels", backend, channels);
+
+            group.bench_function(BenchmarkId::new("interleave", &id), |b| {
+                b.iter(|| backend.interleave(black_box(&input[..]), &mut output[..], channels))
+            });
+            group.bench_function(BenchmarkId::new("deinterleave", &id), |b| {
+                b.iter(|| backend.deinterleave(black_box(&input[..]), &mut output[..], channels))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn benchmark_layouts(c: &mut Criterion) {
+    bench_layouts::<i16>(c, "i16");
+    bench_layouts::<f32>(c, "f32");
+}
+
+fn benchmark_conversions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("simd/conversion");
+
+    let samples = 2 * FRAMES;
+    let i16_samples: Vec<i16> = (0..samples).map(|i| (i as i16).wrapping_mul(31)).collect();
+    let f32_samples: Vec<f32> = (0..samples).map(|i| ((i as f32) * 0.01).sin()).collect();
+    let mut i16_output = vec![0; samples];
+    let mut f32_output = vec![0.0; samples];
+
+    for backend in backends() {
+        let id = format!("{:?}", backend);
+
+        group.bench_function(BenchmarkId::new("i16 => f32", &id), |b| {
+            b.iter(|| backend.i16_to_f32(black_box(&i16_samples[..]), &mut f32_output[..]))
+        });
+        group.bench_function(BenchmarkId::new("f32 => i16", &id), |b| {
+            b.iter(|| backend.f32_to_i16(black_box(&f32_samples[..]), &mut i16_output[..]))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_layouts, benchmark_conversions);
+criterion_main!(benches);
@@ -4,6 +4,7 @@ pub mod layout;
 use std::{
     mem::{ManuallyDrop, MaybeUninit},
     ops::Range,
+    slice::{ChunksExact, ChunksExactMut},
 };
 
 use byteorder::ByteOrder;
@@ -14,6 +15,8 @@ pub use layout::BufferLayout;
 
 use layout::{Deinterleaved, Interleaved};
 
+use crate::audio::simd;
+
 pub trait FromBytes<L>: Sized {
     type Error;
 
@@ -23,12 +26,12 @@ pub trait FromBytes<L>: Sized {
 pub trait ToByteBufferRef<L>: Sized {
     type Error;
 
-    fn bytes_len(&self);
-    fn to_bytes<B: ByteOrder>(
-        &self,
-        bytes: &mut [u8],
-        channels: usize,
-    ) -> Result<usize, Self::Error>;
+    /// Returns the number of bytes required for serializing the buffer.
+    fn bytes_len(&self) -> usize;
+
+    /// Serializes the buffer's samples into `bytes` using layout `L`,
+    /// returning the number of bytes written.
+    fn to_bytes<B: ByteOrder>(&self, bytes: &mut [u8]) -> Result<usize, Self::Error>;
 }
 
 #[derive(Debug, Error, PartialEq, Eq)]
@@ -52,6 +55,109 @@ impl<'a, T, L> BufferRef<'a, T, L> {
         };
         Self { samples, info }
     }
+
+    pub fn samples(&self) -> &'a [T] {
+        self.samples
+    }
+
+    pub fn info(&self) -> BufferInfo<L> {
+        self.info
+    }
+}
+
+impl<'a, T> BufferRef<'a, T, Deinterleaved> {
+    /// Returns the samples of a single channel.
+    pub fn channel(&self, channel: usize) -> &'a [T] {
+        let frames = self.info.frames();
+        &self.samples[(channel * frames)..((channel + 1) * frames)]
+    }
+
+    /// Returns an iterator over the channels' samples.
+    pub fn iter_channels(&self) -> impl Iterator<Item = &'a [T]> {
+        split_channels(self.samples, self.info.channels())
+    }
+}
+
+impl<'a, T> BufferRef<'a, T, Interleaved> {
+    /// Returns the samples of a single frame.
+    pub fn frame(&self, frame: usize) -> &'a [T] {
+        let channels = self.info.channels();
+        &self.samples[(frame * channels)..((frame + 1) * channels)]
+    }
+
+    /// Returns an iterator over the frames' samples.
+    pub fn iter_frames(&self) -> ChunksExact<'a, T> {
+        self.samples.chunks_exact(self.info.channels())
+    }
+}
+
+#[derive(Eq, PartialEq, Debug)]
+pub struct BufferMut<'a, T, L> {
+    samples: &'a mut [T],
+    info: BufferInfo<L>,
+}
+
+impl<'a, T, L> BufferMut<'a, T, L> {
+    pub fn new(samples: &'a mut [T], channels: usize) -> Self {
+        debug_assert_eq!(samples.len() % channels, 0);
+        let info = {
+            let frames = samples.len() / channels;
+            BufferInfo::new(channels, frames)
+        };
+        Self { samples, info }
+    }
+
+    pub fn samples(&self) -> &[T] {
+        self.samples
+    }
+
+    pub fn samples_mut(&mut self) -> &mut [T] {
+        self.samples
+    }
+
+    pub fn info(&self) -> BufferInfo<L> {
+        self.info
+    }
+
+    pub fn as_ref(&'_ self) -> BufferRef<'_, T, L> {
+        BufferRef {
+            samples: self.samples,
+            info: self.info,
+        }
+    }
+
+    pub fn as_mut(&'_ mut self) -> BufferMut<'_, T, L> {
+        BufferMut {
+            samples: self.samples,
+            info: self.info,
+        }
+    }
+}
+
+impl<'a, T> BufferMut<'a, T, Deinterleaved> {
+    /// Returns the samples of a single channel.
+    pub fn channel_mut(&mut self, channel: usize) -> &mut [T] {
+        let frames = self.info.frames();
+        &mut self.samples[(channel * frames)..((channel + 1) * frames)]
+    }
+
+    /// Returns an iterator over the channels' mutable samples.
+    pub fn iter_channels_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        split_channels_mut(self.samples, self.info.channels())
+    }
+}
+
+impl<'a, T> BufferMut<'a, T, Interleaved> {
+    /// Returns the samples of a single frame.
+    pub fn frame_mut(&mut self, frame: usize) -> &mut [T] {
+        let channels = self.info.channels();
+        &mut self.samples[(frame * channels)..((frame + 1) * channels)]
+    }
+
+    /// Returns an iterator over the frames' mutable samples.
+    pub fn iter_frames_mut(&mut self) -> ChunksExactMut<'_, T> {
+        self.samples.chunks_exact_mut(self.info.channels())
+    }
 }
 
 /// Buffer multi-channel interlaced Audio.
@@ -71,6 +177,18 @@ impl<T, L> Buffer<T, L> {
         Self { samples, info }
     }
 
+    pub fn samples(&self) -> &[T] {
+        &self.samples[..]
+    }
+
+    pub fn samples_mut(&mut self) -> &mut [T] {
+        &mut self.samples[..]
+    }
+
+    pub fn info(&self) -> BufferInfo<L> {
+        self.info
+    }
+
     pub fn as_ref(&'_ self) -> BufferRef<'_, T, L> {
         BufferRef {
             samples: &self.samples[..],
@@ -78,6 +196,13 @@ impl<T, L> Buffer<T, L> {
         }
     }
 
+    pub fn as_mut(&'_ mut self) -> BufferMut<'_, T, L> {
+        BufferMut {
+            samples: &mut self.samples[..],
+            info: self.info,
+        }
+    }
+
     pub fn sub_range(&'_ self, range: Range<usize>) -> BufferRef<'_, T, L> {
         let samples_len = range.len();
         let samples = &self.samples[range];
@@ -89,11 +214,95 @@ impl<T, L> Buffer<T, L> {
         };
         BufferRef { samples, info }
     }
+
+    pub fn sub_range_mut(&'_ mut self, range: Range<usize>) -> BufferMut<'_, T, L> {
+        let samples_len = range.len();
+        let samples = &mut self.samples[range];
+        let info = {
+            let channels = self.info.channels();
+            assert_eq!(samples_len % channels, 0);
+            let frames = samples_len / channels;
+            BufferInfo::new(channels, frames)
+        };
+        BufferMut { samples, info }
+    }
+}
+
+impl<T> Buffer<T, Deinterleaved> {
+    /// Returns the samples of a single channel.
+    pub fn channel(&self, channel: usize) -> &[T] {
+        self.as_ref().channel(channel)
+    }
+
+    /// Returns the samples of a single channel.
+    pub fn channel_mut(&mut self, channel: usize) -> &mut [T] {
+        let frames = self.info.frames();
+        &mut self.samples[(channel * frames)..((channel + 1) * frames)]
+    }
+
+    /// Returns an iterator over the channels' samples.
+    pub fn iter_channels(&self) -> impl Iterator<Item = &[T]> {
+        split_channels(&self.samples, self.info.channels())
+    }
+
+    /// Returns an iterator over the channels' mutable samples.
+    pub fn iter_channels_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        split_channels_mut(&mut self.samples, self.info.channels())
+    }
+
+    /// Interleaves the buffer in-place, without allocating a second vec of samples.
+    ///
+    /// This is slower than the (allocating) conversion via `From`,
+    /// due to its non-sequential memory access pattern.
+    pub fn into_interleaved(mut self) -> Buffer<T, Interleaved> {
+        layout::transpose_in_place(&mut self.samples, self.info.channels(), self.info.frames());
+
+        Buffer {
+            samples: self.samples,
+            info: self.info.into(),
+        }
+    }
+}
+
+impl<T> Buffer<T, Interleaved> {
+    /// Returns the samples of a single frame.
+    pub fn frame(&self, frame: usize) -> &[T] {
+        self.as_ref().frame(frame)
+    }
+
+    /// Returns the samples of a single frame.
+    pub fn frame_mut(&mut self, frame: usize) -> &mut [T] {
+        let channels = self.info.channels();
+        &mut self.samples[(frame * channels)..((frame + 1) * channels)]
+    }
+
+    /// Returns an iterator over the frames' samples.
+    pub fn iter_frames(&self) -> ChunksExact<'_, T> {
+        self.samples.chunks_exact(self.info.channels())
+    }
+
+    /// Returns an iterator over the frames' mutable samples.
+    pub fn iter_frames_mut(&mut self) -> ChunksExactMut<'_, T> {
+        self.samples.chunks_exact_mut(self.info.channels())
+    }
+
+    /// De-interleaves the buffer in-place, without allocating a second vec of samples.
+    ///
+    /// This is slower than the (allocating) conversion via `From`,
+    /// due to its non-sequential memory access pattern.
+    pub fn into_deinterleaved(mut self) -> Buffer<T, Deinterleaved> {
+        layout::transpose_in_place(&mut self.samples, self.info.frames(), self.info.channels());
+
+        Buffer {
+            samples: self.samples,
+            info: self.info.into(),
+        }
+    }
 }
 
 impl<T> From<Buffer<T, Deinterleaved>> for Buffer<T, Interleaved>
 where
-    T: Default + Copy,
+    T: Default + Copy + 'static,
 {
     fn from(buffer: Buffer<T, Deinterleaved>) -> Self {
         Self::from(buffer.as_ref())
@@ -102,9 +311,15 @@ where
 
 impl<'a, T> From<BufferRef<'a, T, Deinterleaved>> for Buffer<T, Interleaved>
 where
-    T: Default + Copy,
+    T: Default + Copy + 'static,
 {
     fn from(buffer: BufferRef<'a, T, Deinterleaved>) -> Self {
+        let info = buffer.info.into();
+
+        if let Some(samples) = simd::interleaved_vec(buffer.samples, buffer.info.channels()) {
+            return Self { samples, info };
+        }
+
         // Writing into a vec of uninitialized `samples` is about 10% faster than
         // cloning it or creating a default-initialized one and over-writing it.
         //
@@ -127,17 +342,16 @@ where
             );
 
             // Transmute the vec to the initialized type.
-            unsafe { std::mem::transmute::<_, Vec<T>>(samples) }
+            unsafe { std::mem::transmute::<Vec<MaybeUninit<T>>, Vec<T>>(samples) }
         };
 
-        let info = buffer.info.into();
         Self { samples, info }
     }
 }
 
 impl<T> From<Buffer<T, Interleaved>> for Buffer<T, Deinterleaved>
 where
-    T: Default + Copy,
+    T: Default + Copy + 'static,
 {
     fn from(buffer: Buffer<T, Interleaved>) -> Self {
         Self::from(buffer.as_ref())
@@ -146,9 +360,15 @@ where
 
 impl<'a, T> From<BufferRef<'a, T, Interleaved>> for Buffer<T, Deinterleaved>
 where
-    T: Default + Copy,
+    T: Default + Copy + 'static,
 {
     fn from(buffer: BufferRef<'a, T, Interleaved>) -> Self {
+        let info = buffer.info.into();
+
+        if let Some(samples) = simd::deinterleaved_vec(buffer.samples, buffer.info.channels()) {
+            return Self { samples, info };
+        }
+
         // Writing into a vec of uninitialized `samples` is about 10% faster than
         // cloning it or creating a default-initialized one and over-writing it.
         //
@@ -171,10 +391,9 @@ where
             );
 
             // Everything is initialized. Transmute the vec to the initialized type.
-            unsafe { std::mem::transmute::<_, Vec<T>>(samples) }
+            unsafe { std::mem::transmute::<Vec<MaybeUninit<T>>, Vec<T>>(samples) }
         };
 
-        let info = buffer.info.into();
         Self { samples, info }
     }
 }
@@ -299,6 +518,164 @@ impl FromBytes<Interleaved> for Buffer<i16, Deinterleaved> {
     }
 }
 
+impl FromBytes<Interleaved> for Buffer<f32, Interleaved> {
+    type Error = ();
+
+    fn from_bytes<B: ByteOrder>(bytes: &[u8], channels: usize) -> Result<Self, Self::Error> {
+        const STRIDE: usize = std::mem::size_of::<f32>();
+        assert_eq!(bytes.len() % STRIDE, 0);
+
+        let samples: Vec<_> = bytes.chunks_exact(STRIDE).map(B::read_f32).collect();
+
+        Ok(Self::new(samples, channels))
+    }
+}
+
+impl FromBytes<Deinterleaved> for Buffer<f32, Interleaved> {
+    type Error = ();
+
+    fn from_bytes<B: ByteOrder>(bytes: &[u8], channels: usize) -> Result<Self, Self::Error> {
+        const STRIDE: usize = std::mem::size_of::<f32>();
+        assert_eq!(bytes.len() % STRIDE, 0);
+
+        let chunks = {
+            let chunks_ptr = bytes.as_ptr() as *const [u8; STRIDE];
+            let chunks_len = bytes.len() / STRIDE;
+            unsafe { std::slice::from_raw_parts(chunks_ptr, chunks_len) }
+        };
+
+        // # Safety
+        //
+        // `interleaved_by` initializes each and every single item in `samples`.
+        let samples = unsafe {
+            init_vec(chunks.len(), |samples| {
+                layout::interleaved_by(chunks, samples, channels, |chunk| {
+                    MaybeUninit::new(B::read_f32(&chunk[..]))
+                });
+            })
+        };
+
+        Ok(Self::new(samples, channels))
+    }
+}
+
+impl FromBytes<Deinterleaved> for Buffer<f32, Deinterleaved> {
+    type Error = ();
+
+    fn from_bytes<B: ByteOrder>(bytes: &[u8], channels: usize) -> Result<Self, Self::Error> {
+        const STRIDE: usize = std::mem::size_of::<f32>();
+        assert_eq!(bytes.len() % STRIDE, 0);
+
+        let samples: Vec<_> = bytes.chunks_exact(STRIDE).map(B::read_f32).collect();
+
+        Ok(Self::new(samples, channels))
+    }
+}
+
+impl FromBytes<Interleaved> for Buffer<f32, Deinterleaved> {
+    type Error = ();
+
+    fn from_bytes<B: ByteOrder>(bytes: &[u8], channels: usize) -> Result<Self, Self::Error> {
+        const STRIDE: usize = std::mem::size_of::<f32>();
+        assert_eq!(bytes.len() % STRIDE, 0);
+
+        let chunks = {
+            let chunks_ptr = bytes.as_ptr() as *const [u8; STRIDE];
+            let chunks_len = bytes.len() / STRIDE;
+            unsafe { std::slice::from_raw_parts(chunks_ptr, chunks_len) }
+        };
+
+        // # Safety
+        //
+        // `deinterleaved_by` initializes each and every single item in `samples`.
+        let samples = unsafe {
+            init_vec(chunks.len(), |samples| {
+                layout::deinterleaved_by(chunks, samples, channels, |chunk| {
+                    MaybeUninit::new(B::read_f32(&chunk[..]))
+                });
+            })
+        };
+
+        Ok(Self::new(samples, channels))
+    }
+}
+
+macro_rules! impl_to_byte_buffer_ref {
+    ($raw:ty, $write:ident) => {
+        impl_to_byte_buffer_ref!($raw, $write, Interleaved => Interleaved, layout::interleaved_positions);
+        impl_to_byte_buffer_ref!($raw, $write, Deinterleaved => Deinterleaved, layout::deinterleaved_positions);
+        impl_to_byte_buffer_ref!($raw, $write, Interleaved => Deinterleaved, layout::deinterleaved_positions);
+        impl_to_byte_buffer_ref!($raw, $write, Deinterleaved => Interleaved, layout::interleaved_positions);
+    };
+    ($raw:ty, $write:ident, $layout:ty => $bytes_layout:ty, $positions:path) => {
+        impl<'a> ToByteBufferRef<$bytes_layout> for BufferRef<'a, $raw, $layout> {
+            type Error = Error;
+
+            fn bytes_len(&self) -> usize {
+                self.samples.len() * std::mem::size_of::<$raw>()
+            }
+
+            fn to_bytes<B: ByteOrder>(&self, bytes: &mut [u8]) -> Result<usize, Self::Error> {
+                const STRIDE: usize = std::mem::size_of::<$raw>();
+
+                let bytes_len = ToByteBufferRef::<$bytes_layout>::bytes_len(self);
+                if bytes.len() < bytes_len {
+                    return Err(Error::UnexpectedEndOfBuffer {
+                        expected: bytes_len,
+                        actual: bytes.len(),
+                    });
+                }
+
+                // Writes the chunks sequentially, in the order of the bytes' layout:
+                let chunks = bytes[..bytes_len].chunks_exact_mut(STRIDE);
+                let positions = $positions(self.info.channels(), self.info.frames());
+                for (chunk, (channel, frame)) in chunks.zip(positions) {
+                    B::$write(chunk, self.samples[<$layout>::index_of(&self.info, channel, frame)]);
+                }
+
+                Ok(bytes_len)
+            }
+        }
+
+        impl ToByteBufferRef<$bytes_layout> for Buffer<$raw, $layout> {
+            type Error = Error;
+
+            fn bytes_len(&self) -> usize {
+                ToByteBufferRef::<$bytes_layout>::bytes_len(&self.as_ref())
+            }
+
+            fn to_bytes<B: ByteOrder>(&self, bytes: &mut [u8]) -> Result<usize, Self::Error> {
+                ToByteBufferRef::<$bytes_layout>::to_bytes::<B>(&self.as_ref(), bytes)
+            }
+        }
+    };
+}
+
+impl_to_byte_buffer_ref!(i16, write_i16);
+impl_to_byte_buffer_ref!(f32, write_f32);
+
+/// Splits de-interleaved samples into one slice per channel,
+/// which are all empty if there are no frames.
+fn split_channels<T>(samples: &[T], channels: usize) -> impl Iterator<Item = &[T]> {
+    let frames = samples.len() / channels;
+    let empty = if frames == 0 { channels } else { 0 };
+
+    samples
+        .chunks_exact(frames.max(1))
+        .chain(std::iter::repeat(&[][..]).take(empty))
+}
+
+/// Splits de-interleaved samples into one mutable slice per channel,
+/// which are all empty if there are no frames.
+fn split_channels_mut<T>(samples: &mut [T], channels: usize) -> impl Iterator<Item = &mut [T]> {
+    let frames = samples.len() / channels;
+    let empty = if frames == 0 { channels } else { 0 };
+
+    samples
+        .chunks_exact_mut(frames.max(1))
+        .chain(std::iter::repeat_with(<&mut [T]>::default).take(empty))
+}
+
 /// Creates a vec with deferred initialization.
 ///
 /// # Safety
@@ -327,7 +704,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use byteorder::NativeEndian;
+    use byteorder::{BigEndian, LittleEndian, NativeEndian};
 
     use super::*;
 
@@ -361,6 +738,18 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn layouts_without_simd() {
+        // `u8` samples don't have a SIMD path:
+        let input: Buffer<u8, Interleaved> = Buffer::new(vec![0, 3, 1, 4, 2, 5], 2);
+
+        let output = Buffer::<u8, Deinterleaved>::from(input.as_ref());
+        assert_eq!(output.samples(), &[0, 1, 2, 3, 4, 5]);
+
+        let output = Buffer::<u8, Interleaved>::from(output);
+        assert_eq!(output, input);
+    }
+
     #[test]
     fn deinterleaved_from_deinterleaved_bytes() {
         let channels = 3;
@@ -444,4 +833,179 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn buffer_mut() {
+        let mut buffer: Buffer<i32, Interleaved> = Buffer::new(vec![0, 1, 2, 3, 4, 5], 2);
+
+        {
+            let mut view = buffer.sub_range_mut(2..6);
+            assert_eq!(view.info().frames(), 2);
+            view.samples_mut()[0] = 20;
+            view.frame_mut(1)[1] = 50;
+            assert_eq!(view.as_ref().frame(0), &[20, 3]);
+        }
+
+        assert_eq!(buffer.samples(), &[0, 1, 20, 3, 4, 50]);
+
+        let mut samples = vec![1, 2, 3, 4];
+        let mut view = BufferMut::<i32, Deinterleaved>::new(&mut samples[..], 2);
+        for channel in view.iter_channels_mut() {
+            channel.reverse();
+        }
+        view.channel_mut(0)[0] = 10;
+        assert_eq!(samples, vec![10, 1, 4, 3]);
+    }
+
+    #[test]
+    fn iter_channels() {
+        let mut buffer: Buffer<i32, Deinterleaved> = Buffer::new(vec![0, 1, 2, 3, 4, 5], 3);
+
+        let channels: Vec<_> = buffer.iter_channels().collect();
+        assert_eq!(channels, vec![&[0, 1][..], &[2, 3][..], &[4, 5][..]]);
+        assert_eq!(buffer.channel(1), &[2, 3]);
+        assert_eq!(buffer.as_ref().iter_channels().count(), 3);
+
+        for channel in buffer.iter_channels_mut() {
+            channel[0] *= 10;
+        }
+        buffer.channel_mut(2)[1] = 0;
+
+        assert_eq!(buffer.samples(), &[0, 1, 20, 3, 40, 0]);
+    }
+
+    #[test]
+    fn iter_channels_without_frames() {
+        let mut buffer: Buffer<i32, Deinterleaved> = Buffer::new(vec![], 3);
+
+        let channels: Vec<_> = buffer.iter_channels().collect();
+        assert_eq!(channels, vec![&[] as &[i32]; 3]);
+        assert_eq!(buffer.as_ref().iter_channels().count(), 3);
+        assert_eq!(buffer.as_mut().iter_channels_mut().count(), 3);
+        assert_eq!(buffer.iter_channels_mut().count(), 3);
+    }
+
+    #[test]
+    fn iter_frames() {
+        let mut buffer: Buffer<i32, Interleaved> = Buffer::new(vec![0, 1, 2, 3, 4, 5], 3);
+
+        let frames: Vec<_> = buffer.iter_frames().collect();
+        assert_eq!(frames, vec![&[0, 1, 2][..], &[3, 4, 5][..]]);
+        assert_eq!(buffer.frame(1), &[3, 4, 5]);
+        assert_eq!(buffer.as_ref().iter_frames().count(), 2);
+
+        for frame in buffer.iter_frames_mut() {
+            frame.swap(0, 2);
+        }
+        buffer.frame_mut(0)[1] = 10;
+
+        assert_eq!(buffer.samples(), &[2, 10, 0, 5, 4, 3]);
+    }
+
+    #[test]
+    fn in_place_transposition() {
+        for channels in 1..=6 {
+            for frames in [0, 1, 2, 7, 480] {
+                let samples: Vec<usize> = (0..(channels * frames)).collect();
+                let interleaved: Buffer<usize, Interleaved> = Buffer::new(samples, channels);
+
+                let expected = Buffer::<usize, Deinterleaved>::from(interleaved.clone());
+                let actual = interleaved.clone().into_deinterleaved();
+                assert_eq!(actual, expected);
+
+                assert_eq!(actual.into_interleaved(), interleaved);
+            }
+        }
+    }
+
+    #[test]
+    fn to_bytes() {
+        let channels = 3;
+
+        let input_samples: Vec<i16> = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14];
+        let input: Buffer<i16, Interleaved> = Buffer::new(input_samples, channels);
+
+        let mut bytes = vec![0; ToByteBufferRef::<Deinterleaved>::bytes_len(&input)];
+        let bytes_len =
+            ToByteBufferRef::<Deinterleaved>::to_bytes::<BigEndian>(&input, &mut bytes[..])
+                .unwrap();
+
+        assert_eq!(bytes_len, 30);
+        assert_eq!(&bytes[..8], &[0, 0, 0, 3, 0, 6, 0, 9]);
+    }
+
+    #[test]
+    fn to_bytes_too_short() {
+        let input: Buffer<f32, Deinterleaved> = Buffer::new(vec![0.0; 4], 2);
+
+        let mut bytes = [0; 15];
+        let result =
+            ToByteBufferRef::<Interleaved>::to_bytes::<LittleEndian>(&input, &mut bytes[..]);
+
+        assert_eq!(
+            result,
+            Err(Error::UnexpectedEndOfBuffer {
+                expected: 16,
+                actual: 15
+            })
+        );
+    }
+
+    fn round_trip<T, L, BL, B>(input: Buffer<T, L>)
+    where
+        T: std::fmt::Debug + PartialEq,
+        Buffer<T, L>: FromBytes<BL> + ToByteBufferRef<BL> + std::fmt::Debug + PartialEq,
+        <Buffer<T, L> as FromBytes<BL>>::Error: std::fmt::Debug,
+        <Buffer<T, L> as ToByteBufferRef<BL>>::Error: std::fmt::Debug,
+        B: ByteOrder,
+    {
+        let channels = input.info().channels();
+
+        let mut bytes = vec![0; ToByteBufferRef::<BL>::bytes_len(&input)];
+        ToByteBufferRef::<BL>::to_bytes::<B>(&input, &mut bytes[..]).unwrap();
+        let output: Buffer<T, L> = FromBytes::<BL>::from_bytes::<B>(&bytes[..], channels).unwrap();
+
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let i16_samples: Vec<i16> = vec![i16::MIN, -1, 0, 1, 2, i16::MAX];
+        let f32_samples: Vec<f32> = vec![-1.0, -0.5, 0.0, 0.25, 0.5, 1.0];
+
+        macro_rules! round_trips {
+            ($samples:expr, $raw:ty) => {
+                round_trips!($samples, $raw, Interleaved);
+                round_trips!($samples, $raw, Deinterleaved);
+            };
+            ($samples:expr, $raw:ty, $layout:ty) => {
+                let buffer: Buffer<$raw, $layout> = Buffer::new($samples.clone(), 2);
+                round_trip::<_, _, Interleaved, LittleEndian>(buffer.clone());
+                round_trip::<_, _, Interleaved, BigEndian>(buffer.clone());
+                round_trip::<_, _, Deinterleaved, LittleEndian>(buffer.clone());
+                round_trip::<_, _, Deinterleaved, BigEndian>(buffer);
+            };
+        }
+
+        round_trips!(i16_samples, i16);
+        round_trips!(f32_samples, f32);
+    }
+
+    #[test]
+    fn f32_from_bytes() {
+        let bytes: Vec<u8> = [0.5_f32, -1.0, 0.25, 1.0]
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+
+        let output: Buffer<f32, Deinterleaved> =
+            FromBytes::<Interleaved>::from_bytes::<LittleEndian>(&bytes[..], 2).unwrap();
+
+        assert_eq!(output.samples(), &[0.5, 0.25, -1.0, 1.0]);
+
+        let output: Buffer<f32, Interleaved> =
+            FromBytes::<Deinterleaved>::from_bytes::<LittleEndian>(&bytes[..], 2).unwrap();
+
+        assert_eq!(output.samples(), &[0.5, 0.25, -1.0, 1.0]);
+    }
 }
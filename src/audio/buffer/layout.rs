@@ -89,6 +89,55 @@ where
     }
 }
 
+/// Returns the `(channel, frame)` positions of an interleaved buffer's samples, in memory order.
+pub(crate) fn interleaved_positions(
+    channels: usize,
+    frames: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    (0..frames).flat_map(move |frame| (0..channels).map(move |channel| (channel, frame)))
+}
+
+/// Returns the `(channel, frame)` positions of a de-interleaved buffer's samples, in memory order.
+pub(crate) fn deinterleaved_positions(
+    channels: usize,
+    frames: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    (0..channels).flat_map(move |channel| (0..frames).map(move |frame| (channel, frame)))
+}
+
+/// Transposes a row-major matrix of `rows` rows and `columns` columns in-place,
+/// by following the cycles of the permutation, each starting from its smallest index.
+pub(crate) fn transpose_in_place<T>(samples: &mut [T], rows: usize, columns: usize) {
+    assert_eq!(samples.len(), rows * columns);
+
+    if rows <= 1 || columns <= 1 {
+        return;
+    }
+
+    let len = samples.len();
+    // The element at `index` belongs at `target(index)`:
+    let target = |index: usize| ((index % columns) * rows) + (index / columns);
+
+    // The first and last elements never move:
+    for start in 1..(len - 1) {
+        // Only the cycle's leader, i.e. its smallest index, moves its elements:
+        let mut next = target(start);
+        while next > start {
+            next = target(next);
+        }
+        if next < start {
+            continue;
+        }
+
+        // Keep swapping the element at `start` into its place, until `start`'s own element arrives:
+        let mut next = target(start);
+        while next != start {
+            samples.swap(start, next);
+            next = target(next);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,4 +225,38 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn transpose_in_place() {
+        let mut samples: Vec<_> = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14];
+        let channels = 3;
+        let frames = samples.len() / channels;
+
+        super::transpose_in_place(&mut samples[..], frames, channels);
+
+        let actual = samples;
+        let expected = vec![0, 3, 6, 9, 12, 1, 4, 7, 10, 13, 2, 5, 8, 11, 14];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn transpose_in_place_matches_deinterleaved() {
+        for channels in 1..=8 {
+            for frames in 1..=33 {
+                let input: Vec<usize> = (0..(channels * frames)).collect();
+                let mut expected = vec![0; input.len()];
+                deinterleaved(&input[..], &mut expected[..], channels);
+
+                let mut actual = input;
+                super::transpose_in_place(&mut actual[..], frames, channels);
+
+                assert_eq!(
+                    actual, expected,
+                    "channels: {}, frames: {}",
+                    channels, frames
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,429 @@
+//! In-place processing of a buffer's samples.
+//!
+//! All processors operate on `f32` representations of the samples, converting back and forth
+//! via [`RawSample`], and hence work with any sample type and either layout.
+
+use crate::audio::{
+    buffer::{layout::BufferLayout, Buffer},
+    RawSample,
+};
+
+// The width of the soft limiter's knee around its threshold, in dB:
+const SOFT_LIMITER_KNEE_DB: f32 = 6.0;
+
+/// A linear gain factor.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct Gain(f32);
+
+impl Gain {
+    pub const UNITY: Self = Self(1.0);
+    pub const MUTE: Self = Self(0.0);
+
+    pub fn from_linear(linear: f32) -> Self {
+        Self(linear)
+    }
+
+    pub fn from_db(db: f32) -> Self {
+        Self(10.0_f32.powf(db / 20.0))
+    }
+
+    /// Creates a gain from an Ogg Opus header's output gain,
+    /// a signed Q7.8 fixed-point value in dB.
+    ///
+    /// See: <https://tools.ietf.org/html/rfc7845#section-5.1>
+    pub fn from_opus_output_gain(output_gain: u16) -> Self {
+        Self::from_db(f32::from(output_gain as i16) / 256.0)
+    }
+
+    pub fn linear(&self) -> f32 {
+        self.0
+    }
+
+    pub fn db(&self) -> f32 {
+        20.0 * self.0.log10()
+    }
+}
+
+impl Default for Gain {
+    fn default() -> Self {
+        Self::UNITY
+    }
+}
+
+/// Multiplies all samples with `gain`, saturating at the sample type's range.
+pub fn apply_gain<T, L>(buffer: &mut Buffer<T, L>, gain: Gain)
+where
+    T: RawSample,
+    L: BufferLayout,
+{
+    process_frames(buffer, |_, frame| {
+        for sample in frame.iter_mut() {
+            *sample *= gain.0;
+        }
+    });
+}
+
+/// Replaces all samples with silence.
+pub fn mute<T, L>(buffer: &mut Buffer<T, L>)
+where
+    T: RawSample,
+    L: BufferLayout,
+{
+    process_frames(buffer, |_, frame| {
+        for sample in frame.iter_mut() {
+            *sample = 0.0;
+        }
+    });
+}
+
+/// Applies a gain ramping linearly from `start` at the first frame to `end` at the last frame.
+pub fn ramp<T, L>(buffer: &mut Buffer<T, L>, start: Gain, end: Gain)
+where
+    T: RawSample,
+    L: BufferLayout,
+{
+    let steps = buffer.info().frames().saturating_sub(1).max(1) as f32;
+    let step = (end.0 - start.0) / steps;
+
+    process_frames(buffer, |index, frame| {
+        let gain = start.0 + (step * index as f32);
+        for sample in frame.iter_mut() {
+            *sample *= gain;
+        }
+    });
+}
+
+/// Fades in from silence across the entire buffer.
+pub fn fade_in<T, L>(buffer: &mut Buffer<T, L>)
+where
+    T: RawSample,
+    L: BufferLayout,
+{
+    ramp(buffer, Gain::MUTE, Gain::UNITY);
+}
+
+/// Fades out to silence across the entire buffer.
+pub fn fade_out<T, L>(buffer: &mut Buffer<T, L>)
+where
+    T: RawSample,
+    L: BufferLayout,
+{
+    ramp(buffer, Gain::UNITY, Gain::MUTE);
+}
+
+/// A stateful gain stage, which smoothly ramps towards any newly set gain
+/// (or silence, when muted) over a fixed number of frames, avoiding audible clicks.
+#[derive(Clone, Debug)]
+pub struct Fader {
+    gain: Gain,
+    is_muted: bool,
+    // The gain applied to the most recently processed frame:
+    current: f32,
+    // The linear change in gain per frame while ramping:
+    step: f32,
+    ramp_frames: usize,
+}
+
+impl Fader {
+    pub fn new(gain: Gain, ramp_frames: usize) -> Self {
+        Self {
+            gain,
+            is_muted: false,
+            current: gain.0,
+            step: 0.0,
+            ramp_frames: ramp_frames.max(1),
+        }
+    }
+
+    pub fn gain(&self) -> Gain {
+        self.gain
+    }
+
+    pub fn set_gain(&mut self, gain: Gain) {
+        self.gain = gain;
+        self.retarget();
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.is_muted
+    }
+
+    pub fn set_muted(&mut self, is_muted: bool) {
+        self.is_muted = is_muted;
+        self.retarget();
+    }
+
+    pub fn process<T, L>(&mut self, buffer: &mut Buffer<T, L>)
+    where
+        T: RawSample,
+        L: BufferLayout,
+    {
+        let target = self.target();
+
+        process_frames(buffer, |_, frame| {
+            if self.current != target {
+                self.current += self.step;
+                let has_reached_target = (self.step > 0.0 && self.current >= target)
+                    || (self.step < 0.0 && self.current <= target)
+                    || self.step == 0.0;
+                if has_reached_target {
+                    self.current = target;
+                }
+            }
+
+            for sample in frame.iter_mut() {
+                *sample *= self.current;
+            }
+        });
+    }
+
+    fn target(&self) -> f32 {
+        if self.is_muted {
+            0.0
+        } else {
+            self.gain.0
+        }
+    }
+
+    fn retarget(&mut self) {
+        self.step = (self.target() - self.current) / (self.ramp_frames as f32);
+    }
+}
+
+/// A peak limiter keeping the absolute level of all samples at or below a threshold.
+///
+/// Gain reduction eases in across a 6 dB knee centered on the threshold,
+/// reaching the threshold at its top, and sets in instantly when a frame's peak rises.
+/// It then recovers exponentially, leaving quieter passages untouched.
+#[derive(Clone, Debug)]
+pub struct SoftLimiter {
+    threshold: f32,
+    // Per-frame decay factor of the envelope:
+    release: f32,
+    envelope: f32,
+}
+
+impl SoftLimiter {
+    /// Creates a limiter whose gain reduction recovers by a factor of `1 / e` every `release_frames` frames.
+    pub fn new(threshold: Gain, release_frames: usize) -> Self {
+        assert!(threshold.0 > 0.0);
+
+        Self {
+            threshold: threshold.0,
+            release: (-1.0 / (release_frames.max(1) as f32)).exp(),
+            envelope: 0.0,
+        }
+    }
+
+    pub fn threshold(&self) -> Gain {
+        Gain(self.threshold)
+    }
+
+    /// Returns the gain reduction applied to the most recently processed frame.
+    pub fn reduction(&self) -> Gain {
+        Gain(self.reduction_factor())
+    }
+
+    pub fn reset(&mut self) {
+        self.envelope = 0.0;
+    }
+
+    pub fn process<T, L>(&mut self, buffer: &mut Buffer<T, L>)
+    where
+        T: RawSample,
+        L: BufferLayout,
+    {
+        process_frames(buffer, |_, frame| {
+            let peak = frame
+                .iter()
+                .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+            self.envelope = peak.max(self.envelope * self.release);
+
+            let reduction = self.reduction_factor();
+            for sample in frame.iter_mut() {
+                *sample *= reduction;
+            }
+        });
+    }
+
+    fn reduction_factor(&self) -> f32 {
+        let overshoot = 20.0 * (self.envelope / self.threshold).log10();
+
+        if 2.0 * overshoot <= -SOFT_LIMITER_KNEE_DB {
+            1.0
+        } else if 2.0 * overshoot < SOFT_LIMITER_KNEE_DB {
+            // A quadratic transition from no reduction to limiting at the threshold:
+            let reduction =
+                (overshoot + (SOFT_LIMITER_KNEE_DB / 2.0)).powi(2) / (2.0 * SOFT_LIMITER_KNEE_DB);
+            Gain::from_db(-reduction).linear()
+        } else {
+            self.threshold / self.envelope
+        }
+    }
+}
+
+// Calls `f` with the index and the `f32` samples of each frame, writing back any changes.
+fn process_frames<T, L, F>(buffer: &mut Buffer<T, L>, mut f: F)
+where
+    T: RawSample,
+    L: BufferLayout,
+    F: FnMut(usize, &mut [f32]),
+{
+    let info = buffer.info();
+    let samples = buffer.samples_mut();
+
+    let mut frame = vec![0.0_f32; info.channels()];
+
+    for index in 0..info.frames() {
+        for (channel, sample) in frame.iter_mut().enumerate() {
+            let raw = samples[L::index_of(&info, channel, index)];
+            *sample = raw.to_f32();
+        }
+
+        f(index, &mut frame);
+
+        for (channel, &sample) in frame.iter().enumerate() {
+            samples[L::index_of(&info, channel, index)] = T::from_f32(sample);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nearly_eq::assert_nearly_eq;
+
+    use crate::audio::buffer::layout::{Deinterleaved, Interleaved};
+
+    use super::*;
+
+    #[test]
+    fn gain() {
+        assert_nearly_eq!(Gain::from_db(0.0).linear(), 1.0);
+        assert_nearly_eq!(Gain::from_db(-6.0).linear(), 0.501_187_2);
+        assert_nearly_eq!(Gain::from_linear(0.1).db(), -20.0, 1e-5);
+        assert_eq!(Gain::MUTE.db(), f32::NEG_INFINITY);
+
+        assert_nearly_eq!(Gain::from_opus_output_gain(256).db(), 1.0, 1e-5);
+        assert_nearly_eq!(
+            Gain::from_opus_output_gain(-512_i16 as u16).db(),
+            -2.0,
+            1e-5
+        );
+    }
+
+    #[test]
+    fn apply_gain_i16() {
+        let mut buffer = Buffer::<i16, Interleaved>::new(vec![1000, -1000, 30000, -30000], 2);
+
+        apply_gain(&mut buffer, Gain::from_linear(2.0));
+
+        assert_eq!(buffer.samples(), &[2000, -2000, i16::MAX, i16::MIN]);
+    }
+
+    #[test]
+    fn apply_gain_f32() {
+        let mut buffer = Buffer::<f32, Deinterleaved>::new(vec![0.5, -0.25], 1);
+
+        apply_gain(&mut buffer, Gain::from_linear(0.5));
+
+        assert_eq!(buffer.samples(), &[0.25, -0.125]);
+    }
+
+    #[test]
+    fn unity_gain_is_lossless() {
+        let samples: Vec<i16> = (i16::MIN..=i16::MAX).step_by(7).collect();
+        let mut buffer = Buffer::<i16, Interleaved>::new(samples.clone(), 1);
+
+        apply_gain(&mut buffer, Gain::UNITY);
+
+        assert_eq!(buffer.samples(), &samples[..]);
+    }
+
+    #[test]
+    fn mute() {
+        let mut buffer = Buffer::<i16, Interleaved>::new(vec![1, -2, 3, -4], 2);
+        super::mute(&mut buffer);
+        assert_eq!(buffer.samples(), &[0; 4]);
+    }
+
+    #[test]
+    fn fades() {
+        let mut buffer = Buffer::<f32, Deinterleaved>::new(vec![1.0; 10], 2);
+        fade_in(&mut buffer);
+        assert_nearly_eq!(
+            buffer.samples(),
+            &[0.0, 0.25, 0.5, 0.75, 1.0, 0.0, 0.25, 0.5, 0.75, 1.0][..]
+        );
+
+        let mut buffer = Buffer::<f32, Interleaved>::new(vec![1.0; 10], 2);
+        fade_out(&mut buffer);
+        assert_nearly_eq!(
+            buffer.samples(),
+            &[1.0, 1.0, 0.75, 0.75, 0.5, 0.5, 0.25, 0.25, 0.0, 0.0][..]
+        );
+    }
+
+    #[test]
+    fn fader() {
+        let mut subject = Fader::new(Gain::UNITY, 4);
+
+        let mut buffer = Buffer::<f32, Interleaved>::new(vec![1.0; 2], 1);
+        subject.process(&mut buffer);
+        assert_eq!(buffer.samples(), &[1.0, 1.0]);
+
+        subject.set_muted(true);
+
+        // The ramp spans multiple buffers:
+        let mut buffer = Buffer::<f32, Interleaved>::new(vec![1.0; 2], 1);
+        subject.process(&mut buffer);
+        assert_nearly_eq!(buffer.samples(), &[0.75, 0.5][..]);
+
+        let mut buffer = Buffer::<f32, Interleaved>::new(vec![1.0; 4], 1);
+        subject.process(&mut buffer);
+        assert_nearly_eq!(buffer.samples(), &[0.25, 0.0, 0.0, 0.0][..]);
+
+        subject.set_muted(false);
+        subject.set_gain(Gain::from_linear(0.5));
+
+        let mut buffer = Buffer::<f32, Interleaved>::new(vec![1.0; 6], 1);
+        subject.process(&mut buffer);
+        assert_nearly_eq!(buffer.samples(), &[0.125, 0.25, 0.375, 0.5, 0.5, 0.5][..]);
+    }
+
+    #[test]
+    fn soft_limiter() {
+        let mut subject = SoftLimiter::new(Gain::from_linear(0.5), 10);
+
+        let mut buffer = Buffer::<f32, Interleaved>::new(vec![0.25, -0.25, 1.0, -0.5, 0.5, 0.5], 2);
+        subject.process(&mut buffer);
+
+        let samples = buffer.samples();
+        // Quiet frames pass untouched:
+        assert_eq!(&samples[..2], &[0.25, -0.25]);
+        // Loud frames get reduced to the threshold, across all channels:
+        assert_eq!(&samples[2..4], &[0.5, -0.25]);
+        // Gain reduction recovers gradually:
+        assert!(samples[4] > 0.25 && samples[4] < 0.5);
+        assert!(subject.reduction().linear() < 1.0);
+
+        assert!(samples.iter().all(|sample| sample.abs() <= 0.5));
+
+        subject.reset();
+        assert_eq!(subject.reduction(), Gain::UNITY);
+    }
+
+    #[test]
+    fn soft_limiter_knee() {
+        let mut subject = SoftLimiter::new(Gain::UNITY, 1);
+
+        // Peaks at the threshold get reduced softly by an eighth of the knee:
+        let mut buffer = Buffer::<f32, Interleaved>::new(vec![1.0], 1);
+        subject.process(&mut buffer);
+        assert_nearly_eq!(subject.reduction().db(), -0.75, 0.001);
+
+        // Overs of float samples get limited rather than clamped:
+        let mut buffer = Buffer::<f32, Interleaved>::new(vec![4.0, -2.0], 2);
+        subject.process(&mut buffer);
+        assert_nearly_eq!(buffer.samples(), &[1.0, -0.5][..]);
+    }
+}
@@ -0,0 +1,316 @@
+//! Re-framing of audio streams, e.g. for turning a decoder's 20 ms frames into 10 ms frames.
+
+use std::collections::VecDeque;
+
+use crate::audio::{
+    buffer::{layout::BufferLayout, Buffer, BufferInfo, BufferRef},
+    RawSample,
+};
+
+// The default maximum gap between pushed buffers, in frames (i.e. 1 s at 48 kHz):
+const DEFAULT_MAX_GAP: u64 = 48_000;
+
+/// The result of popping frames from a [`Fifo`].
+#[derive(PartialEq, Clone, Debug)]
+pub struct Popped<T, L> {
+    /// The timestamp of the buffer's first frame.
+    pub timestamp: u64,
+    pub buffer: Buffer<T, L>,
+    /// The number of trailing frames that had to be filled with silence due to an underrun.
+    pub missing_frames: usize,
+}
+
+/// A first-in, first-out queue of frames, accepting and emitting buffers of arbitrary frame counts.
+///
+/// Timestamps are expressed in frames, i.e. in units of the stream's sample rate
+/// (such as extended RTP timestamps, or a sample clock), with the timestamp of the first
+/// pending frame being tracked across pushes and pops.
+#[derive(Clone, Debug)]
+pub struct Fifo<T> {
+    channels: usize,
+    // Interleaved samples:
+    samples: VecDeque<T>,
+    silence: T,
+    timestamp: Option<u64>,
+    max_gap: u64,
+    underrun_frames: u64,
+}
+
+impl<T> Fifo<T>
+where
+    T: RawSample,
+{
+    pub fn new(channels: usize) -> Self {
+        assert!(channels > 0);
+
+        let silence = T::from_f32(0.0);
+
+        Self {
+            channels,
+            samples: VecDeque::new(),
+            silence,
+            timestamp: None,
+            max_gap: DEFAULT_MAX_GAP,
+            underrun_frames: 0,
+        }
+    }
+
+    /// Sets the number of frames up to which gaps between buffers get filled with silence,
+    /// which defaults to 48000 (i.e. 1 s at 48 kHz).
+    pub fn with_max_gap(mut self, frames: u64) -> Self {
+        self.max_gap = frames;
+        self
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Returns the number of pending frames.
+    pub fn frames(&self) -> usize {
+        self.samples.len() / self.channels
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Returns the timestamp of the next frame to be popped,
+    /// or `None` if nothing has been pushed yet.
+    pub fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
+
+    /// Returns the total number of frames that had to be filled with silence due to underruns.
+    pub fn underrun_frames(&self) -> u64 {
+        self.underrun_frames
+    }
+
+    /// Queues `buffer`, with its first frame starting at `timestamp`.
+    ///
+    /// Gaps between buffers (e.g. due to packet loss) get filled with silence,
+    /// frames preceding the next frame to be popped arrived too late and get dropped,
+    /// and frames overlapping already pending ones replace them.
+    ///
+    /// Gaps larger than the maximum gap (e.g. due to a timestamp jump) are treated as a
+    /// discontinuity instead: Pending frames get discarded, the next frame to be popped
+    /// becomes `buffer`'s first one, and the gap gets counted as underrun.
+    /// The same goes for buffers arriving later than the maximum gap (e.g. due to a backward jump),
+    /// just without any underrun.
+    pub fn push<L>(&mut self, timestamp: u64, buffer: BufferRef<'_, T, L>)
+    where
+        L: BufferLayout,
+    {
+        let channels = self.channels;
+        let info = buffer.info();
+        let samples = buffer.samples();
+
+        assert_eq!(info.channels(), channels);
+
+        let mut start = *self.timestamp.get_or_insert(timestamp);
+
+        let gap = timestamp.saturating_sub(start.saturating_add(self.frames() as u64));
+        let lateness = start.saturating_sub(timestamp);
+        if gap > self.max_gap || lateness > self.max_gap {
+            self.samples.clear();
+            self.timestamp = Some(timestamp);
+            self.underrun_frames += gap;
+            start = timestamp;
+        }
+
+        let skipped = start.saturating_sub(timestamp) as usize;
+        if skipped >= info.frames() {
+            return;
+        }
+        let offset = (timestamp.saturating_sub(start) as usize) * channels;
+
+        let end = offset + ((info.frames() - skipped) * channels);
+        if self.samples.len() < end {
+            self.samples.resize(end, self.silence);
+        }
+
+        for frame in skipped..info.frames() {
+            for channel in 0..channels {
+                let index = offset + ((frame - skipped) * channels) + channel;
+                self.samples[index] = samples[L::index_of(&info, channel, frame)];
+            }
+        }
+    }
+
+    /// Pops exactly `frames` frames, filling any missing ones with silence.
+    ///
+    /// Returns `None` if nothing has been pushed yet, and hence there is no timestamp to start from.
+    pub fn pop<L>(&mut self, frames: usize) -> Option<Popped<T, L>>
+    where
+        L: BufferLayout,
+    {
+        let timestamp = self.timestamp?;
+
+        let channels = self.channels;
+        let available = self.frames().min(frames);
+        let missing_frames = frames - available;
+
+        let info = BufferInfo::<L>::new(channels, frames);
+        let mut samples = vec![self.silence; frames * channels];
+
+        for (index, sample) in self.samples.drain(..(available * channels)).enumerate() {
+            let (frame, channel) = (index / channels, index % channels);
+            samples[L::index_of(&info, channel, frame)] = sample;
+        }
+
+        self.timestamp = Some(timestamp + frames as u64);
+        self.underrun_frames += missing_frames as u64;
+
+        Some(Popped {
+            timestamp,
+            buffer: Buffer::new(samples, channels),
+            missing_frames,
+        })
+    }
+
+    /// Discards all pending frames, as well as the timestamp.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.timestamp = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::audio::buffer::layout::{Deinterleaved, Interleaved};
+
+    use super::*;
+
+    #[test]
+    fn reframing() {
+        let mut subject = Fifo::<i16>::new(1);
+        assert_eq!(subject.pop::<Interleaved>(2), None);
+
+        let input = Buffer::<i16, Interleaved>::new(vec![1, 2, 3, 4], 1);
+        subject.push(960, input.as_ref());
+        assert_eq!(subject.frames(), 4);
+
+        let first = subject.pop::<Interleaved>(3).unwrap();
+        assert_eq!(first.timestamp, 960);
+        assert_eq!(first.buffer.samples(), &[1, 2, 3]);
+        assert_eq!(first.missing_frames, 0);
+
+        let input = Buffer::<i16, Interleaved>::new(vec![5, 6, 7, 8], 1);
+        subject.push(964, input.as_ref());
+
+        let second = subject.pop::<Interleaved>(3).unwrap();
+        assert_eq!(second.timestamp, 963);
+        assert_eq!(second.buffer.samples(), &[4, 5, 6]);
+        assert_eq!(subject.timestamp(), Some(966));
+    }
+
+    #[test]
+    fn underrun() {
+        let mut subject = Fifo::<f32>::new(2);
+
+        let input = Buffer::<f32, Interleaved>::new(vec![0.1, 0.2, 0.3, 0.4], 2);
+        subject.push(0, input.as_ref());
+
+        let popped = subject.pop::<Deinterleaved>(3).unwrap();
+        assert_eq!(popped.buffer.samples(), &[0.1, 0.3, 0.0, 0.2, 0.4, 0.0]);
+        assert_eq!(popped.missing_frames, 1);
+        assert_eq!(subject.underrun_frames(), 1);
+
+        // The timestamp keeps advancing, so late data for the missing frame gets dropped:
+        let late = Buffer::<f32, Interleaved>::new(vec![0.5, 0.6, 0.7, 0.8], 2);
+        subject.push(2, late.as_ref());
+
+        let popped = subject.pop::<Interleaved>(1).unwrap();
+        assert_eq!(popped.timestamp, 3);
+        assert_eq!(popped.buffer.samples(), &[0.7, 0.8]);
+    }
+
+    #[test]
+    fn gap() {
+        let mut subject = Fifo::<u8>::new(1);
+
+        let input = Buffer::<u8, Interleaved>::new(vec![1, 2], 1);
+        subject.push(10, input.as_ref());
+        subject.push(14, input.as_ref());
+
+        // Silence is the sample type's mid-point, rather than zero:
+        let popped = subject.pop::<Interleaved>(6).unwrap();
+        assert_eq!(popped.buffer.samples(), &[1, 2, 128, 128, 1, 2]);
+        assert_eq!(popped.missing_frames, 0);
+    }
+
+    #[test]
+    fn discontinuity() {
+        let mut subject = Fifo::<i16>::new(1).with_max_gap(4);
+
+        let input = Buffer::<i16, Interleaved>::new(vec![1, 2], 1);
+        subject.push(10, input.as_ref());
+        subject.push(16, input.as_ref());
+        assert_eq!(subject.frames(), 8);
+        assert_eq!(subject.underrun_frames(), 0);
+
+        // Rather than filling the gap, the fifo starts over at the jump:
+        subject.push(1_000_000_000, input.as_ref());
+        assert_eq!(subject.frames(), 2);
+        assert_eq!(subject.underrun_frames(), 1_000_000_000 - 18);
+
+        let popped = subject.pop::<Interleaved>(2).unwrap();
+        assert_eq!(popped.timestamp, 1_000_000_000);
+        assert_eq!(popped.buffer.samples(), &[1, 2]);
+    }
+
+    #[test]
+    fn backward_discontinuity() {
+        let mut subject = Fifo::<i16>::new(1).with_max_gap(4);
+
+        let input = Buffer::<i16, Interleaved>::new(vec![1, 2], 1);
+        subject.push(1_000, input.as_ref());
+        subject.pop::<Interleaved>(2).unwrap();
+
+        // Slightly late frames still get dropped:
+        subject.push(999, input.as_ref());
+        assert_eq!(subject.frames(), 0);
+
+        // Rather than dropping everything from now on, the fifo starts over at the jump:
+        let input = Buffer::<i16, Interleaved>::new(vec![3, 4], 1);
+        subject.push(10, input.as_ref());
+        assert_eq!(subject.frames(), 2);
+        assert_eq!(subject.underrun_frames(), 0);
+
+        let popped = subject.pop::<Interleaved>(2).unwrap();
+        assert_eq!(popped.timestamp, 10);
+        assert_eq!(popped.buffer.samples(), &[3, 4]);
+
+        subject.push(12, input.as_ref());
+        assert_eq!(
+            subject.pop::<Interleaved>(2).unwrap().buffer.samples(),
+            &[3, 4]
+        );
+    }
+
+    #[test]
+    fn layouts() {
+        let mut subject = Fifo::<i16>::new(2);
+
+        let input = Buffer::<i16, Deinterleaved>::new(vec![1, 2, 3, 4, 5, 6], 2);
+        subject.push(0, input.as_ref());
+
+        let popped = subject.pop::<Interleaved>(3).unwrap();
+        assert_eq!(popped.buffer.samples(), &[1, 4, 2, 5, 3, 6]);
+    }
+
+    #[test]
+    fn reset() {
+        let mut subject = Fifo::<i16>::new(1);
+
+        let input = Buffer::<i16, Interleaved>::new(vec![1, 2], 1);
+        subject.push(10, input.as_ref());
+        subject.reset();
+
+        assert!(subject.is_empty());
+        assert_eq!(subject.timestamp(), None);
+
+        subject.push(100, input.as_ref());
+        assert_eq!(subject.pop::<Interleaved>(2).unwrap().timestamp, 100);
+    }
+}
@@ -0,0 +1,307 @@
+//! Level metering, as used for e.g. active-speaker detection and
+//! the client-to-mixer audio level RTP header extension.
+
+use rtp::extension::audio_level_extension::AudioLevelExtension;
+
+use crate::audio::{
+    buffer::{layout::BufferLayout, BufferRef},
+    RawSample,
+};
+
+/// The lowest level representable by an audio level header extension, in dBov.
+pub const MIN_DBOV: f32 = -127.0;
+
+/// Per-channel peak and RMS levels of a number of frames,
+/// relative to full scale (i.e. within `0.0..=1.0`).
+#[derive(PartialEq, Clone, Debug)]
+pub struct Levels {
+    peaks: Vec<f32>,
+    rms: Vec<f32>,
+}
+
+impl Levels {
+    pub fn measure<T, L>(buffer: BufferRef<'_, T, L>) -> Self
+    where
+        T: RawSample,
+        L: BufferLayout,
+    {
+        let mut accumulator = Accumulator::new(buffer.info().channels());
+        accumulator.accumulate(&buffer, 0..buffer.info().frames());
+        accumulator.levels()
+    }
+
+    pub fn channels(&self) -> usize {
+        self.peaks.len()
+    }
+
+    pub fn peak(&self, channel: usize) -> f32 {
+        self.peaks[channel]
+    }
+
+    pub fn peaks(&self) -> &[f32] {
+        &self.peaks[..]
+    }
+
+    pub fn rms(&self, channel: usize) -> f32 {
+        self.rms[channel]
+    }
+
+    pub fn rms_levels(&self) -> &[f32] {
+        &self.rms[..]
+    }
+
+    /// Returns the level of the average power across all channels,
+    /// in decibels relative to the overload point (i.e. a full-scale square wave).
+    ///
+    /// See: <https://tools.ietf.org/html/rfc6464#section-3>
+    pub fn dbov(&self) -> f32 {
+        if self.rms.is_empty() {
+            return f32::NEG_INFINITY;
+        }
+
+        let power = self.rms.iter().map(|rms| rms * rms).sum::<f32>() / (self.rms.len() as f32);
+        10.0 * power.log10()
+    }
+
+    /// Returns the level as used by the audio level header extension, i.e. as `-dBov`,
+    /// from `0` (i.e. 0 dBov) to `127` (i.e. -127 dBov or lower, such as for silence).
+    ///
+    /// See: <https://tools.ietf.org/html/rfc6464#section-3>
+    pub fn audio_level(&self) -> u8 {
+        dbov_to_audio_level(self.dbov())
+    }
+
+    pub fn to_audio_level_extension(&self, voice: bool) -> AudioLevelExtension {
+        AudioLevelExtension {
+            level: self.audio_level(),
+            voice,
+        }
+    }
+}
+
+/// Converts a level in dBov to the audio level header extension's `-dBov` representation.
+pub fn dbov_to_audio_level(dbov: f32) -> u8 {
+    if dbov.is_nan() {
+        return -MIN_DBOV as u8;
+    }
+
+    (-dbov).round().clamp(0.0, -MIN_DBOV) as u8
+}
+
+/// A streaming meter, emitting levels integrated over consecutive windows of a fixed number of frames,
+/// regardless of the sizes of the buffers being fed into it.
+#[derive(Clone, Debug)]
+pub struct Meter {
+    window: usize,
+    accumulator: Accumulator,
+}
+
+impl Meter {
+    pub fn new(channels: usize, window: usize) -> Self {
+        assert!(channels > 0);
+        assert!(window > 0);
+
+        Self {
+            window,
+            accumulator: Accumulator::new(channels),
+        }
+    }
+
+    pub fn channels(&self) -> usize {
+        self.accumulator.peaks.len()
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the levels of each window completed by `buffer`.
+    pub fn push<T, L>(&mut self, buffer: BufferRef<'_, T, L>) -> Vec<Levels>
+    where
+        T: RawSample,
+        L: BufferLayout,
+    {
+        assert_eq!(buffer.info().channels(), self.channels());
+
+        let frames = buffer.info().frames();
+        let mut levels = vec![];
+
+        let mut start = 0;
+        while start < frames {
+            let remaining = self.window - self.accumulator.frames;
+            let end = frames.min(start + remaining);
+
+            self.accumulator.accumulate(&buffer, start..end);
+
+            if self.accumulator.frames == self.window {
+                levels.push(self.accumulator.levels());
+                self.accumulator.reset();
+            }
+
+            start = end;
+        }
+
+        levels
+    }
+
+    /// Discards the current, incomplete window.
+    pub fn reset(&mut self) {
+        self.accumulator.reset();
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Accumulator {
+    peaks: Vec<f32>,
+    squares: Vec<f64>,
+    frames: usize,
+}
+
+impl Accumulator {
+    fn new(channels: usize) -> Self {
+        Self {
+            peaks: vec![0.0; channels],
+            squares: vec![0.0; channels],
+            frames: 0,
+        }
+    }
+
+    fn accumulate<T, L>(&mut self, buffer: &BufferRef<'_, T, L>, frames: std::ops::Range<usize>)
+    where
+        T: RawSample,
+        L: BufferLayout,
+    {
+        let info = buffer.info();
+        let samples = buffer.samples();
+
+        for channel in 0..info.channels() {
+            for frame in frames.clone() {
+                let raw = samples[L::index_of(&info, channel, frame)];
+                let sample = raw.to_f32();
+
+                self.peaks[channel] = self.peaks[channel].max(sample.abs());
+                self.squares[channel] += f64::from(sample) * f64::from(sample);
+            }
+        }
+
+        self.frames += frames.len();
+    }
+
+    fn levels(&self) -> Levels {
+        let frames = self.frames.max(1) as f64;
+        let rms = self
+            .squares
+            .iter()
+            .map(|squares| (squares / frames).sqrt() as f32)
+            .collect();
+
+        Levels {
+            peaks: self.peaks.clone(),
+            rms,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.peaks.iter_mut().for_each(|peak| *peak = 0.0);
+        self.squares.iter_mut().for_each(|squares| *squares = 0.0);
+        self.frames = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nearly_eq::assert_nearly_eq;
+
+    use crate::audio::{
+        buffer::layout::{Deinterleaved, Interleaved},
+        buffer::Buffer,
+    };
+
+    use super::*;
+
+    fn sine(frames: usize, amplitude: f32) -> Vec<f32> {
+        (0..frames)
+            .map(|i| amplitude * (2.0 * std::f32::consts::PI * (i as f32) / 48.0).sin())
+            .collect()
+    }
+
+    #[test]
+    fn measure() {
+        let buffer = Buffer::<f32, Interleaved>::new(vec![0.5, -0.25, -1.0, 0.25], 2);
+
+        let levels = Levels::measure(buffer.as_ref());
+
+        assert_eq!(levels.channels(), 2);
+        assert_eq!(levels.peaks(), &[1.0, 0.25]);
+        assert_nearly_eq!(levels.rms(0), (0.625_f32).sqrt());
+        assert_nearly_eq!(levels.rms(1), 0.25);
+    }
+
+    #[test]
+    fn layouts_are_equivalent() {
+        let interleaved =
+            Buffer::<i16, Interleaved>::new((0..96).map(|i| i * 300 - 9000).collect(), 2);
+        let deinterleaved = Buffer::<i16, Deinterleaved>::from(interleaved.clone());
+
+        assert_eq!(
+            Levels::measure(interleaved.as_ref()),
+            Levels::measure(deinterleaved.as_ref())
+        );
+    }
+
+    #[test]
+    fn dbov() {
+        // A full-scale square wave is at the overload point:
+        let square = Buffer::<f32, Interleaved>::new(vec![1.0, -1.0, 1.0, -1.0], 1);
+        let levels = Levels::measure(square.as_ref());
+        assert_nearly_eq!(levels.dbov(), 0.0);
+        assert_eq!(levels.audio_level(), 0);
+
+        // A full-scale sine wave is ~3 dB below:
+        let full_scale = Buffer::<f32, Interleaved>::new(sine(480, 1.0), 1);
+        assert_eq!(Levels::measure(full_scale.as_ref()).audio_level(), 3);
+
+        let quiet = Buffer::<f32, Interleaved>::new(sine(480, 0.01), 1);
+        assert_eq!(Levels::measure(quiet.as_ref()).audio_level(), 43);
+
+        let silence = Buffer::<i16, Interleaved>::new(vec![0; 480], 1);
+        let levels = Levels::measure(silence.as_ref());
+        assert_eq!(levels.dbov(), f32::NEG_INFINITY);
+        assert_eq!(levels.audio_level(), 127);
+
+        let extension = levels.to_audio_level_extension(false);
+        assert_eq!(extension.level, 127);
+        assert!(!extension.voice);
+    }
+
+    #[test]
+    fn dbov_to_audio_level() {
+        assert_eq!(super::dbov_to_audio_level(3.0), 0);
+        assert_eq!(super::dbov_to_audio_level(-42.4), 42);
+        assert_eq!(super::dbov_to_audio_level(-200.0), 127);
+        assert_eq!(super::dbov_to_audio_level(f32::NAN), 127);
+    }
+
+    #[test]
+    fn meter() {
+        let mut subject = Meter::new(1, 4);
+
+        let first = Buffer::<f32, Interleaved>::new(vec![0.5, 0.5, 0.5], 1);
+        assert_eq!(subject.push(first.as_ref()), vec![]);
+
+        let second = Buffer::<f32, Interleaved>::new(vec![0.5, 1.0, 1.0, 1.0, 1.0, 0.25], 1);
+        let levels = subject.push(second.as_ref());
+
+        assert_eq!(levels.len(), 2);
+        assert_eq!(levels[0].peaks(), &[0.5]);
+        assert_eq!(levels[0].rms_levels(), &[0.5]);
+        assert_eq!(levels[1].peaks(), &[1.0]);
+        assert_eq!(levels[1].rms_levels(), &[1.0]);
+
+        // Resetting discards the pending trailing frame:
+        subject.reset();
+        let third = Buffer::<f32, Interleaved>::new(vec![0.125; 4], 1);
+        let levels = subject.push(third.as_ref());
+        assert_eq!(levels[0].peaks(), &[0.125]);
+    }
+}
@@ -0,0 +1,460 @@
+//! Mixing of multiple audio streams into a single one.
+//!
+//! Each input's buffers get placed on a shared timeline via their timestamps, which are expressed
+//! in frames (i.e. in units of the common sample rate all inputs are expected to have been resampled to).
+//! The mixer then emits fixed-size frames of the weighted sum of all inputs, treating missing input as silence.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+use thiserror::Error;
+
+use crate::audio::{
+    buffer::{layout::BufferLayout, Buffer, BufferInfo, BufferRef},
+    simd, RawSample,
+};
+
+// The absolute level above which the soft clipping's knee starts to attenuate the mix:
+const SOFT_CLIP_THRESHOLD: f32 = 0.75;
+
+// The default look-ahead, in output frames (i.e. 1 s for 20 ms frames):
+const DEFAULT_MAX_LOOKAHEAD_MIXES: usize = 50;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("Unknown input")]
+    UnknownInput,
+    #[error("Unexpected number of channels: (expected: {expected}, actual: {actual})")]
+    UnexpectedChannels { expected: usize, actual: usize },
+    #[error("Timestamp too far ahead: (ahead: {ahead}, max: {max})")]
+    TooFarAhead { ahead: u64, max: usize },
+}
+
+#[derive(Clone, Debug)]
+struct Input {
+    gain: f32,
+    // Interleaved samples, with the first frame being aligned to the mixer's timestamp:
+    pending: VecDeque<f32>,
+}
+
+/// A mixer for combining multiple inputs (e.g. the participants of a conference),
+/// identified by `Id`, into fixed-size frames.
+///
+/// For a sample rate of 48 kHz an output frame size of `480` frames corresponds to 10 ms,
+/// one of `960` frames to 20 ms.
+#[derive(Clone, Debug)]
+pub struct Mixer<Id> {
+    channels: usize,
+    frames: usize,
+    timestamp: u64,
+    max_lookahead: usize,
+    inputs: HashMap<Id, Input>,
+}
+
+impl<Id> Mixer<Id>
+where
+    Id: Eq + Hash + Clone,
+{
+    /// Creates a mixer emitting frames of `frames` frames with `channels` channels each,
+    /// with the first one starting at `timestamp`.
+    ///
+    /// Buffers may be pushed up to 50 output frames ahead, see [`Mixer::with_max_lookahead`].
+    pub fn new(channels: usize, frames: usize, timestamp: u64) -> Self {
+        assert!(channels > 0);
+        assert!(frames > 0);
+
+        Self {
+            channels,
+            frames,
+            timestamp,
+            max_lookahead: DEFAULT_MAX_LOOKAHEAD_MIXES * frames,
+            inputs: HashMap::new(),
+        }
+    }
+
+    /// Sets how many frames past the next frame to be mixed a pushed buffer may start,
+    /// which bounds the silence buffered to fill the gap up to it.
+    pub fn with_max_lookahead(mut self, frames: usize) -> Self {
+        self.max_lookahead = frames;
+        self
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Returns the timestamp of the next frame to be mixed.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Adds an input with a linear `gain`, replacing any existing input with the same `id`.
+    pub fn add_input(&mut self, id: Id, gain: f32) {
+        let input = Input {
+            gain,
+            pending: VecDeque::new(),
+        };
+        self.inputs.insert(id, input);
+    }
+
+    /// Removes an input, discarding its pending samples.
+    ///
+    /// Returns `false` if there was no such input.
+    pub fn remove_input(&mut self, id: &Id) -> bool {
+        self.inputs.remove(id).is_some()
+    }
+
+    pub fn gain(&self, id: &Id) -> Option<f32> {
+        self.inputs.get(id).map(|input| input.gain)
+    }
+
+    pub fn set_gain(&mut self, id: &Id, gain: f32) -> Result<(), Error> {
+        let input = self.inputs.get_mut(id).ok_or(Error::UnknownInput)?;
+        input.gain = gain;
+        Ok(())
+    }
+
+    /// Returns the number of frames pending for an input, counting from the next frame to be mixed.
+    pub fn pending_frames(&self, id: &Id) -> Option<usize> {
+        self.inputs
+            .get(id)
+            .map(|input| input.pending.len() / self.channels)
+    }
+
+    /// Queues `buffer` for an input, with its first frame starting at `timestamp`.
+    ///
+    /// Frames preceding the next frame to be mixed arrived too late and get dropped,
+    /// gaps between buffers get filled with silence, and frames overlapping already
+    /// pending ones replace them.
+    ///
+    /// Buffers starting more than the maximum look-ahead past the next frame to be mixed
+    /// (e.g. due to a timestamp jump) are rejected with [`Error::TooFarAhead`].
+    pub fn push<T, L>(
+        &mut self,
+        id: &Id,
+        timestamp: u64,
+        buffer: BufferRef<'_, T, L>,
+    ) -> Result<(), Error>
+    where
+        T: RawSample,
+        L: BufferLayout,
+    {
+        let channels = self.channels;
+        let info = buffer.info();
+        let samples = buffer.samples();
+
+        if info.channels() != channels {
+            return Err(Error::UnexpectedChannels {
+                expected: channels,
+                actual: info.channels(),
+            });
+        }
+
+        let input = self.inputs.get_mut(id).ok_or(Error::UnknownInput)?;
+
+        let ahead = timestamp.saturating_sub(self.timestamp);
+        if ahead > self.max_lookahead as u64 {
+            return Err(Error::TooFarAhead {
+                ahead,
+                max: self.max_lookahead,
+            });
+        }
+
+        let skipped = self.timestamp.saturating_sub(timestamp);
+        if skipped >= info.frames() as u64 {
+            return Ok(());
+        }
+        let skipped = skipped as usize;
+        let offset = (ahead as usize) * channels;
+
+        let end = offset + ((info.frames() - skipped) * channels);
+        if input.pending.len() < end {
+            input.pending.resize(end, 0.0);
+        }
+
+        for frame in skipped..info.frames() {
+            for channel in 0..channels {
+                let raw = samples[L::index_of(&info, channel, frame)];
+                let sample = raw.to_f32();
+                input.pending[offset + ((frame - skipped) * channels) + channel] = sample;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Mixes the next frame, advancing the mixer's timestamp by one frame.
+    pub fn mix(&mut self) -> Mix<Id> {
+        let samples = self.frames * self.channels;
+
+        let mut total = vec![0.0; samples];
+        let mut contributions = HashMap::with_capacity(self.inputs.len());
+
+        for (id, input) in self.inputs.iter_mut() {
+            let available = input.pending.len().min(samples);
+
+            let mut contribution = vec![0.0; samples];
+            for (contribution, sample) in contribution
+                .iter_mut()
+                .zip(input.pending.drain(..available))
+            {
+                *contribution = sample * input.gain;
+            }
+
+            for (total, contribution) in total.iter_mut().zip(&contribution) {
+                *total += contribution;
+            }
+
+            contributions.insert(id.clone(), contribution);
+        }
+
+        let mix = Mix {
+            timestamp: self.timestamp,
+            channels: self.channels,
+            total,
+            contributions,
+        };
+
+        self.timestamp += self.frames as u64;
+
+        mix
+    }
+}
+
+/// A single mixed frame, from which both the complete mix and
+/// per-input "mix-minus" outputs (i.e. the mix excluding an input's own contribution) can be obtained.
+///
+/// Clipping protection gets applied to each output, by softly compressing peaks
+/// above an absolute level of `0.75` into the remaining headroom.
+#[derive(Clone, Debug)]
+pub struct Mix<Id> {
+    timestamp: u64,
+    channels: usize,
+    // Interleaved samples:
+    total: Vec<f32>,
+    contributions: HashMap<Id, Vec<f32>>,
+}
+
+impl<Id> Mix<Id>
+where
+    Id: Eq + Hash,
+{
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Returns the mix of all inputs.
+    pub fn output<T, L>(&self) -> Buffer<T, L>
+    where
+        T: RawSample,
+        L: BufferLayout,
+    {
+        self.to_buffer(|index| self.total[index])
+    }
+
+    /// Returns the mix of all inputs but the one identified by `id`.
+    ///
+    /// For unknown inputs this is equivalent to [`Mix::output`].
+    pub fn output_without<T, L>(&self, id: &Id) -> Buffer<T, L>
+    where
+        T: RawSample,
+        L: BufferLayout,
+    {
+        match self.contributions.get(id) {
+            Some(contribution) => self.to_buffer(|index| self.total[index] - contribution[index]),
+            None => self.output(),
+        }
+    }
+
+    fn to_buffer<T, L, F>(&self, f: F) -> Buffer<T, L>
+    where
+        T: RawSample,
+        L: BufferLayout,
+        F: Fn(usize) -> f32,
+    {
+        let frames = self.total.len() / self.channels;
+        let info = BufferInfo::<L>::new(self.channels, frames);
+
+        let mut output = vec![0.0_f32; self.total.len()];
+        for frame in 0..frames {
+            for channel in 0..self.channels {
+                output[L::index_of(&info, channel, frame)] =
+                    soft_clip(f((frame * self.channels) + channel));
+            }
+        }
+
+        let samples = simd::from_f32_vec(output);
+
+        Buffer::new(samples, self.channels)
+    }
+}
+
+fn soft_clip(sample: f32) -> f32 {
+    let magnitude = sample.abs();
+    if magnitude <= SOFT_CLIP_THRESHOLD {
+        return sample;
+    }
+
+    // Continuous (and smooth) at the threshold, while asymptotically approaching full scale:
+    let headroom = 1.0 - SOFT_CLIP_THRESHOLD;
+    let compressed =
+        SOFT_CLIP_THRESHOLD + (headroom * ((magnitude - SOFT_CLIP_THRESHOLD) / headroom).tanh());
+
+    compressed.copysign(sample)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::audio::buffer::layout::{Deinterleaved, Interleaved};
+
+    use super::*;
+
+    fn mixer() -> Mixer<&'static str> {
+        let mut mixer = Mixer::new(1, 4, 100);
+        mixer.add_input("alice", 1.0);
+        mixer.add_input("bob", 0.5);
+        mixer
+    }
+
+    #[test]
+    fn mix() {
+        let mut subject = mixer();
+
+        let alice = Buffer::<f32, Interleaved>::new(vec![0.1, 0.2, 0.3, 0.4], 1);
+        let bob = Buffer::<f32, Interleaved>::new(vec![0.2, 0.2, 0.2, 0.2], 1);
+        subject.push(&"alice", 100, alice.as_ref()).unwrap();
+        subject.push(&"bob", 100, bob.as_ref()).unwrap();
+
+        let mix = subject.mix();
+        assert_eq!(mix.timestamp(), 100);
+        assert_eq!(subject.timestamp(), 104);
+
+        let output: Buffer<f32, Interleaved> = mix.output();
+        nearly_eq::assert_nearly_eq!(output.samples(), &[0.2, 0.3, 0.4, 0.5][..]);
+    }
+
+    #[test]
+    fn mix_minus() {
+        let mut subject = mixer();
+
+        let alice = Buffer::<f32, Interleaved>::new(vec![0.1; 4], 1);
+        let bob = Buffer::<f32, Interleaved>::new(vec![0.4; 4], 1);
+        subject.push(&"alice", 100, alice.as_ref()).unwrap();
+        subject.push(&"bob", 100, bob.as_ref()).unwrap();
+
+        let mix = subject.mix();
+
+        let for_alice: Buffer<f32, Interleaved> = mix.output_without(&"alice");
+        let for_bob: Buffer<f32, Interleaved> = mix.output_without(&"bob");
+        nearly_eq::assert_nearly_eq!(for_alice.samples(), &[0.2; 4][..]);
+        nearly_eq::assert_nearly_eq!(for_bob.samples(), &[0.1; 4][..]);
+    }
+
+    #[test]
+    fn alignment() {
+        let mut subject = mixer();
+
+        // Partially late, partially in the next frame:
+        let early = Buffer::<f32, Interleaved>::new(vec![0.1, 0.2, 0.3], 1);
+        subject.push(&"alice", 99, early.as_ref()).unwrap();
+        // Leaving a gap, extending into the next frame:
+        let late = Buffer::<f32, Interleaved>::new(vec![0.5, 0.6], 1);
+        subject.push(&"alice", 103, late.as_ref()).unwrap();
+        assert_eq!(subject.pending_frames(&"alice"), Some(5));
+
+        let first: Buffer<f32, Interleaved> = subject.mix().output();
+        assert_eq!(first.samples(), &[0.2, 0.3, 0.0, 0.5]);
+
+        let second: Buffer<f32, Interleaved> = subject.mix().output();
+        assert_eq!(second.samples(), &[0.6, 0.0, 0.0, 0.0]);
+
+        // Entirely late:
+        subject.push(&"alice", 100, early.as_ref()).unwrap();
+        assert_eq!(subject.pending_frames(&"alice"), Some(0));
+    }
+
+    #[test]
+    fn layouts() {
+        let mut subject = Mixer::new(2, 2, 0);
+        subject.add_input(1, 1.0);
+
+        let input = Buffer::<i16, Deinterleaved>::new(vec![1000, 2000, -1000, -2000], 2);
+        subject.push(&1, 0, input.as_ref()).unwrap();
+
+        let mix = subject.mix();
+        let interleaved: Buffer<i16, Interleaved> = mix.output();
+        let deinterleaved: Buffer<i16, Deinterleaved> = mix.output();
+
+        assert_eq!(interleaved.samples(), &[1000, -1000, 2000, -2000]);
+        assert_eq!(deinterleaved, input);
+    }
+
+    #[test]
+    fn clipping_protection() {
+        let mut subject = mixer();
+        subject.set_gain(&"bob", 1.0).unwrap();
+
+        let loud = Buffer::<f32, Interleaved>::new(vec![0.9, -0.9, 0.5, 0.2], 1);
+        subject.push(&"alice", 100, loud.as_ref()).unwrap();
+        subject.push(&"bob", 100, loud.as_ref()).unwrap();
+
+        let output: Buffer<f32, Interleaved> = subject.mix().output();
+        let samples = output.samples();
+
+        assert!(samples[0] > 0.75 && samples[0] < 1.0);
+        assert_eq!(samples[1], -samples[0]);
+        assert!(samples[2] > 0.75 && samples[2] < samples[0]);
+        assert_eq!(samples[3], 0.4);
+    }
+
+    #[test]
+    fn errors() {
+        let mut subject = mixer();
+
+        let mono = Buffer::<f32, Interleaved>::new(vec![0.0; 4], 1);
+        let stereo = Buffer::<f32, Interleaved>::new(vec![0.0; 4], 2);
+
+        assert_eq!(
+            subject.push(&"carol", 100, mono.as_ref()),
+            Err(Error::UnknownInput)
+        );
+        assert_eq!(
+            subject.push(&"alice", 100, stereo.as_ref()),
+            Err(Error::UnexpectedChannels {
+                expected: 1,
+                actual: 2
+            })
+        );
+        assert_eq!(subject.set_gain(&"carol", 1.0), Err(Error::UnknownInput));
+
+        assert!(subject.remove_input(&"bob"));
+        assert!(!subject.remove_input(&"bob"));
+        assert_eq!(subject.gain(&"bob"), None);
+    }
+
+    #[test]
+    fn lookahead() {
+        let mut subject = mixer().with_max_lookahead(8);
+
+        let buffer = Buffer::<f32, Interleaved>::new(vec![1.0; 4], 1);
+
+        assert_eq!(subject.push(&"alice", 108, buffer.as_ref()), Ok(()));
+        assert_eq!(
+            subject.push(&"alice", 109, buffer.as_ref()),
+            Err(Error::TooFarAhead { ahead: 9, max: 8 })
+        );
+        assert_eq!(
+            subject.push(&"bob", u64::MAX, buffer.as_ref()),
+            Err(Error::TooFarAhead {
+                ahead: u64::MAX - 100,
+                max: 8
+            })
+        );
+    }
+}
@@ -1,7 +1,17 @@
 pub mod buffer;
+pub mod dsp;
+pub mod fifo;
+pub mod level;
+pub mod mixer;
+pub mod remix;
+pub mod requantize;
+pub mod resample;
 mod sample;
+pub mod simd;
+pub mod vad;
 
-pub use sample::Sample;
+pub use fifo::Fifo;
+pub use sample::{I24RangeError, RawSample, Sample, I24};
 
 mod sealed {
     pub trait Sealed {}
@@ -0,0 +1,276 @@
+//! Channel remixing, i.e. up- and down-mixing of multi-channel audio.
+
+use thiserror::Error;
+
+use crate::audio::{
+    buffer::{layout::BufferLayout, Buffer, BufferInfo, BufferRef},
+    simd, RawSample,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("Invalid number of coefficients: (expected: {expected}, actual: {actual})")]
+    InvalidCoefficients { expected: usize, actual: usize },
+    #[error("Unexpected number of channels: (expected: {expected}, actual: {actual})")]
+    UnexpectedChannels { expected: usize, actual: usize },
+    #[error("A channel matrix needs at least one input and one output channel")]
+    NoChannels,
+}
+
+/// A matrix mapping a buffer's input channels onto output channels,
+/// with each output channel being a weighted sum of the input channels.
+///
+/// Multi-channel layouts are expected to follow the common (WAVE/SMPTE) channel order:
+/// left, right, center, low-frequency effects, left surround, right surround.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ChannelMatrix {
+    inputs: usize,
+    outputs: usize,
+    // Row-major, i.e. one row of `inputs` coefficients per output channel:
+    coefficients: Vec<f32>,
+}
+
+impl ChannelMatrix {
+    /// Creates a matrix from rows of `inputs` coefficients, one row per output channel.
+    pub fn new(inputs: usize, outputs: usize, coefficients: Vec<f32>) -> Result<Self, Error> {
+        if inputs == 0 || outputs == 0 {
+            return Err(Error::NoChannels);
+        }
+
+        let expected = inputs * outputs;
+        if coefficients.len() != expected {
+            return Err(Error::InvalidCoefficients {
+                expected,
+                actual: coefficients.len(),
+            });
+        }
+
+        Ok(Self {
+            inputs,
+            outputs,
+            coefficients,
+        })
+    }
+
+    pub fn identity(channels: usize) -> Result<Self, Error> {
+        let coefficients = (0..channels)
+            .flat_map(|output| (0..channels).map(move |input| (input == output) as u8 as f32))
+            .collect();
+
+        Self::new(channels, channels, coefficients)
+    }
+
+    /// Duplicates a mono channel into both stereo channels.
+    pub fn mono_to_stereo() -> Self {
+        Self {
+            inputs: 1,
+            outputs: 2,
+            coefficients: vec![1.0, 1.0],
+        }
+    }
+
+    /// Averages both stereo channels into a mono channel.
+    pub fn stereo_to_mono() -> Self {
+        Self {
+            inputs: 2,
+            outputs: 1,
+            coefficients: vec![0.5, 0.5],
+        }
+    }
+
+    /// Down-mixes 5.1 surround to stereo, using the ITU-R BS.775 coefficients,
+    /// with the low-frequency effects channel getting dropped.
+    ///
+    /// As the coefficients of each output channel add up to more than `1.0`,
+    /// loud input may clip.
+    pub fn surround_5_1_to_stereo() -> Self {
+        const MINUS_3_DB: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+        #[rustfmt::skip]
+        let coefficients = vec![
+            // L    R    C           LFE  Ls          Rs
+            1.0, 0.0, MINUS_3_DB, 0.0, MINUS_3_DB, 0.0,
+            0.0, 1.0, MINUS_3_DB, 0.0, 0.0,        MINUS_3_DB,
+        ];
+
+        Self {
+            inputs: 6,
+            outputs: 2,
+            coefficients,
+        }
+    }
+
+    /// Returns a matrix for mixing `channels` channels to stereo,
+    /// if there is a standard one for said number of channels.
+    pub fn to_stereo(channels: usize) -> Option<Self> {
+        match channels {
+            1 => Some(Self::mono_to_stereo()),
+            2 => Self::identity(2).ok(),
+            6 => Some(Self::surround_5_1_to_stereo()),
+            _ => None,
+        }
+    }
+
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    pub fn outputs(&self) -> usize {
+        self.outputs
+    }
+
+    pub fn coefficient(&self, output: usize, input: usize) -> f32 {
+        assert!(output < self.outputs);
+        assert!(input < self.inputs);
+        self.coefficients[(output * self.inputs) + input]
+    }
+
+    /// Remixes the channels of `buffer` into a new buffer with the same layout.
+    pub fn remix<T, L>(&self, buffer: BufferRef<'_, T, L>) -> Result<Buffer<T, L>, Error>
+    where
+        T: RawSample,
+        L: BufferLayout,
+    {
+        let input_info = buffer.info();
+        let input = buffer.samples();
+
+        if input_info.channels() != self.inputs {
+            return Err(Error::UnexpectedChannels {
+                expected: self.inputs,
+                actual: input_info.channels(),
+            });
+        }
+
+        let frames = input_info.frames();
+        let output_info = BufferInfo::<L>::new(self.outputs, frames);
+
+        let mut output = vec![0.0_f32; self.outputs * frames];
+        let mut input_frame = vec![0.0_f32; self.inputs];
+
+        for frame in 0..frames {
+            for (channel, sample) in input_frame.iter_mut().enumerate() {
+                let raw = input[L::index_of(&input_info, channel, frame)];
+                *sample = raw.to_f32();
+            }
+
+            for (channel, row) in self.coefficients.chunks(self.inputs).enumerate() {
+                output[L::index_of(&output_info, channel, frame)] = row
+                    .iter()
+                    .zip(&input_frame)
+                    .map(|(coefficient, sample)| coefficient * sample)
+                    .sum();
+            }
+        }
+
+        let output = simd::from_f32_vec(output);
+
+        Ok(Buffer::new(output, self.outputs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::audio::buffer::layout::{Deinterleaved, Interleaved};
+
+    use super::*;
+
+    #[test]
+    fn new() {
+        assert!(ChannelMatrix::new(2, 3, vec![0.0; 6]).is_ok());
+        assert_eq!(
+            ChannelMatrix::new(2, 3, vec![0.0; 5]),
+            Err(Error::InvalidCoefficients {
+                expected: 6,
+                actual: 5
+            })
+        );
+        assert_eq!(ChannelMatrix::new(0, 2, vec![]), Err(Error::NoChannels));
+        assert_eq!(ChannelMatrix::new(2, 0, vec![]), Err(Error::NoChannels));
+    }
+
+    #[test]
+    fn identity() {
+        assert_eq!(ChannelMatrix::identity(0), Err(Error::NoChannels));
+
+        let subject = ChannelMatrix::identity(3).unwrap();
+        assert_eq!(subject.coefficient(1, 1), 1.0);
+        assert_eq!(subject.coefficient(1, 2), 0.0);
+
+        let buffer = Buffer::<f32, Interleaved>::new(vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6], 3);
+        assert_eq!(subject.remix(buffer.as_ref()), Ok(buffer));
+    }
+
+    #[test]
+    fn mono_to_stereo() {
+        let buffer = Buffer::<f32, Interleaved>::new(vec![0.1, -0.2], 1);
+
+        let actual = ChannelMatrix::mono_to_stereo()
+            .remix(buffer.as_ref())
+            .unwrap();
+
+        assert_eq!(actual.samples(), &[0.1, 0.1, -0.2, -0.2]);
+        assert_eq!(actual.info().channels(), 2);
+    }
+
+    #[test]
+    fn stereo_to_mono() {
+        let buffer = Buffer::<i16, Deinterleaved>::new(vec![100, 200, 300, 400], 2);
+
+        let actual = ChannelMatrix::stereo_to_mono()
+            .remix(buffer.as_ref())
+            .unwrap();
+
+        assert_eq!(actual.samples(), &[200, 300]);
+    }
+
+    #[test]
+    fn surround_5_1_to_stereo() {
+        // A single frame with only the center and LFE channels being active:
+        let buffer = Buffer::<f32, Interleaved>::new(vec![0.0, 0.0, 0.5, 1.0, 0.0, 0.0], 6);
+
+        let actual = ChannelMatrix::surround_5_1_to_stereo()
+            .remix(buffer.as_ref())
+            .unwrap();
+
+        let expected = 0.5 * std::f32::consts::FRAC_1_SQRT_2;
+        assert_eq!(actual.samples(), &[expected, expected]);
+    }
+
+    #[test]
+    fn layouts_are_equivalent() {
+        let samples: Vec<f32> = (0..60).map(|i| (i as f32 * 0.1).sin() * 0.3).collect();
+        let interleaved = Buffer::<f32, Interleaved>::new(samples, 6);
+        let deinterleaved = Buffer::<f32, Deinterleaved>::from(interleaved.clone());
+
+        let subject = ChannelMatrix::surround_5_1_to_stereo();
+
+        let expected = subject.remix(interleaved.as_ref()).unwrap();
+        let actual =
+            Buffer::<f32, Interleaved>::from(subject.remix(deinterleaved.as_ref()).unwrap());
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn unexpected_channels() {
+        let buffer = Buffer::<f32, Interleaved>::new(vec![0.0; 4], 2);
+
+        assert_eq!(
+            ChannelMatrix::mono_to_stereo().remix(buffer.as_ref()),
+            Err(Error::UnexpectedChannels {
+                expected: 1,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn to_stereo() {
+        for channels in [1, 2, 6] {
+            let subject = ChannelMatrix::to_stereo(channels).unwrap();
+            assert_eq!(subject.inputs(), channels);
+            assert_eq!(subject.outputs(), 2);
+        }
+        assert_eq!(ChannelMatrix::to_stereo(3), None);
+    }
+}
@@ -0,0 +1,278 @@
+//! Requantization of float samples to 16-bit integer samples.
+//!
+//! Reducing the bit-depth of a signal introduces a quantization error that is
+//! correlated with the signal, which is audible as distortion for quiet signals.
+//! Adding a small amount of random noise (i.e. dither) prior to quantizing
+//! decorrelates the error from the signal, turning it into a constant noise floor,
+//! which noise shaping then moves towards less audible (i.e. higher) frequencies.
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::audio::buffer::{layout::BufferLayout, Buffer, BufferRef};
+
+/// The strategy used for mapping float samples onto 16-bit integer samples.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Requantization {
+    /// Discards the fractional part, i.e. rounds towards zero.
+    Truncate,
+    /// Rounds to the nearest value.
+    Round,
+    /// Adds triangular (TPDF) dither of ±1 LSB, then rounds to the nearest value.
+    TpdfDither,
+    /// Adds triangular (TPDF) dither of ±1 LSB, then rounds to the nearest value,
+    /// while feeding the quantization error back through a second-order filter,
+    /// which shifts the noise floor towards higher frequencies.
+    NoiseShapedDither,
+}
+
+/// A stateful converter from float buffers to 16-bit integer buffers.
+///
+/// The dither's random number generator, as well as each channel's quantization
+/// error history get carried over from one call to the next, so that consecutive
+/// buffers of a stream get requantized as if they were a single buffer.
+#[derive(Clone, Debug)]
+pub struct Requantizer {
+    requantization: Requantization,
+    rng: StdRng,
+    // The two most recent quantization errors per channel, most recent first:
+    errors: Vec<[f32; 2]>,
+    clipped: usize,
+}
+
+impl Requantizer {
+    pub fn new(requantization: Requantization) -> Self {
+        Self::with_rng(requantization, StdRng::from_entropy())
+    }
+
+    /// Creates a requantizer whose dither is seeded with `seed`, making its output reproducible.
+    pub fn with_seed(requantization: Requantization, seed: u64) -> Self {
+        Self::with_rng(requantization, StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(requantization: Requantization, rng: StdRng) -> Self {
+        Self {
+            requantization,
+            rng,
+            errors: vec![],
+            clipped: 0,
+        }
+    }
+
+    pub fn requantization(&self) -> Requantization {
+        self.requantization
+    }
+
+    /// Returns the number of samples that had to be clipped since creation or the last reset.
+    pub fn clipped_samples(&self) -> usize {
+        self.clipped
+    }
+
+    /// Resets the quantization error history and the clipped samples count.
+    pub fn reset(&mut self) {
+        self.errors.clear();
+        self.clipped = 0;
+    }
+
+    /// Requantizes `buffer`, clipping samples outside of `-1.0..=1.0`.
+    pub fn requantize<L>(&mut self, buffer: BufferRef<'_, f32, L>) -> Buffer<i16, L>
+    where
+        L: BufferLayout,
+    {
+        let info = buffer.info();
+        let input = buffer.samples();
+        let channels = info.channels();
+
+        if self.errors.len() != channels {
+            self.errors = vec![[0.0; 2]; channels];
+        }
+
+        let mut output = vec![0; input.len()];
+
+        // Process each channel's samples in chronological order,
+        // as required for the error feedback:
+        for frame in 0..info.frames() {
+            for channel in 0..channels {
+                let index = L::index_of(&info, channel, frame);
+                output[index] = self.requantize_sample(input[index], channel);
+            }
+        }
+
+        Buffer::new(output, channels)
+    }
+
+    fn requantize_sample(&mut self, sample: f32, channel: usize) -> i16 {
+        // Dither may push full-scale samples past the limits, which is not clipping:
+        if sample.abs() > 1.0 {
+            self.clipped += 1;
+        }
+
+        let scaled = if sample < 0.0 {
+            sample * -(i16::MIN as f32)
+        } else {
+            sample * i16::MAX as f32
+        };
+
+        let quantized = match self.requantization {
+            Requantization::Truncate => scaled.trunc(),
+            Requantization::Round => scaled.round(),
+            Requantization::TpdfDither => (scaled + self.tpdf()).round(),
+            Requantization::NoiseShapedDither => {
+                let [e1, e2] = self.errors[channel];
+                // Noise transfer function: (1 - z^-1)^2
+                let shaped = scaled - (2.0 * e1 - e2);
+                let rounded = (shaped + self.tpdf()).round();
+                let quantized = rounded.clamp(i16::MIN as f32, i16::MAX as f32);
+                // Saturation errors aren't noise that can be shaped, and feeding them back
+                // would make the filter unstable, so the history starts over instead:
+                self.errors[channel] = if quantized == rounded {
+                    [quantized - shaped, e1]
+                } else {
+                    [0.0; 2]
+                };
+                quantized
+            }
+        };
+
+        // Float-to-int casts saturate:
+        quantized as i16
+    }
+
+    /// Returns triangularly distributed noise within `-1.0..1.0`.
+    fn tpdf(&mut self) -> f32 {
+        self.rng.gen::<f32>() - self.rng.gen::<f32>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::audio::buffer::layout::{Deinterleaved, Interleaved};
+
+    use super::*;
+
+    fn requantize(requantization: Requantization, samples: Vec<f32>) -> Vec<i16> {
+        let buffer: Buffer<f32, Interleaved> = Buffer::new(samples, 1);
+        let mut subject = Requantizer::with_seed(requantization, 42);
+        subject.requantize(buffer.as_ref()).samples().to_vec()
+    }
+
+    #[test]
+    fn truncate() {
+        let actual = requantize(Requantization::Truncate, vec![-1.0, -0.7, 0.0, 0.7, 1.0]);
+        assert_eq!(actual, vec![i16::MIN, -22937, 0, 22936, i16::MAX]);
+    }
+
+    #[test]
+    fn round() {
+        let actual = requantize(Requantization::Round, vec![-1.0, -0.7, 0.0, 0.7, 1.0]);
+        assert_eq!(actual, vec![i16::MIN, -22938, 0, 22937, i16::MAX]);
+    }
+
+    #[test]
+    fn tpdf_dither() {
+        let samples = vec![0.25; 10_000];
+        let scaled = 0.25 * i16::MAX as f32;
+
+        let actual = requantize(Requantization::TpdfDither, samples);
+
+        // TPDF dither never exceeds ±1 LSB:
+        assert!(actual
+            .iter()
+            .all(|&sample| (sample as f32 - scaled).abs() < 2.0));
+
+        // ... and is unbiased:
+        let mean = actual.iter().map(|&sample| sample as f64).sum::<f64>() / actual.len() as f64;
+        assert!((mean - scaled as f64).abs() < 0.05);
+    }
+
+    #[test]
+    fn noise_shaped_dither() {
+        // A DC offset of a fraction of an LSB, which plain rounding would erase:
+        let samples = vec![0.3 / i16::MAX as f32; 10_000];
+
+        let actual = requantize(Requantization::NoiseShapedDither, samples);
+
+        let mean = actual.iter().map(|&sample| sample as f64).sum::<f64>() / actual.len() as f64;
+        assert!((mean - 0.3).abs() < 0.05);
+    }
+
+    #[test]
+    fn state_is_kept_between_calls() {
+        let samples: Vec<f32> = (0..200).map(|i| (i as f32 * 0.1).sin() * 0.5).collect();
+
+        let mut subject = Requantizer::with_seed(Requantization::NoiseShapedDither, 42);
+        let whole = Buffer::<f32, Interleaved>::new(samples.clone(), 2);
+        let expected = subject.requantize(whole.as_ref()).samples().to_vec();
+
+        let mut subject = Requantizer::with_seed(Requantization::NoiseShapedDither, 42);
+        let first = Buffer::<f32, Interleaved>::new(samples[..100].to_vec(), 2);
+        let second = Buffer::<f32, Interleaved>::new(samples[100..].to_vec(), 2);
+        let mut actual = subject.requantize(first.as_ref()).samples().to_vec();
+        actual.extend_from_slice(subject.requantize(second.as_ref()).samples());
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn layouts_are_equivalent() {
+        let samples: Vec<f32> = (0..200).map(|i| (i as f32 * 0.1).sin() * 0.5).collect();
+
+        let interleaved = Buffer::<f32, Interleaved>::new(samples, 2);
+        let deinterleaved = Buffer::<f32, Deinterleaved>::from(interleaved.clone());
+
+        let mut subject = Requantizer::with_seed(Requantization::NoiseShapedDither, 42);
+        let expected = subject.requantize(interleaved.as_ref());
+
+        let mut subject = Requantizer::with_seed(Requantization::NoiseShapedDither, 42);
+        let actual = Buffer::<i16, Interleaved>::from(subject.requantize(deinterleaved.as_ref()));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn clipping() {
+        let buffer = Buffer::<f32, Interleaved>::new(vec![-2.0, 0.0, 1.5], 1);
+
+        let mut subject = Requantizer::with_seed(Requantization::Round, 42);
+        let actual = subject.requantize(buffer.as_ref());
+
+        assert_eq!(actual.samples(), &[i16::MIN, 0, i16::MAX]);
+        assert_eq!(subject.clipped_samples(), 2);
+
+        subject.reset();
+        assert_eq!(subject.clipped_samples(), 0);
+    }
+
+    #[test]
+    fn dithered_full_scale_is_not_clipping() {
+        for requantization in [
+            Requantization::TpdfDither,
+            Requantization::NoiseShapedDither,
+        ] {
+            let buffer = Buffer::<f32, Interleaved>::new(vec![1.0; 1000], 1);
+
+            let mut subject = Requantizer::with_seed(requantization, 42);
+            let actual = subject.requantize(buffer.as_ref());
+
+            assert!(actual
+                .samples()
+                .iter()
+                .all(|&sample| sample >= i16::MAX - 2));
+            assert_eq!(subject.clipped_samples(), 0);
+        }
+    }
+
+    #[test]
+    fn noise_shaped_dither_recovers_from_clipping() {
+        let mut samples = vec![1.5; 480];
+        samples.extend(vec![0.0; 48_000]);
+        let buffer = Buffer::<f32, Interleaved>::new(samples, 1);
+
+        let mut subject = Requantizer::with_seed(Requantization::NoiseShapedDither, 42);
+        let actual = subject.requantize(buffer.as_ref());
+
+        assert!(actual.samples()[480..]
+            .iter()
+            .all(|&sample| sample.abs() <= 4));
+        assert_eq!(subject.clipped_samples(), 480);
+    }
+}
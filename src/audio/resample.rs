@@ -0,0 +1,381 @@
+//! Sample-rate conversion.
+//!
+//! Resampling by a rational factor of `up / down` conceptually up-samples the signal by `up`,
+//! low-pass filters it and then down-samples it by `down`. A polyphase implementation only
+//! ever evaluates the filter at the output samples' positions, using one of `up` pre-computed
+//! sets of filter coefficients (i.e. phases), depending on the output sample's fractional
+//! position within the input signal.
+
+use crate::audio::{
+    buffer::{layout::BufferLayout, Buffer, BufferInfo, BufferRef},
+    simd, RawSample,
+};
+
+/// The interpolation used for computing output samples.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Interpolation {
+    /// Linear interpolation between the two nearest input samples.
+    ///
+    /// Cheap, but prone to aliasing and attenuating high frequencies.
+    Linear,
+    /// Blackman-windowed sinc interpolation, spanning `zero_crossings`
+    /// on either side of the output sample's position.
+    ///
+    /// When down-sampling the filter's cutoff gets lowered to the output's Nyquist frequency,
+    /// which widens the filter proportionally.
+    Sinc { zero_crossings: usize },
+}
+
+/// A streaming resampler for multi-channel audio.
+///
+/// Input buffers of arbitrary length can be passed in one after another,
+/// with each resulting output buffer containing as many frames as could be computed
+/// from the input received so far. Due to the filter's look-ahead an output frame only
+/// gets computed once [`Resampler::latency`] further input frames have been received.
+/// The output frames still pending at the end of a stream can be obtained via [`Resampler::flush`].
+#[derive(Clone, Debug)]
+pub struct Resampler {
+    up: usize,
+    down: usize,
+    // Number of taps on either side of an output sample's position:
+    half_taps: usize,
+    // `up` phases of `2 * half_taps` coefficients each:
+    coefficients: Vec<f32>,
+    // Not yet fully consumed input samples, per channel:
+    history: Vec<Vec<f32>>,
+    // The next output sample's position relative to the current window, in units of `1 / up` input samples:
+    position: usize,
+}
+
+impl Resampler {
+    pub fn new(input_rate: u32, output_rate: u32, interpolation: Interpolation) -> Self {
+        assert!(input_rate > 0);
+        assert!(output_rate > 0);
+
+        let divisor = gcd(input_rate, output_rate);
+        let up = (output_rate / divisor) as usize;
+        let down = (input_rate / divisor) as usize;
+
+        let (half_taps, kernel): (usize, Box<dyn Fn(f64) -> f64>) = match interpolation {
+            Interpolation::Linear => (1, Box::new(|distance: f64| 1.0 - distance.abs())),
+            Interpolation::Sinc { zero_crossings } => {
+                assert!(zero_crossings > 0);
+
+                let cutoff = (up as f64 / down as f64).min(1.0);
+                let half_taps = (zero_crossings as f64 / cutoff).ceil() as usize;
+                let half_width = half_taps as f64;
+
+                let kernel = move |distance: f64| {
+                    if distance.abs() >= half_width {
+                        return 0.0;
+                    }
+                    cutoff * sinc(cutoff * distance) * blackman(distance / half_width)
+                };
+                (half_taps, Box::new(kernel))
+            }
+        };
+
+        let taps = 2 * half_taps;
+        let mut coefficients = Vec::with_capacity(up * taps);
+
+        for phase in 0..up {
+            let fraction = phase as f64 / up as f64;
+            let start = coefficients.len();
+
+            // Tap `tap` gets applied to the input sample at offset `tap + 1 - half_taps`
+            // from the one preceding the output sample's position:
+            coefficients.extend(
+                (0..taps).map(|tap| kernel(fraction - (tap as f64 + 1.0 - half_taps as f64))),
+            );
+
+            // Normalize each phase to unity gain, so DC passes through unaltered:
+            let sum: f64 = coefficients[start..].iter().sum();
+            for coefficient in &mut coefficients[start..] {
+                *coefficient /= sum;
+            }
+        }
+
+        Self {
+            up,
+            down,
+            half_taps,
+            coefficients: coefficients.into_iter().map(|c| c as f32).collect(),
+            history: vec![],
+            position: 0,
+        }
+    }
+
+    /// The number of input frames that need to follow an output frame's position for it to be computed.
+    pub fn latency(&self) -> usize {
+        self.half_taps
+    }
+
+    /// Discards any buffered input.
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.position = 0;
+    }
+
+    /// Resamples `input`, returning as many output frames as can be computed so far.
+    ///
+    /// Passing a buffer with a different number of channels than the previous one resets `self`.
+    pub fn process<T, L>(&mut self, input: BufferRef<'_, T, L>) -> Buffer<T, L>
+    where
+        T: RawSample,
+        L: BufferLayout,
+    {
+        let info = input.info();
+        let samples = input.samples();
+
+        if self.history.len() != info.channels() {
+            // Pre-fill with silence, so that the first output frame is aligned with the first input frame:
+            self.history = vec![vec![0.0; self.half_taps - 1]; info.channels()];
+            self.position = 0;
+        }
+
+        for (channel, history) in self.history.iter_mut().enumerate() {
+            history.extend((0..info.frames()).map(|frame| {
+                let sample = samples[L::index_of(&info, channel, frame)];
+                sample.to_f32()
+            }));
+        }
+
+        self.resample(info.channels())
+    }
+
+    /// Feeds `self` with enough silence to flush out the output frames still pending due to latency.
+    ///
+    /// Afterwards `self` is reset, ready to resample a new stream.
+    pub fn flush<T, L>(&mut self) -> Buffer<T, L>
+    where
+        T: RawSample,
+        L: BufferLayout,
+    {
+        let padding = self.half_taps;
+        for history in self.history.iter_mut() {
+            history.resize(history.len() + padding, 0.0);
+        }
+
+        let output = self.resample(self.history.len());
+        self.reset();
+        output
+    }
+
+    fn resample<T, L>(&mut self, channels: usize) -> Buffer<T, L>
+    where
+        T: RawSample,
+        L: BufferLayout,
+    {
+        let taps = 2 * self.half_taps;
+        let available = self.history.first().map_or(0, |history| history.len());
+
+        // Each output frame's (window start, phase):
+        let mut windows = vec![];
+        while (self.position / self.up) + taps <= available {
+            windows.push((self.position / self.up, self.position % self.up));
+            self.position += self.down;
+        }
+
+        let frames = windows.len();
+        let info = BufferInfo::<L>::new(channels, frames);
+        let mut output = vec![0.0_f32; channels * frames];
+
+        for (channel, history) in self.history.iter().enumerate() {
+            for (frame, &(start, phase)) in windows.iter().enumerate() {
+                let window = &history[start..(start + taps)];
+                let coefficients = &self.coefficients[(phase * taps)..((phase + 1) * taps)];
+                let sample = window
+                    .iter()
+                    .zip(coefficients)
+                    .map(|(sample, coefficient)| sample * coefficient)
+                    .sum();
+                output[L::index_of(&info, channel, frame)] = sample;
+            }
+        }
+
+        // Drop the input samples that no future output frame depends on:
+        let consumed = (self.position / self.up).min(available);
+        for history in self.history.iter_mut() {
+            history.drain(..consumed);
+        }
+        self.position -= consumed * self.up;
+
+        let output = simd::from_f32_vec(output);
+
+        Buffer::new(output, channels.max(1))
+    }
+}
+
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        let x = std::f64::consts::PI * x;
+        x.sin() / x
+    }
+}
+
+/// The Blackman window, for `x` within `-1.0..=1.0`.
+fn blackman(x: f64) -> f64 {
+    let x = std::f64::consts::PI * x;
+    0.42 + 0.5 * x.cos() + 0.08 * (2.0 * x).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::audio::buffer::layout::{Deinterleaved, Interleaved};
+
+    use super::*;
+
+    const SINC: Interpolation = Interpolation::Sinc { zero_crossings: 16 };
+
+    fn sine(frequency: f64, rate: u32, frames: usize) -> Vec<f32> {
+        (0..frames)
+            .map(|frame| {
+                let time = frame as f64 / rate as f64;
+                (0.5 * (2.0 * std::f64::consts::PI * frequency * time).sin()) as f32
+            })
+            .collect()
+    }
+
+    fn resample_mono(subject: &mut Resampler, samples: &[f32], chunk_size: usize) -> Vec<f32> {
+        let mut output = vec![];
+        for chunk in samples.chunks(chunk_size) {
+            let buffer = Buffer::<f32, Interleaved>::new(chunk.to_vec(), 1);
+            output.extend_from_slice(subject.process(buffer.as_ref()).samples());
+        }
+        output.extend_from_slice(subject.flush::<f32, Interleaved>().samples());
+        output
+    }
+
+    #[test]
+    fn identity() {
+        let input = sine(1_000.0, 48_000, 1_000);
+
+        for interpolation in [Interpolation::Linear, SINC] {
+            let mut subject = Resampler::new(48_000, 48_000, interpolation);
+            let actual = resample_mono(&mut subject, &input, 100);
+
+            assert_eq!(actual.len(), input.len());
+            for (actual, expected) in actual.iter().zip(&input) {
+                assert!((actual - expected).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn flush_resets() {
+        let input = sine(440.0, 44_100, 4_410);
+
+        let mut subject = Resampler::new(44_100, 16_000, SINC);
+        let expected = resample_mono(&mut subject, &input, 441);
+        let actual = resample_mono(&mut subject, &input, 441);
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn frames() {
+        let input = vec![0.0; 44_100];
+
+        let mut subject = Resampler::new(44_100, 48_000, SINC);
+        let actual = resample_mono(&mut subject, &input, 441);
+
+        assert_eq!(actual.len(), 48_000);
+    }
+
+    #[test]
+    fn chunk_size_does_not_matter() {
+        let input = sine(440.0, 44_100, 4_410);
+
+        let mut subject = Resampler::new(44_100, 16_000, SINC);
+        let expected = resample_mono(&mut subject, &input, input.len());
+
+        for chunk_size in [1, 7, 128, 1_000] {
+            let mut subject = Resampler::new(44_100, 16_000, SINC);
+            let actual = resample_mono(&mut subject, &input, chunk_size);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn preserves_pass_band() {
+        let input = sine(1_000.0, 44_100, 44_100);
+        let expected = sine(1_000.0, 48_000, 48_000);
+
+        let mut subject = Resampler::new(44_100, 48_000, SINC);
+        let actual = resample_mono(&mut subject, &input, 512);
+
+        // Skip the edges, which are affected by the implicit silence around the input:
+        let max_error = actual[100..47_900]
+            .iter()
+            .zip(&expected[100..47_900])
+            .map(|(actual, expected)| (actual - expected).abs())
+            .fold(0.0, f32::max);
+        assert!(max_error < 1e-3, "max error: {}", max_error);
+    }
+
+    #[test]
+    fn attenuates_stop_band() {
+        // Above the output's Nyquist frequency of 8 kHz:
+        let input = sine(10_000.0, 48_000, 48_000);
+
+        let mut subject = Resampler::new(48_000, 16_000, SINC);
+        let actual = resample_mono(&mut subject, &input, 480);
+
+        let rms = (actual[100..15_900]
+            .iter()
+            .map(|sample| sample * sample)
+            .sum::<f32>()
+            / 15_800.0)
+            .sqrt();
+        assert!(rms < 1e-3, "rms: {}", rms);
+    }
+
+    #[test]
+    fn linear() {
+        let input = Buffer::<f32, Interleaved>::new(vec![0.0, 1.0, 0.0, -1.0], 1);
+
+        let mut subject = Resampler::new(1, 2, Interpolation::Linear);
+        let mut actual = subject.process(input.as_ref()).samples().to_vec();
+        actual.extend_from_slice(subject.flush::<f32, Interleaved>().samples());
+
+        assert_eq!(actual, vec![0.0, 0.5, 1.0, 0.5, 0.0, -0.5, -1.0, -0.5]);
+    }
+
+    #[test]
+    fn multi_channel_integer_samples() {
+        let left: Vec<i16> = (0..480).map(|i| (i * 10) as i16).collect();
+        let right: Vec<i16> = left.iter().map(|sample| -sample).collect();
+
+        let interleaved: Vec<i16> = left
+            .iter()
+            .zip(&right)
+            .flat_map(|(left, right)| [*left, *right])
+            .collect();
+        let interleaved = Buffer::<i16, Interleaved>::new(interleaved, 2);
+        let deinterleaved = Buffer::<i16, Deinterleaved>::from(interleaved.clone());
+
+        let mut subject = Resampler::new(48_000, 16_000, Interpolation::Linear);
+        let expected = subject.process(interleaved.as_ref());
+
+        let mut subject = Resampler::new(48_000, 16_000, Interpolation::Linear);
+        let actual = Buffer::<i16, Interleaved>::from(subject.process(deinterleaved.as_ref()));
+
+        assert_eq!(actual, expected);
+        assert_eq!(expected.info().channels(), 2);
+        assert_eq!(expected.info().frames(), 160);
+        for frame in expected.samples().chunks(2) {
+            assert_eq!(frame[0], -frame[1]);
+        }
+    }
+}
@@ -5,17 +5,38 @@ use byteorder::{ByteOrder, ReadBytesExt};
 #[cfg(test)]
 use nearly_eq::NearlyEq;
 
+mod i24;
+
+pub use i24::*;
+
+/// An audio sample.
+///
+/// Integer samples span their raw type's full range, with unsigned samples centered
+/// around the middle of said range (i.e. `128` for `u8`), while float samples span `-1.0..=1.0`.
+///
+/// Conversions between sample types map the minimum, zero and maximum values of one type
+/// onto the minimum, zero and maximum values of the other, scaling negative and positive
+/// values separately. As such converting to a wider type and back is lossless.
 #[derive(Eq, PartialEq, Copy, Clone, Default, Debug)]
 #[repr(transparent)]
 pub struct Sample<Raw>(Raw);
 
-impl From<i16> for Sample<i16> {
-    #[inline]
-    fn from(raw: i16) -> Self {
-        Self(raw)
-    }
+macro_rules! impl_from_raw_for_sample {
+    ($raw:ty) => {
+        impl From<$raw> for Sample<$raw> {
+            #[inline]
+            fn from(raw: $raw) -> Self {
+                Self(raw)
+            }
+        }
+    };
 }
 
+impl_from_raw_for_sample!(u8);
+impl_from_raw_for_sample!(i16);
+impl_from_raw_for_sample!(I24);
+impl_from_raw_for_sample!(i32);
+
 impl From<f32> for Sample<f32> {
     #[inline]
     fn from(raw: f32) -> Self {
@@ -23,6 +44,13 @@ impl From<f32> for Sample<f32> {
     }
 }
 
+impl From<f64> for Sample<f64> {
+    #[inline]
+    fn from(raw: f64) -> Self {
+        Self(raw.clamp(-1.0, 1.0))
+    }
+}
+
 macro_rules! impl_from_sample_for_raw {
     ($raw:ty) => {
         impl From<Sample<$raw>> for $raw {
@@ -34,52 +62,244 @@ macro_rules! impl_from_sample_for_raw {
     };
 }
 
+impl_from_sample_for_raw!(u8);
 impl_from_sample_for_raw!(i16);
+impl_from_sample_for_raw!(I24);
+impl_from_sample_for_raw!(i32);
 impl_from_sample_for_raw!(f32);
+impl_from_sample_for_raw!(f64);
 
-// impl From<Sample<i16>> for Sample<i64> {
-//     #[inline]
-//     fn from(sample: Sample<i16>) -> Self {
-//         // Fast but imprecise approach:
-//         // Perform crude but fast upsample by bit-shifting the raw value:
-//         Self::from((sample.0 as i64) << 16)
+/// An integer sample's raw type, mapped onto a signed range centered around zero.
+trait IntegerRaw: Copy {
+    const MIN: i64;
+    const MAX: i64;
 
-//         // Slow but precise approach:
-//         // Perform a proper but expensive lerp from
-//         // i16::MIN..i16::MAX to i32::MIN..i32::MAX:
+    fn to_i64(self) -> i64;
+    fn from_i64(value: i64) -> Self;
+}
 
-//         // let value = sample.0 as i64;
+impl IntegerRaw for u8 {
+    const MIN: i64 = i8::MIN as i64;
+    const MAX: i64 = i8::MAX as i64;
 
-//         // let from = if value <= 0 { i16::MIN } else { i16::MAX } as i64;
-//         // let to = if value <= 0 { i32::MIN } else { i32::MAX } as i64;
+    #[inline]
+    fn to_i64(self) -> i64 {
+        self as i64 - 128
+    }
+
+    #[inline]
+    fn from_i64(value: i64) -> Self {
+        (value + 128) as u8
+    }
+}
 
-//         // Self::from((value * to + from / 2) / from)
-//     }
-// }
+impl IntegerRaw for i16 {
+    const MIN: i64 = i16::MIN as i64;
+    const MAX: i64 = i16::MAX as i64;
 
-impl From<Sample<i16>> for Sample<f32> {
     #[inline]
-    fn from(sample: Sample<i16>) -> Self {
-        let divisor = if sample.0 < 0 {
-            i16::MIN as f32
-        } else {
-            i16::MAX as f32
-        }
-        .abs();
-        Self::from((sample.0 as f32) / divisor)
+    fn to_i64(self) -> i64 {
+        self as i64
+    }
+
+    #[inline]
+    fn from_i64(value: i64) -> Self {
+        value as i16
     }
 }
 
-impl From<Sample<f32>> for Sample<i16> {
+impl IntegerRaw for I24 {
+    const MIN: i64 = I24::MIN.to_i32() as i64;
+    const MAX: i64 = I24::MAX.to_i32() as i64;
+
+    #[inline]
+    fn to_i64(self) -> i64 {
+        self.to_i32() as i64
+    }
+
+    #[inline]
+    fn from_i64(value: i64) -> Self {
+        I24::from_i32_wrapping(value as i32)
+    }
+}
+
+impl IntegerRaw for i32 {
+    const MIN: i64 = i32::MIN as i64;
+    const MAX: i64 = i32::MAX as i64;
+
+    #[inline]
+    fn to_i64(self) -> i64 {
+        self as i64
+    }
+
+    #[inline]
+    fn from_i64(value: i64) -> Self {
+        value as i32
+    }
+}
+
+/// Rounds `value` to the nearest integer, with ties rounding towards zero.
+///
+/// Rounding (rather than truncating) makes integer-to-float-to-integer round-trips lossless,
+/// while rounding ties towards zero maps exact fractions like `0.5` onto `MAX / 2`.
+#[inline]
+fn round_half_towards_zero(value: f64) -> f64 {
+    (value.abs() - 0.5).ceil().max(0.0).copysign(value)
+}
+
+/// Rescales `value` from the range of `From` to the range of `To`,
+/// scaling negative and positive values separately.
+#[inline]
+fn rescale_integer<From, To>(value: From) -> To
+where
+    From: IntegerRaw,
+    To: IntegerRaw,
+{
+    let value = value.to_i64();
+    let (numerator, denominator) = if value < 0 {
+        (-To::MIN, -From::MIN)
+    } else {
+        (To::MAX, From::MAX)
+    };
+    // Widening the value to `i128` rules out overflows for any pair of integer types:
+    let scaled = value as i128 * numerator as i128;
+    let denominator = denominator as i128;
+    let rounded = if scaled < 0 {
+        (scaled - denominator / 2) / denominator
+    } else {
+        (scaled + denominator / 2) / denominator
+    };
+    To::from_i64(rounded as i64)
+}
+
+#[inline]
+fn integer_to_float<Raw>(value: Raw) -> f64
+where
+    Raw: IntegerRaw,
+{
+    let value = value.to_i64();
+    let divisor = if value < 0 { -Raw::MIN } else { Raw::MAX };
+    value as f64 / divisor as f64
+}
+
+#[inline]
+fn float_to_integer<Raw>(value: f64) -> Raw
+where
+    Raw: IntegerRaw,
+{
+    let value = value.clamp(-1.0, 1.0);
+    let multiplier = if value < 0.0 { -Raw::MIN } else { Raw::MAX };
+    Raw::from_i64(round_half_towards_zero(value * multiplier as f64) as i64)
+}
+
+macro_rules! impl_from_integer_sample_for_integer_sample {
+    ($from:ty => $($to:ty),+) => {
+        $(
+            impl From<Sample<$from>> for Sample<$to> {
+                #[inline]
+                fn from(sample: Sample<$from>) -> Self {
+                    Self::from(rescale_integer::<$from, $to>(sample.0))
+                }
+            }
+        )+
+    };
+}
+
+impl_from_integer_sample_for_integer_sample!(u8 => i16, I24, i32);
+impl_from_integer_sample_for_integer_sample!(i16 => u8, I24, i32);
+impl_from_integer_sample_for_integer_sample!(I24 => u8, i16, i32);
+impl_from_integer_sample_for_integer_sample!(i32 => u8, i16, I24);
+
+macro_rules! impl_float_conversions_for_integer_sample {
+    ($($raw:ty),+) => {
+        $(
+            impl From<Sample<$raw>> for Sample<f32> {
+                #[inline]
+                fn from(sample: Sample<$raw>) -> Self {
+                    Self::from(integer_to_float(sample.0) as f32)
+                }
+            }
+
+            impl From<Sample<$raw>> for Sample<f64> {
+                #[inline]
+                fn from(sample: Sample<$raw>) -> Self {
+                    Self::from(integer_to_float(sample.0))
+                }
+            }
+
+            impl From<Sample<f32>> for Sample<$raw> {
+                #[inline]
+                fn from(sample: Sample<f32>) -> Self {
+                    Self::from(float_to_integer::<$raw>(sample.0 as f64))
+                }
+            }
+
+            impl From<Sample<f64>> for Sample<$raw> {
+                #[inline]
+                fn from(sample: Sample<f64>) -> Self {
+                    Self::from(float_to_integer::<$raw>(sample.0))
+                }
+            }
+        )+
+    };
+}
+
+impl_float_conversions_for_integer_sample!(u8, i16, I24, i32);
+
+impl From<Sample<f32>> for Sample<f64> {
     #[inline]
     fn from(sample: Sample<f32>) -> Self {
-        let multiplier = if sample.0 < 0.0 {
-            i16::MIN as f32
-        } else {
-            i16::MAX as f32
-        }
-        .abs();
-        Self::from((sample.0 * multiplier) as i16)
+        Self::from(sample.0 as f64)
+    }
+}
+
+impl From<Sample<f64>> for Sample<f32> {
+    #[inline]
+    fn from(sample: Sample<f64>) -> Self {
+        Self::from(sample.0 as f32)
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A raw sample type convertible to and from `f32` samples, which the audio processing operates on.
+///
+/// Implemented for any raw type with conversions from and to [`Sample<f32>`].
+pub trait RawSample: Copy + 'static + private::Sealed {
+    /// Converts the raw sample to an `f32` one.
+    ///
+    /// Raw `f32` samples are passed through as-is, keeping any overs beyond `-1.0..=1.0`.
+    fn to_f32(self) -> f32;
+
+    /// Converts an `f32` sample, clamped to `-1.0..=1.0`, to a raw one.
+    fn from_f32(sample: f32) -> Self;
+}
+
+impl<T> private::Sealed for T
+where
+    T: Copy + 'static + From<Sample<T>>,
+    Sample<T>: From<Sample<f32>>,
+    Sample<f32>: From<Sample<T>>,
+{
+}
+
+impl<T> RawSample for T
+where
+    T: Copy + 'static + From<Sample<T>>,
+    Sample<T>: From<Sample<f32>>,
+    Sample<f32>: From<Sample<T>>,
+{
+    #[inline]
+    fn to_f32(self) -> f32 {
+        // Wraps the raw sample without `Sample::from`'s clamping of floats:
+        f32::from(Sample::<f32>::from(Sample(self)))
+    }
+
+    #[inline]
+    fn from_f32(sample: f32) -> Self {
+        T::from(Sample::<T>::from(<Sample<f32> as From<f32>>::from(sample)))
     }
 }
 
@@ -92,18 +312,44 @@ trait FromBytes: Sized {
     }
 }
 
+impl FromBytes for Sample<u8> {
+    fn from_reader<B: ByteOrder, R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        reader.read_u8().map(Self::from)
+    }
+}
+
 impl FromBytes for Sample<i16> {
     fn from_reader<B: ByteOrder, R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
         reader.read_i16::<B>().map(Self::from)
     }
 }
 
+impl FromBytes for Sample<I24> {
+    fn from_reader<B: ByteOrder, R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        reader
+            .read_i24::<B>()
+            .map(|raw| Self::from(I24::from_i32_wrapping(raw)))
+    }
+}
+
+impl FromBytes for Sample<i32> {
+    fn from_reader<B: ByteOrder, R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        reader.read_i32::<B>().map(Self::from)
+    }
+}
+
 impl FromBytes for Sample<f32> {
     fn from_reader<B: ByteOrder, R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
         reader.read_f32::<B>().map(Self::from)
     }
 }
 
+impl FromBytes for Sample<f64> {
+    fn from_reader<B: ByteOrder, R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
+        reader.read_f64::<B>().map(Self::from)
+    }
+}
+
 #[cfg(test)]
 impl<Raw> NearlyEq<Self, Raw> for Sample<Raw>
 where
@@ -171,6 +417,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sample_i16_from_sample_f32_rounds_to_nearest() {
+        // Truncating, as conversions used to, would give 0, 0, 100, 100 and -100:
+        let scenarios = [
+            (0.75 / 32767.0, 1),
+            (-0.75 / 32768.0, -1),
+            (100.6 / 32767.0, 101),
+            (100.4 / 32767.0, 100),
+            (-100.6 / 32768.0, -101),
+        ];
+
+        for (value, expected) in scenarios {
+            assert_nearly_eq!(
+                Sample::<i16>::from(Sample::<f32>::from(value as f32)),
+                Sample::from(expected as i16)
+            );
+        }
+    }
+
+    #[test]
+    fn raw_sample() {
+        assert_eq!(i16::MIN.to_f32(), -1.0);
+        assert_eq!(0_u8.to_f32(), -1.0);
+        assert_eq!(i16::from_f32(1.0), i16::MAX);
+        assert_eq!(i16::from_f32(-2.0), i16::MIN);
+        assert_eq!(f32::from_f32(2.0), 1.0);
+        assert_eq!(0.5_f32.to_f32(), 0.5);
+        assert_eq!(1.5_f32.to_f32(), 1.5);
+    }
+
     #[test]
     fn sample_f32_from_sample_i16() {
         assert_nearly_eq!(
@@ -195,4 +471,135 @@ mod tests {
             Sample::from(1.0)
         );
     }
+
+    #[test]
+    fn sample_f64_from_f64() {
+        assert_eq!(Sample::<f64>::from(-0.5).0, -0.5);
+        assert_eq!(Sample::<f64>::from(0.5).0, 0.5);
+
+        // For any values outside of -1.0..=1.0 we expect clamping:
+        assert_eq!(Sample::<f64>::from(f64::MIN).0, -1.0);
+        assert_eq!(Sample::<f64>::from(f64::MAX).0, 1.0);
+    }
+
+    #[test]
+    fn sample_integer_extremes() {
+        fn extremes<T>(min: T, zero: T, max: T) -> [Sample<T>; 3] {
+            [Sample(min), Sample(zero), Sample(max)]
+        }
+
+        let u8s = extremes(u8::MIN, 128, u8::MAX);
+        let i16s = extremes(i16::MIN, 0, i16::MAX);
+        let i24s = extremes(I24::MIN, I24::default(), I24::MAX);
+        let i32s = extremes(i32::MIN, 0, i32::MAX);
+
+        for i in 0..3 {
+            assert_eq!(Sample::<i16>::from(u8s[i]), i16s[i]);
+            assert_eq!(Sample::<I24>::from(u8s[i]), i24s[i]);
+            assert_eq!(Sample::<i32>::from(u8s[i]), i32s[i]);
+            assert_eq!(Sample::<u8>::from(i16s[i]), u8s[i]);
+            assert_eq!(Sample::<I24>::from(i16s[i]), i24s[i]);
+            assert_eq!(Sample::<i32>::from(i16s[i]), i32s[i]);
+            assert_eq!(Sample::<u8>::from(i24s[i]), u8s[i]);
+            assert_eq!(Sample::<i16>::from(i24s[i]), i16s[i]);
+            assert_eq!(Sample::<i32>::from(i24s[i]), i32s[i]);
+            assert_eq!(Sample::<u8>::from(i32s[i]), u8s[i]);
+            assert_eq!(Sample::<i16>::from(i32s[i]), i16s[i]);
+            assert_eq!(Sample::<I24>::from(i32s[i]), i24s[i]);
+        }
+
+        for (i, float) in [-1.0, 0.0, 1.0].iter().enumerate() {
+            assert_eq!(Sample::<u8>::from(Sample::<f64>::from(*float)), u8s[i]);
+            assert_eq!(Sample::<I24>::from(Sample::<f64>::from(*float)), i24s[i]);
+            assert_eq!(
+                Sample::<i32>::from(Sample::<f32>::from(*float as f32)),
+                i32s[i]
+            );
+            assert_eq!(Sample::<f64>::from(i32s[i]), Sample::from(*float));
+        }
+    }
+
+    #[test]
+    fn sample_integer_widening_is_lossless() {
+        for raw in u8::MIN..=u8::MAX {
+            let sample = Sample::from(raw);
+            assert_eq!(Sample::<u8>::from(Sample::<i16>::from(sample)), sample);
+            assert_eq!(Sample::<u8>::from(Sample::<I24>::from(sample)), sample);
+            assert_eq!(Sample::<u8>::from(Sample::<i32>::from(sample)), sample);
+        }
+
+        for raw in i16::MIN..=i16::MAX {
+            let sample = Sample::from(raw);
+            assert_eq!(Sample::<i16>::from(Sample::<I24>::from(sample)), sample);
+            assert_eq!(Sample::<i16>::from(Sample::<i32>::from(sample)), sample);
+        }
+
+        for raw in (I24::MIN.to_i32()..=I24::MAX.to_i32()).step_by(97) {
+            let sample = Sample::from(I24::from_i32_wrapping(raw));
+            assert_eq!(Sample::<I24>::from(Sample::<i32>::from(sample)), sample);
+        }
+    }
+
+    #[test]
+    fn sample_float_round_trip_is_lossless() {
+        for raw in i16::MIN..=i16::MAX {
+            let sample = Sample::from(raw);
+            assert_eq!(Sample::<i16>::from(Sample::<f32>::from(sample)), sample);
+            assert_eq!(Sample::<i16>::from(Sample::<f64>::from(sample)), sample);
+        }
+
+        for raw in (I24::MIN.to_i32()..=I24::MAX.to_i32()).step_by(97) {
+            let sample = Sample::from(I24::from_i32_wrapping(raw));
+            assert_eq!(Sample::<I24>::from(Sample::<f32>::from(sample)), sample);
+            assert_eq!(Sample::<I24>::from(Sample::<f64>::from(sample)), sample);
+        }
+
+        for raw in (i32::MIN..=i32::MAX).step_by(65_537) {
+            let sample = Sample::from(raw);
+            assert_eq!(Sample::<i32>::from(Sample::<f64>::from(sample)), sample);
+        }
+    }
+
+    #[test]
+    fn sample_f32_from_sample_f64() {
+        assert_nearly_eq!(
+            Sample::<f32>::from(Sample::<f64>::from(0.25)),
+            Sample::from(0.25)
+        );
+        assert_nearly_eq!(
+            Sample::<f64>::from(Sample::<f32>::from(-0.25)),
+            Sample::from(-0.25)
+        );
+    }
+
+    #[test]
+    fn from_bytes() {
+        use byteorder::{BigEndian, LittleEndian};
+
+        assert_eq!(
+            Sample::<u8>::from_bytes::<LittleEndian>(&[0x80]).unwrap(),
+            Sample::from(128)
+        );
+        assert_eq!(
+            Sample::<i16>::from_bytes::<BigEndian>(&[0x12, 0x34]).unwrap(),
+            Sample::from(0x1234)
+        );
+        assert_eq!(
+            Sample::<I24>::from_bytes::<LittleEndian>(&[0x56, 0x34, 0xF2]).unwrap(),
+            Sample::from(I24::from_be_bytes([0xF2, 0x34, 0x56]))
+        );
+        assert_eq!(
+            Sample::<i32>::from_bytes::<LittleEndian>(&[0x78, 0x56, 0x34, 0x12]).unwrap(),
+            Sample::from(0x12345678)
+        );
+        assert_eq!(
+            Sample::<f32>::from_bytes::<BigEndian>(&0.5f32.to_be_bytes()).unwrap(),
+            Sample::from(0.5)
+        );
+        assert_eq!(
+            Sample::<f64>::from_bytes::<LittleEndian>(&(-0.5f64).to_le_bytes()).unwrap(),
+            Sample::from(-0.5)
+        );
+        assert!(Sample::<I24>::from_bytes::<LittleEndian>(&[0x00, 0x00]).is_err());
+    }
 }
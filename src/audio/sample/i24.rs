@@ -0,0 +1,140 @@
+use std::{cmp::Ordering, convert::TryFrom, fmt::Debug};
+
+use thiserror::Error;
+
+#[derive(Error, PartialEq, Eq, Clone, Copy, Debug)]
+#[error("Value out of range for a 24-bit integer: {value}")]
+pub struct I24RangeError {
+    pub value: i32,
+}
+
+/// A packed 24-bit signed integer, as commonly used for PCM audio.
+///
+/// The value is stored as three little-endian bytes,
+/// so that slices of `I24` have the same layout as packed 24-bit PCM data.
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Default)]
+#[repr(transparent)]
+pub struct I24([u8; 3]);
+
+impl I24 {
+    pub const MIN: Self = Self::from_i32_wrapping(-(1 << 23));
+    pub const MAX: Self = Self::from_i32_wrapping((1 << 23) - 1);
+
+    /// Creates a value from the lower 24 bits of `value`, discarding its upper 8 bits.
+    #[inline]
+    pub const fn from_i32_wrapping(value: i32) -> Self {
+        let [a, b, c, _] = value.to_le_bytes();
+        Self([a, b, c])
+    }
+
+    #[inline]
+    pub const fn to_i32(self) -> i32 {
+        let [a, b, c] = self.0;
+        // Casting the most significant byte via `i8` sign-extends it:
+        ((c as i8 as i32) << 16) | ((b as i32) << 8) | (a as i32)
+    }
+
+    #[inline]
+    pub const fn from_le_bytes(bytes: [u8; 3]) -> Self {
+        Self(bytes)
+    }
+
+    #[inline]
+    pub const fn from_be_bytes(bytes: [u8; 3]) -> Self {
+        let [a, b, c] = bytes;
+        Self([c, b, a])
+    }
+
+    #[inline]
+    pub const fn to_le_bytes(self) -> [u8; 3] {
+        self.0
+    }
+
+    #[inline]
+    pub const fn to_be_bytes(self) -> [u8; 3] {
+        let [a, b, c] = self.0;
+        [c, b, a]
+    }
+}
+
+impl TryFrom<i32> for I24 {
+    type Error = I24RangeError;
+
+    #[inline]
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        if (Self::MIN.to_i32()..=Self::MAX.to_i32()).contains(&value) {
+            Ok(Self::from_i32_wrapping(value))
+        } else {
+            Err(I24RangeError { value })
+        }
+    }
+}
+
+impl From<I24> for i32 {
+    #[inline]
+    fn from(value: I24) -> Self {
+        value.to_i32()
+    }
+}
+
+impl PartialOrd for I24 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for I24 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_i32().cmp(&other.to_i32())
+    }
+}
+
+impl Debug for I24 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.to_i32().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_i32() {
+        assert_eq!(I24::MIN.to_i32(), -8_388_608);
+        assert_eq!(I24::MAX.to_i32(), 8_388_607);
+        assert_eq!(I24::default().to_i32(), 0);
+        assert_eq!(I24::from_i32_wrapping(-1).to_i32(), -1);
+        assert_eq!(I24::from_i32_wrapping(1 << 23).to_i32(), -8_388_608);
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(I24::try_from(-8_388_608), Ok(I24::MIN));
+        assert_eq!(I24::try_from(8_388_607), Ok(I24::MAX));
+        assert_eq!(
+            I24::try_from(8_388_608),
+            Err(I24RangeError { value: 8_388_608 })
+        );
+        assert_eq!(
+            I24::try_from(-8_388_609),
+            Err(I24RangeError { value: -8_388_609 })
+        );
+    }
+
+    #[test]
+    fn bytes() {
+        let subject = I24::try_from(0x123456).unwrap();
+        assert_eq!(subject.to_le_bytes(), [0x56, 0x34, 0x12]);
+        assert_eq!(subject.to_be_bytes(), [0x12, 0x34, 0x56]);
+        assert_eq!(I24::from_le_bytes([0x56, 0x34, 0x12]), subject);
+        assert_eq!(I24::from_be_bytes([0x12, 0x34, 0x56]), subject);
+    }
+
+    #[test]
+    fn ord() {
+        assert!(I24::MIN < I24::default());
+        assert!(I24::from_i32_wrapping(-1) < I24::from_i32_wrapping(1));
+        assert!(I24::MAX > I24::from_i32_wrapping(255));
+    }
+}
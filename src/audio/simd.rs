@@ -0,0 +1,631 @@
+//! SIMD-accelerated (de-)interleaving and sample conversion.
+//!
+//! Each operation picks the widest instruction set supported by the CPU at runtime
+//! (AVX2 or SSE2 on x86, NEON on AArch64), falling back to scalar code for everything
+//! else, as well as for the trailing frames that don't fill an entire vector.
+//!
+//! (De-)interleaving is vectorized for single-channel buffers and any even number of channels
+//! (covering the common stereo, 5.1 and 7.1 layouts), for samples of 16 and 32 bits.
+//! Conversions between `i16` and `f32` samples produce exactly the same results as
+//! the corresponding [`Sample`](crate::audio::Sample) conversions.
+//!
+//! [`Buffer`]'s layout conversions via `From`, as well as the processors' conversions
+//! of their `f32` output to `i16` samples, go through the [`Backend::cached`] backend.
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64;
+mod scalar;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86;
+
+use std::{
+    any::{Any, TypeId},
+    mem::size_of,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use crate::audio::{
+    buffer::{
+        layout::{Deinterleaved, Interleaved},
+        Buffer, BufferRef,
+    },
+    sealed::Sealed,
+    RawSample,
+};
+
+/// A raw sample type supported by the SIMD (de-)interleaving routines.
+pub trait Element: Sealed + Copy + Default {}
+
+impl Sealed for i16 {}
+impl Element for i16 {}
+
+impl Sealed for u16 {}
+impl Element for u16 {}
+
+impl Sealed for i32 {}
+impl Element for i32 {}
+
+impl Sealed for u32 {}
+impl Element for u32 {}
+
+impl Sealed for f32 {}
+impl Element for f32 {}
+
+/// The instruction set used for processing samples.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Backend {
+    Scalar,
+    Sse2,
+    Avx2,
+    Neon,
+}
+
+// The backend returned by `Backend::cached`, encoded by `Backend::to_u8`:
+static CACHED_BACKEND: AtomicU8 = AtomicU8::new(UNDETECTED);
+const UNDETECTED: u8 = u8::MAX;
+
+impl Backend {
+    /// Returns the fastest backend supported by the current CPU, detecting it on the first call only.
+    pub fn cached() -> Self {
+        match Self::from_u8(CACHED_BACKEND.load(Ordering::Relaxed)) {
+            Some(backend) => backend,
+            None => {
+                let backend = Self::detect();
+                CACHED_BACKEND.store(backend.to_u8(), Ordering::Relaxed);
+                backend
+            }
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        self as u8
+    }
+
+    fn from_u8(value: u8) -> Option<Self> {
+        [Self::Scalar, Self::Sse2, Self::Avx2, Self::Neon]
+            .iter()
+            .copied()
+            .find(|backend| backend.to_u8() == value)
+    }
+
+    /// Returns the fastest backend supported by the current CPU.
+    pub fn detect() -> Self {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                return Self::Avx2;
+            }
+            if is_x86_feature_detected!("sse2") {
+                return Self::Sse2;
+            }
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                return Self::Neon;
+            }
+        }
+
+        Self::Scalar
+    }
+
+    /// Returns `true` if the backend is supported by the current CPU.
+    ///
+    /// Unsupported backends fall back to the scalar implementations.
+    pub fn is_supported(&self) -> bool {
+        match self {
+            Self::Scalar => true,
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Self::Sse2 => is_x86_feature_detected!("sse2"),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Self::Avx2 => is_x86_feature_detected!("avx2"),
+            #[cfg(target_arch = "aarch64")]
+            Self::Neon => std::arch::is_aarch64_feature_detected!("neon"),
+            _ => false,
+        }
+    }
+
+    /// Interleaves the de-interleaved `input` into `output`.
+    pub fn interleave<T>(&self, input: &[T], output: &mut [T], channels: usize)
+    where
+        T: Element,
+    {
+        assert_eq!(input.len(), output.len());
+        assert_eq!(input.len() % channels, 0);
+
+        if channels == 1 {
+            output.copy_from_slice(input);
+            return;
+        }
+
+        // Safety: `Element` is only implemented for primitive types of either size.
+        match size_of::<T>() {
+            2 => unsafe {
+                let (input, output) = cast::<T, u16>(input, output);
+                self.interleave_u16(input, output, channels)
+            },
+            4 => unsafe {
+                let (input, output) = cast::<T, u32>(input, output);
+                self.interleave_u32(input, output, channels)
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    /// De-interleaves the interleaved `input` into `output`.
+    pub fn deinterleave<T>(&self, input: &[T], output: &mut [T], channels: usize)
+    where
+        T: Element,
+    {
+        assert_eq!(input.len(), output.len());
+        assert_eq!(input.len() % channels, 0);
+
+        if channels == 1 {
+            output.copy_from_slice(input);
+            return;
+        }
+
+        // Safety: `Element` is only implemented for primitive types of either size.
+        match size_of::<T>() {
+            2 => unsafe {
+                let (input, output) = cast::<T, u16>(input, output);
+                self.deinterleave_u16(input, output, channels)
+            },
+            4 => unsafe {
+                let (input, output) = cast::<T, u32>(input, output);
+                self.deinterleave_u32(input, output, channels)
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn i16_to_f32(&self, input: &[i16], output: &mut [f32]) {
+        assert_eq!(input.len(), output.len());
+
+        match self {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Self::Avx2 if self.is_supported() => unsafe { x86::i16_to_f32_avx2(input, output) },
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Self::Sse2 if self.is_supported() => unsafe { x86::i16_to_f32_sse2(input, output) },
+            #[cfg(target_arch = "aarch64")]
+            Self::Neon if self.is_supported() => unsafe { aarch64::i16_to_f32_neon(input, output) },
+            _ => scalar::i16_to_f32(input, output),
+        }
+    }
+
+    pub fn f32_to_i16(&self, input: &[f32], output: &mut [i16]) {
+        assert_eq!(input.len(), output.len());
+
+        match self {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Self::Avx2 if self.is_supported() => unsafe { x86::f32_to_i16_avx2(input, output) },
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Self::Sse2 if self.is_supported() => unsafe { x86::f32_to_i16_sse2(input, output) },
+            #[cfg(target_arch = "aarch64")]
+            Self::Neon if self.is_supported() => unsafe { aarch64::f32_to_i16_neon(input, output) },
+            _ => scalar::f32_to_i16(input, output),
+        }
+    }
+
+    fn interleave_u16(&self, input: &[u16], output: &mut [u16], channels: usize) {
+        if channels % 2 == 0 {
+            match self {
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Self::Avx2 if self.is_supported() => {
+                    return unsafe { x86::interleave_u16_avx2(input, output, channels) }
+                }
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Self::Sse2 if self.is_supported() => {
+                    return unsafe { x86::interleave_u16_sse2(input, output, channels) }
+                }
+                #[cfg(target_arch = "aarch64")]
+                Self::Neon if self.is_supported() => {
+                    return unsafe { aarch64::interleave_u16_neon(input, output, channels) }
+                }
+                _ => {}
+            }
+        }
+
+        scalar::interleave(input, output, channels)
+    }
+
+    fn interleave_u32(&self, input: &[u32], output: &mut [u32], channels: usize) {
+        if channels % 2 == 0 {
+            match self {
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Self::Avx2 if self.is_supported() => {
+                    return unsafe { x86::interleave_u32_avx2(input, output, channels) }
+                }
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Self::Sse2 if self.is_supported() => {
+                    return unsafe { x86::interleave_u32_sse2(input, output, channels) }
+                }
+                #[cfg(target_arch = "aarch64")]
+                Self::Neon if self.is_supported() => {
+                    return unsafe { aarch64::interleave_u32_neon(input, output, channels) }
+                }
+                _ => {}
+            }
+        }
+
+        scalar::interleave(input, output, channels)
+    }
+
+    fn deinterleave_u16(&self, input: &[u16], output: &mut [u16], channels: usize) {
+        if channels % 2 == 0 {
+            match self {
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Self::Avx2 if self.is_supported() => {
+                    return unsafe { x86::deinterleave_u16_avx2(input, output, channels) }
+                }
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Self::Sse2 if self.is_supported() => {
+                    return unsafe { x86::deinterleave_u16_sse2(input, output, channels) }
+                }
+                #[cfg(target_arch = "aarch64")]
+                Self::Neon if self.is_supported() => {
+                    return unsafe { aarch64::deinterleave_u16_neon(input, output, channels) }
+                }
+                _ => {}
+            }
+        }
+
+        scalar::deinterleave(input, output, channels)
+    }
+
+    fn deinterleave_u32(&self, input: &[u32], output: &mut [u32], channels: usize) {
+        if channels % 2 == 0 {
+            match self {
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Self::Avx2 if self.is_supported() => {
+                    return unsafe { x86::deinterleave_u32_avx2(input, output, channels) }
+                }
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                Self::Sse2 if self.is_supported() => {
+                    return unsafe { x86::deinterleave_u32_sse2(input, output, channels) }
+                }
+                #[cfg(target_arch = "aarch64")]
+                Self::Neon if self.is_supported() => {
+                    return unsafe { aarch64::deinterleave_u32_neon(input, output, channels) }
+                }
+                _ => {}
+            }
+        }
+
+        scalar::deinterleave(input, output, channels)
+    }
+}
+
+/// Interleaves the de-interleaved `input` into `output`, using the fastest available backend.
+pub fn interleave<T>(input: &[T], output: &mut [T], channels: usize)
+where
+    T: Element,
+{
+    Backend::cached().interleave(input, output, channels)
+}
+
+/// De-interleaves the interleaved `input` into `output`, using the fastest available backend.
+pub fn deinterleave<T>(input: &[T], output: &mut [T], channels: usize)
+where
+    T: Element,
+{
+    Backend::cached().deinterleave(input, output, channels)
+}
+
+/// Converts `i16` samples to `f32` samples, using the fastest available backend.
+pub fn i16_to_f32(input: &[i16], output: &mut [f32]) {
+    Backend::cached().i16_to_f32(input, output)
+}
+
+/// Converts `f32` samples to `i16` samples, using the fastest available backend.
+pub fn f32_to_i16(input: &[f32], output: &mut [i16]) {
+    Backend::cached().f32_to_i16(input, output)
+}
+
+/// Returns an interleaved copy of `buffer`.
+pub fn interleaved<T>(buffer: BufferRef<'_, T, Deinterleaved>) -> Buffer<T, Interleaved>
+where
+    T: Element,
+{
+    let channels = buffer.info().channels();
+    let mut samples = vec![T::default(); buffer.samples().len()];
+    interleave(buffer.samples(), &mut samples[..], channels);
+    Buffer::new(samples, channels)
+}
+
+/// Returns a de-interleaved copy of `buffer`.
+pub fn deinterleaved<T>(buffer: BufferRef<'_, T, Interleaved>) -> Buffer<T, Deinterleaved>
+where
+    T: Element,
+{
+    let channels = buffer.info().channels();
+    let mut samples = vec![T::default(); buffer.samples().len()];
+    deinterleave(buffer.samples(), &mut samples[..], channels);
+    Buffer::new(samples, channels)
+}
+
+/// Returns a copy of `buffer` with its samples converted to `f32`.
+pub fn to_f32<L>(buffer: BufferRef<'_, i16, L>) -> Buffer<f32, L> {
+    let mut samples = vec![0.0; buffer.samples().len()];
+    i16_to_f32(buffer.samples(), &mut samples[..]);
+    Buffer::new(samples, buffer.info().channels())
+}
+
+/// Returns a copy of `buffer` with its samples converted to `i16`.
+pub fn to_i16<L>(buffer: BufferRef<'_, f32, L>) -> Buffer<i16, L> {
+    let mut samples = vec![0; buffer.samples().len()];
+    f32_to_i16(buffer.samples(), &mut samples[..]);
+    Buffer::new(samples, buffer.info().channels())
+}
+
+/// Returns an interleaved copy of the de-interleaved `input`,
+/// or `None` if `T` isn't an [`Element`] and hence has no SIMD path.
+pub(crate) fn interleaved_vec<T>(input: &[T], channels: usize) -> Option<Vec<T>>
+where
+    T: Copy + Default + 'static,
+{
+    relayout_vec(
+        input,
+        channels,
+        Backend::interleave::<u16>,
+        Backend::interleave::<u32>,
+    )
+}
+
+/// Returns a de-interleaved copy of the interleaved `input`,
+/// or `None` if `T` isn't an [`Element`] and hence has no SIMD path.
+pub(crate) fn deinterleaved_vec<T>(input: &[T], channels: usize) -> Option<Vec<T>>
+where
+    T: Copy + Default + 'static,
+{
+    relayout_vec(
+        input,
+        channels,
+        Backend::deinterleave::<u16>,
+        Backend::deinterleave::<u32>,
+    )
+}
+
+fn relayout_vec<T>(
+    input: &[T],
+    channels: usize,
+    relayout_16: fn(&Backend, &[u16], &mut [u16], usize),
+    relayout_32: fn(&Backend, &[u32], &mut [u32], usize),
+) -> Option<Vec<T>>
+where
+    T: Copy + Default + 'static,
+{
+    let id = TypeId::of::<T>();
+    let is_16_bit = [TypeId::of::<i16>(), TypeId::of::<u16>()].contains(&id);
+    let is_32_bit = [
+        TypeId::of::<i32>(),
+        TypeId::of::<u32>(),
+        TypeId::of::<f32>(),
+    ]
+    .contains(&id);
+    if !is_16_bit && !is_32_bit {
+        return None;
+    }
+
+    let backend = Backend::cached();
+    let mut output = vec![T::default(); input.len()];
+
+    // Safety: `T` is one of the `Element`s of the size it gets cast to.
+    if is_16_bit {
+        let (input, output) = unsafe { cast::<T, u16>(input, &mut output[..]) };
+        relayout_16(&backend, input, output, channels);
+    } else {
+        let (input, output) = unsafe { cast::<T, u32>(input, &mut output[..]) };
+        relayout_32(&backend, input, output, channels);
+    }
+
+    Some(output)
+}
+
+/// Converts `f32` samples to raw ones, which for `i16` samples happens via the backend.
+pub(crate) fn from_f32_vec<T>(input: Vec<f32>) -> Vec<T>
+where
+    T: RawSample,
+{
+    if TypeId::of::<T>() != TypeId::of::<i16>() {
+        return input.into_iter().map(T::from_f32).collect();
+    }
+
+    let mut output = vec![0_i16; input.len()];
+    Backend::cached().f32_to_i16(&input[..], &mut output[..]);
+
+    let output: Box<dyn Any> = Box::new(output);
+    *output.downcast::<Vec<T>>().expect("`T` is `i16`")
+}
+
+/// Reinterprets slices of `T` as slices of `U`.
+///
+/// # Safety
+///
+/// `T` and `U` MUST be primitive types of identical size and alignment.
+unsafe fn cast<'a, T, U>(input: &'a [T], output: &'a mut [T]) -> (&'a [U], &'a mut [U]) {
+    debug_assert_eq!(size_of::<T>(), size_of::<U>());
+    debug_assert_eq!(std::mem::align_of::<T>(), std::mem::align_of::<U>());
+
+    let input = std::slice::from_raw_parts(input.as_ptr() as *const U, input.len());
+    let output = std::slice::from_raw_parts_mut(output.as_mut_ptr() as *mut U, output.len());
+    (input, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::audio::{buffer::layout, Sample};
+
+    use super::*;
+
+    const BACKENDS: [Backend; 4] = [Backend::Scalar, Backend::Sse2, Backend::Avx2, Backend::Neon];
+
+    fn backends() -> impl Iterator<Item = Backend> {
+        BACKENDS.iter().copied().filter(Backend::is_supported)
+    }
+
+    fn check_layouts<T>(rng: &mut StdRng)
+    where
+        T: Element + PartialEq + std::fmt::Debug,
+        rand::distributions::Standard: rand::distributions::Distribution<T>,
+    {
+        for backend in backends() {
+            for channels in 1..=8 {
+                for frames in (0..=40).chain([480, 961]) {
+                    let input: Vec<T> = (0..(channels * frames)).map(|_| rng.gen()).collect();
+
+                    let mut expected = vec![T::default(); input.len()];
+                    let mut actual = vec![T::default(); input.len()];
+
+                    layout::deinterleaved_by(&input[..], &mut expected[..], channels, |s| *s);
+                    backend.deinterleave(&input[..], &mut actual[..], channels);
+                    assert_eq!(
+                        actual, expected,
+                        "{:?}, {} channels, {} frames",
+                        backend, channels, frames
+                    );
+
+                    layout::interleaved_by(&input[..], &mut expected[..], channels, |s| *s);
+                    backend.interleave(&input[..], &mut actual[..], channels);
+                    assert_eq!(
+                        actual, expected,
+                        "{:?}, {} channels, {} frames",
+                        backend, channels, frames
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn detect() {
+        assert!(Backend::detect().is_supported());
+        assert!(Backend::Scalar.is_supported());
+        assert_eq!(Backend::cached(), Backend::detect());
+        assert_eq!(Backend::cached(), Backend::detect());
+    }
+
+    #[test]
+    fn from_f32_vec() {
+        let input: Vec<f32> = (-300..=300).map(|i| (i as f32) / 256.0).collect();
+
+        let expected: Vec<i16> = input.iter().map(|&sample| i16::from_f32(sample)).collect();
+        assert_eq!(super::from_f32_vec::<i16>(input.clone()), expected);
+
+        let expected: Vec<u8> = input.iter().map(|&sample| u8::from_f32(sample)).collect();
+        assert_eq!(super::from_f32_vec::<u8>(input), expected);
+    }
+
+    #[test]
+    fn relayout_vec() {
+        let input: Vec<i16> = (0..12).collect();
+        let mut expected = vec![0; input.len()];
+
+        layout::interleaved_by(&input[..], &mut expected[..], 3, |s| *s);
+        assert_eq!(interleaved_vec(&input[..], 3), Some(expected.clone()));
+
+        layout::deinterleaved_by(&input[..], &mut expected[..], 3, |s| *s);
+        assert_eq!(deinterleaved_vec(&input[..], 3), Some(expected));
+
+        assert_eq!(interleaved_vec(&[0.0_f64; 12][..], 3), None);
+        assert_eq!(deinterleaved_vec(&[0_u8; 12][..], 3), None);
+    }
+
+    #[test]
+    fn layouts_16_bit() {
+        let mut rng = StdRng::seed_from_u64(16);
+        check_layouts::<i16>(&mut rng);
+        check_layouts::<u16>(&mut rng);
+    }
+
+    #[test]
+    fn layouts_32_bit() {
+        let mut rng = StdRng::seed_from_u64(32);
+        check_layouts::<i32>(&mut rng);
+        check_layouts::<u32>(&mut rng);
+        check_layouts::<f32>(&mut rng);
+    }
+
+    #[test]
+    fn i16_to_f32() {
+        let input: Vec<i16> = (i16::MIN..=i16::MAX).collect();
+        let expected: Vec<f32> = input
+            .iter()
+            .map(|&sample| f32::from(Sample::<f32>::from(Sample::from(sample))))
+            .collect();
+
+        for backend in backends() {
+            // Offsetting the input covers the trailing samples of all vector widths:
+            for offset in 0..16 {
+                let mut actual = vec![0.0; input.len() - offset];
+                backend.i16_to_f32(&input[offset..], &mut actual[..]);
+                assert_eq!(&actual[..], &expected[offset..], "{:?}", backend);
+            }
+        }
+    }
+
+    #[test]
+    fn f32_to_i16() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let mut input: Vec<f32> = vec![
+            0.0,
+            -0.0,
+            1.0,
+            -1.0,
+            f32::NAN,
+            -f32::NAN,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::MIN_POSITIVE,
+            -f32::MIN_POSITIVE,
+            0.5,
+            -0.5,
+        ];
+        // Values on and around the rounding ties:
+        for step in (0..32768).step_by(7) {
+            for tie in [
+                (step as f32 + 0.5) / 32767.0,
+                -(step as f32 + 0.5) / 32768.0,
+            ] {
+                input.extend([
+                    tie,
+                    f32::from_bits(tie.to_bits() - 1),
+                    f32::from_bits(tie.to_bits() + 1),
+                ]);
+            }
+        }
+        input.extend((0..10_000).map(|_| rng.gen_range(-1.5..1.5)));
+
+        let expected: Vec<i16> = input
+            .iter()
+            .map(|&sample| i16::from(Sample::<i16>::from(Sample::<f32>::from(sample))))
+            .collect();
+
+        for backend in backends() {
+            for offset in 0..16 {
+                let mut actual = vec![0; input.len() - offset];
+                backend.f32_to_i16(&input[offset..], &mut actual[..]);
+                assert_eq!(&actual[..], &expected[offset..], "{:?}", backend);
+            }
+        }
+    }
+
+    #[test]
+    fn buffers() {
+        let buffer = Buffer::<i16, Interleaved>::new((0..60).collect(), 6);
+
+        let deinterleaved = super::deinterleaved(buffer.as_ref());
+        assert_eq!(
+            deinterleaved,
+            Buffer::<i16, Deinterleaved>::from(buffer.clone())
+        );
+        assert_eq!(super::interleaved(deinterleaved.as_ref()), buffer);
+
+        let converted = to_i16(to_f32(buffer.as_ref()).as_ref());
+        assert_eq!(converted, buffer);
+    }
+}
@@ -0,0 +1,219 @@
+//! NEON kernels.
+//!
+//! Stereo buffers get (de-)interleaved using NEON's structured loads and stores,
+//! while other even channel counts get processed a pair of adjacent channels at a time.
+//!
+//! Float to integer conversions are computed in double precision, in which the scaled samples
+//! are exact, so as to round ties exactly like the scalar conversion does.
+
+use std::arch::aarch64::*;
+use std::ptr::{read_unaligned, write_unaligned};
+
+use super::scalar;
+
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn interleave_u16_neon(input: &[u16], output: &mut [u16], channels: usize) {
+    debug_assert_eq!(channels % 2, 0);
+
+    let frames = input.len() / channels;
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    let simd_frames = if channels == 2 {
+        let simd_frames = frames - (frames % 8);
+        for frame in (0..simd_frames).step_by(8) {
+            let left = vld1q_u16(src.add(frame));
+            let right = vld1q_u16(src.add(frames + frame));
+            vst2q_u16(dst.add(frame * 2), uint16x8x2_t(left, right));
+        }
+        simd_frames
+    } else {
+        let simd_frames = frames - (frames % 4);
+        for frame in (0..simd_frames).step_by(4) {
+            for channel in (0..channels).step_by(2) {
+                let a = vld1_u16(src.add((channel * frames) + frame));
+                let b = vld1_u16(src.add(((channel + 1) * frames) + frame));
+                // `[a0, b0, a1, b1]` and `[a2, b2, a3, b3]`, with each pair being written as a single `u32`:
+                let lo = vreinterpret_u32_u16(vzip1_u16(a, b));
+                let hi = vreinterpret_u32_u16(vzip2_u16(a, b));
+                let dst =
+                    |offset: usize| dst.add(((frame + offset) * channels) + channel) as *mut u32;
+                write_unaligned(dst(0), vget_lane_u32(lo, 0));
+                write_unaligned(dst(1), vget_lane_u32(lo, 1));
+                write_unaligned(dst(2), vget_lane_u32(hi, 0));
+                write_unaligned(dst(3), vget_lane_u32(hi, 1));
+            }
+        }
+        simd_frames
+    };
+
+    scalar::interleave_frames(input, output, channels, simd_frames..frames);
+}
+
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn deinterleave_u16_neon(input: &[u16], output: &mut [u16], channels: usize) {
+    debug_assert_eq!(channels % 2, 0);
+
+    let frames = input.len() / channels;
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    let simd_frames = if channels == 2 {
+        let simd_frames = frames - (frames % 8);
+        for frame in (0..simd_frames).step_by(8) {
+            let uint16x8x2_t(left, right) = vld2q_u16(src.add(frame * 2));
+            vst1q_u16(dst.add(frame), left);
+            vst1q_u16(dst.add(frames + frame), right);
+        }
+        simd_frames
+    } else {
+        let simd_frames = frames - (frames % 4);
+        for frame in (0..simd_frames).step_by(4) {
+            for channel in (0..channels).step_by(2) {
+                let pair = |offset: usize| {
+                    read_unaligned(src.add(((frame + offset) * channels) + channel) as *const u32)
+                };
+                let pairs = [pair(0), pair(1), pair(2), pair(3)];
+                // `[a0, b0, a1, b1, a2, b2, a3, b3]`:
+                let pairs = vreinterpretq_u16_u32(vld1q_u32(pairs.as_ptr()));
+                let a = vget_low_u16(vuzp1q_u16(pairs, pairs));
+                let b = vget_low_u16(vuzp2q_u16(pairs, pairs));
+                vst1_u16(dst.add((channel * frames) + frame), a);
+                vst1_u16(dst.add(((channel + 1) * frames) + frame), b);
+            }
+        }
+        simd_frames
+    };
+
+    scalar::deinterleave_frames(input, output, channels, simd_frames..frames);
+}
+
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn interleave_u32_neon(input: &[u32], output: &mut [u32], channels: usize) {
+    debug_assert_eq!(channels % 2, 0);
+
+    let frames = input.len() / channels;
+    let simd_frames = frames - (frames % 4);
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    for frame in (0..simd_frames).step_by(4) {
+        for channel in (0..channels).step_by(2) {
+            let a = vld1q_u32(src.add((channel * frames) + frame));
+            let b = vld1q_u32(src.add(((channel + 1) * frames) + frame));
+
+            if channels == 2 {
+                vst2q_u32(dst.add(frame * 2), uint32x4x2_t(a, b));
+            } else {
+                // `[a0, b0, a1, b1]` and `[a2, b2, a3, b3]`:
+                let lo = vzip1q_u32(a, b);
+                let hi = vzip2q_u32(a, b);
+                let dst = |offset: usize| dst.add(((frame + offset) * channels) + channel);
+                vst1_u32(dst(0), vget_low_u32(lo));
+                vst1_u32(dst(1), vget_high_u32(lo));
+                vst1_u32(dst(2), vget_low_u32(hi));
+                vst1_u32(dst(3), vget_high_u32(hi));
+            }
+        }
+    }
+
+    scalar::interleave_frames(input, output, channels, simd_frames..frames);
+}
+
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn deinterleave_u32_neon(input: &[u32], output: &mut [u32], channels: usize) {
+    debug_assert_eq!(channels % 2, 0);
+
+    let frames = input.len() / channels;
+    let simd_frames = frames - (frames % 4);
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    for frame in (0..simd_frames).step_by(4) {
+        for channel in (0..channels).step_by(2) {
+            let (a, b) = if channels == 2 {
+                let uint32x4x2_t(a, b) = vld2q_u32(src.add(frame * 2));
+                (a, b)
+            } else {
+                let src = |offset: usize| src.add(((frame + offset) * channels) + channel);
+                // `[a0, b0, a1, b1]` and `[a2, b2, a3, b3]`:
+                let lo = vcombine_u32(vld1_u32(src(0)), vld1_u32(src(1)));
+                let hi = vcombine_u32(vld1_u32(src(2)), vld1_u32(src(3)));
+                (vuzp1q_u32(lo, hi), vuzp2q_u32(lo, hi))
+            };
+            vst1q_u32(dst.add((channel * frames) + frame), a);
+            vst1q_u32(dst.add(((channel + 1) * frames) + frame), b);
+        }
+    }
+
+    scalar::deinterleave_frames(input, output, channels, simd_frames..frames);
+}
+
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn i16_to_f32_neon(input: &[i16], output: &mut [f32]) {
+    let simd_len = input.len() - (input.len() % 8);
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    let negative_divisor = vdupq_n_f32(-(i16::MIN as f32));
+    let positive_divisor = vdupq_n_f32(i16::MAX as f32);
+
+    for index in (0..simd_len).step_by(8) {
+        let samples = vld1q_s16(src.add(index));
+        let lo = vmovl_s16(vget_low_s16(samples));
+        let hi = vmovl_high_s16(samples);
+
+        for (offset, samples) in [(0, lo), (4, hi)] {
+            let samples = vcvtq_f32_s32(samples);
+            let divisor = vbslq_f32(vcltzq_f32(samples), negative_divisor, positive_divisor);
+            vst1q_f32(dst.add(index + offset), vdivq_f32(samples, divisor));
+        }
+    }
+
+    scalar::i16_to_f32(&input[simd_len..], &mut output[simd_len..]);
+}
+
+#[target_feature(enable = "neon")]
+pub(super) unsafe fn f32_to_i16_neon(input: &[f32], output: &mut [i16]) {
+    let simd_len = input.len() - (input.len() % 8);
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    for index in (0..simd_len).step_by(8) {
+        let lo = f32_to_i32_neon(vld1q_f32(src.add(index)));
+        let hi = f32_to_i32_neon(vld1q_f32(src.add(index + 4)));
+        vst1q_s16(dst.add(index), vqmovn_high_s32(vqmovn_s32(lo), hi));
+    }
+
+    scalar::f32_to_i16(&input[simd_len..], &mut output[simd_len..]);
+}
+
+/// Converts four samples to the range of `i16`, returning them as `i32`s.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn f32_to_i32_neon(samples: float32x4_t) -> int32x4_t {
+    // Replace NaNs with zero, then clamp:
+    let is_number = vceqq_f32(samples, samples);
+    let samples = vreinterpretq_f32_u32(vandq_u32(vreinterpretq_u32_f32(samples), is_number));
+    let samples = vminq_f32(vmaxq_f32(samples, vdupq_n_f32(-1.0)), vdupq_n_f32(1.0));
+
+    let lo = round_f64_to_i16_neon(vcvt_f64_f32(vget_low_f32(samples)));
+    let hi = round_f64_to_i16_neon(vcvt_high_f64_f32(samples));
+    vcombine_s32(lo, hi)
+}
+
+/// Scales two clamped samples to the range of `i16` and rounds them half towards zero,
+/// returning them as `i32`s.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn round_f64_to_i16_neon(samples: float64x2_t) -> int32x2_t {
+    let magnitude = vabsq_f64(samples);
+    let is_negative = vcltzq_f64(samples);
+
+    // Scale negative samples by `-i16::MIN`, positive ones by `i16::MAX`:
+    let negative_extra = vandq_u64(is_negative, vreinterpretq_u64_f64(magnitude));
+    let scaled = vaddq_f64(
+        vmulq_n_f64(magnitude, i16::MAX as f64),
+        vreinterpretq_f64_u64(negative_extra),
+    );
+
+    // Rounding half towards zero equals `ceil(scaled - 0.5)`:
+    let rounded = vrndpq_f64(vsubq_f64(scaled, vdupq_n_f64(0.5)));
+    let rounded = vbslq_f64(is_negative, vnegq_f64(rounded), rounded);
+
+    vmovn_s64(vcvtq_s64_f64(rounded))
+}
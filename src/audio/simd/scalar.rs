@@ -0,0 +1,71 @@
+use std::ops::Range;
+
+use crate::audio::{buffer::layout, Sample};
+
+pub(super) fn interleave<T>(input: &[T], output: &mut [T], channels: usize)
+where
+    T: Copy,
+{
+    layout::interleaved_by(input, output, channels, |sample| *sample)
+}
+
+pub(super) fn deinterleave<T>(input: &[T], output: &mut [T], channels: usize)
+where
+    T: Copy,
+{
+    layout::deinterleaved_by(input, output, channels, |sample| *sample)
+}
+
+/// Interleaves the given range of frames, e.g. the trailing ones left over by a vectorized loop.
+#[cfg_attr(
+    not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")),
+    allow(dead_code)
+)]
+pub(super) fn interleave_frames<T>(
+    input: &[T],
+    output: &mut [T],
+    channels: usize,
+    frames: Range<usize>,
+) where
+    T: Copy,
+{
+    let total_frames = input.len() / channels;
+    for frame in frames {
+        for channel in 0..channels {
+            output[(frame * channels) + channel] = input[(channel * total_frames) + frame];
+        }
+    }
+}
+
+/// De-interleaves the given range of frames, e.g. the trailing ones left over by a vectorized loop.
+#[cfg_attr(
+    not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")),
+    allow(dead_code)
+)]
+pub(super) fn deinterleave_frames<T>(
+    input: &[T],
+    output: &mut [T],
+    channels: usize,
+    frames: Range<usize>,
+) where
+    T: Copy,
+{
+    let total_frames = input.len() / channels;
+    for frame in frames {
+        for channel in 0..channels {
+            output[(channel * total_frames) + frame] = input[(frame * channels) + channel];
+        }
+    }
+}
+
+pub(super) fn i16_to_f32(input: &[i16], output: &mut [f32]) {
+    for (input, output) in input.iter().zip(output.iter_mut()) {
+        *output = f32::from(Sample::<f32>::from(Sample::from(*input)));
+    }
+}
+
+pub(super) fn f32_to_i16(input: &[f32], output: &mut [i16]) {
+    for (input, output) in input.iter().zip(output.iter_mut()) {
+        *output = i16::from(Sample::<i16>::from(Sample::<f32>::from(*input)));
+    }
+}
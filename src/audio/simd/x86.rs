@@ -0,0 +1,450 @@
+//! SSE2 and AVX2 kernels.
+//!
+//! The (de-)interleaving kernels operate on pairs of adjacent channels, loading or storing
+//! each pair's samples of multiple frames at once, with stereo buffers (being nothing but a
+//! single pair) getting processed using full-width loads and stores.
+//!
+//! Float to integer conversions are computed in double precision, in which the scaled samples
+//! are exact, so as to round ties exactly like the scalar conversion does.
+
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+use std::ptr::{read_unaligned, write_unaligned};
+
+use super::scalar;
+
+#[target_feature(enable = "sse2")]
+pub(super) unsafe fn interleave_u16_sse2(input: &[u16], output: &mut [u16], channels: usize) {
+    debug_assert_eq!(channels % 2, 0);
+
+    let frames = input.len() / channels;
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    let simd_frames = if channels == 2 {
+        let simd_frames = frames - (frames % 8);
+        for frame in (0..simd_frames).step_by(8) {
+            let left = _mm_loadu_si128(src.add(frame) as *const __m128i);
+            let right = _mm_loadu_si128(src.add(frames + frame) as *const __m128i);
+            let dst = dst.add(frame * 2) as *mut __m128i;
+            _mm_storeu_si128(dst, _mm_unpacklo_epi16(left, right));
+            _mm_storeu_si128(dst.add(1), _mm_unpackhi_epi16(left, right));
+        }
+        simd_frames
+    } else {
+        let simd_frames = frames - (frames % 4);
+        for frame in (0..simd_frames).step_by(4) {
+            for channel in (0..channels).step_by(2) {
+                let a = _mm_loadl_epi64(src.add((channel * frames) + frame) as *const __m128i);
+                let b =
+                    _mm_loadl_epi64(src.add(((channel + 1) * frames) + frame) as *const __m128i);
+                // `[a0, b0, a1, b1, a2, b2, a3, b3]`, with each pair being written as a single `i32`:
+                let mut pairs = _mm_unpacklo_epi16(a, b);
+                for offset in 0..4 {
+                    let dst = dst.add(((frame + offset) * channels) + channel) as *mut i32;
+                    write_unaligned(dst, _mm_cvtsi128_si32(pairs));
+                    pairs = _mm_srli_si128(pairs, 4);
+                }
+            }
+        }
+        simd_frames
+    };
+
+    scalar::interleave_frames(input, output, channels, simd_frames..frames);
+}
+
+#[target_feature(enable = "sse2")]
+pub(super) unsafe fn deinterleave_u16_sse2(input: &[u16], output: &mut [u16], channels: usize) {
+    debug_assert_eq!(channels % 2, 0);
+
+    let frames = input.len() / channels;
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    let simd_frames = if channels == 2 {
+        let simd_frames = frames - (frames % 8);
+        for frame in (0..simd_frames).step_by(8) {
+            let src = src.add(frame * 2) as *const __m128i;
+            let lo = _mm_loadu_si128(src);
+            let hi = _mm_loadu_si128(src.add(1));
+            let (left, right) = unzip_u16_sse2(lo, hi);
+            _mm_storeu_si128(dst.add(frame) as *mut __m128i, left);
+            _mm_storeu_si128(dst.add(frames + frame) as *mut __m128i, right);
+        }
+        simd_frames
+    } else {
+        let simd_frames = frames - (frames % 4);
+        for frame in (0..simd_frames).step_by(4) {
+            for channel in (0..channels).step_by(2) {
+                let pair = |offset: usize| {
+                    read_unaligned(src.add(((frame + offset) * channels) + channel) as *const i32)
+                };
+                // `[a0, b0, a1, b1, a2, b2, a3, b3]`:
+                let pairs = _mm_set_epi32(pair(3), pair(2), pair(1), pair(0));
+                let (a, b) = unzip_u16_sse2(pairs, pairs);
+                _mm_storel_epi64(dst.add((channel * frames) + frame) as *mut __m128i, a);
+                _mm_storel_epi64(dst.add(((channel + 1) * frames) + frame) as *mut __m128i, b);
+            }
+        }
+        simd_frames
+    };
+
+    scalar::deinterleave_frames(input, output, channels, simd_frames..frames);
+}
+
+/// Splits the 16-bit lanes of `lo` and `hi` into their even and odd lanes.
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn unzip_u16_sse2(lo: __m128i, hi: __m128i) -> (__m128i, __m128i) {
+    // Sign-extending each lane to 32 bits keeps the saturating pack from altering them:
+    let even = _mm_packs_epi32(
+        _mm_srai_epi32(_mm_slli_epi32(lo, 16), 16),
+        _mm_srai_epi32(_mm_slli_epi32(hi, 16), 16),
+    );
+    let odd = _mm_packs_epi32(_mm_srai_epi32(lo, 16), _mm_srai_epi32(hi, 16));
+    (even, odd)
+}
+
+#[target_feature(enable = "sse2")]
+pub(super) unsafe fn interleave_u32_sse2(input: &[u32], output: &mut [u32], channels: usize) {
+    debug_assert_eq!(channels % 2, 0);
+
+    let frames = input.len() / channels;
+    let simd_frames = frames - (frames % 4);
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    for frame in (0..simd_frames).step_by(4) {
+        for channel in (0..channels).step_by(2) {
+            let a = _mm_loadu_si128(src.add((channel * frames) + frame) as *const __m128i);
+            let b = _mm_loadu_si128(src.add(((channel + 1) * frames) + frame) as *const __m128i);
+            // `[a0, b0, a1, b1]` and `[a2, b2, a3, b3]`:
+            let lo = _mm_unpacklo_epi32(a, b);
+            let hi = _mm_unpackhi_epi32(a, b);
+
+            if channels == 2 {
+                let dst = dst.add(frame * 2) as *mut __m128i;
+                _mm_storeu_si128(dst, lo);
+                _mm_storeu_si128(dst.add(1), hi);
+            } else {
+                let dst = |offset: usize| {
+                    dst.add(((frame + offset) * channels) + channel) as *mut __m128i
+                };
+                _mm_storel_epi64(dst(0), lo);
+                _mm_storel_epi64(dst(1), _mm_unpackhi_epi64(lo, lo));
+                _mm_storel_epi64(dst(2), hi);
+                _mm_storel_epi64(dst(3), _mm_unpackhi_epi64(hi, hi));
+            }
+        }
+    }
+
+    scalar::interleave_frames(input, output, channels, simd_frames..frames);
+}
+
+#[target_feature(enable = "sse2")]
+pub(super) unsafe fn deinterleave_u32_sse2(input: &[u32], output: &mut [u32], channels: usize) {
+    debug_assert_eq!(channels % 2, 0);
+
+    let frames = input.len() / channels;
+    let simd_frames = frames - (frames % 4);
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    for frame in (0..simd_frames).step_by(4) {
+        for channel in (0..channels).step_by(2) {
+            // `[a0, b0, a1, b1]` and `[a2, b2, a3, b3]`:
+            let (lo, hi) = if channels == 2 {
+                let src = src.add(frame * 2) as *const __m128i;
+                (_mm_loadu_si128(src), _mm_loadu_si128(src.add(1)))
+            } else {
+                let src = |offset: usize| {
+                    src.add(((frame + offset) * channels) + channel) as *const __m128i
+                };
+                (
+                    _mm_unpacklo_epi64(_mm_loadl_epi64(src(0)), _mm_loadl_epi64(src(1))),
+                    _mm_unpacklo_epi64(_mm_loadl_epi64(src(2)), _mm_loadl_epi64(src(3))),
+                )
+            };
+            let (lo, hi) = (_mm_castsi128_ps(lo), _mm_castsi128_ps(hi));
+
+            let a = _mm_shuffle_ps(lo, hi, 0b10_00_10_00);
+            let b = _mm_shuffle_ps(lo, hi, 0b11_01_11_01);
+            _mm_storeu_ps(dst.add((channel * frames) + frame) as *mut f32, a);
+            _mm_storeu_ps(dst.add(((channel + 1) * frames) + frame) as *mut f32, b);
+        }
+    }
+
+    scalar::deinterleave_frames(input, output, channels, simd_frames..frames);
+}
+
+#[target_feature(enable = "sse2")]
+pub(super) unsafe fn i16_to_f32_sse2(input: &[i16], output: &mut [f32]) {
+    let simd_len = input.len() - (input.len() % 8);
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    let zero = _mm_setzero_ps();
+    let one = _mm_set1_ps(1.0);
+    let max = _mm_set1_ps(i16::MAX as f32);
+
+    for index in (0..simd_len).step_by(8) {
+        let samples = _mm_loadu_si128(src.add(index) as *const __m128i);
+        // Sign-extend to 32 bits:
+        let lo = _mm_srai_epi32(_mm_unpacklo_epi16(samples, samples), 16);
+        let hi = _mm_srai_epi32(_mm_unpackhi_epi16(samples, samples), 16);
+
+        for (offset, samples) in [(0, lo), (4, hi)] {
+            let samples = _mm_cvtepi32_ps(samples);
+            // Divide negative samples by `-i16::MIN`, positive ones by `i16::MAX`:
+            let is_negative = _mm_cmplt_ps(samples, zero);
+            let divisor = _mm_add_ps(max, _mm_and_ps(is_negative, one));
+            _mm_storeu_ps(dst.add(index + offset), _mm_div_ps(samples, divisor));
+        }
+    }
+
+    scalar::i16_to_f32(&input[simd_len..], &mut output[simd_len..]);
+}
+
+#[target_feature(enable = "sse2")]
+pub(super) unsafe fn f32_to_i16_sse2(input: &[f32], output: &mut [i16]) {
+    let simd_len = input.len() - (input.len() % 8);
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    for index in (0..simd_len).step_by(8) {
+        let lo = f32_to_i32_sse2(_mm_loadu_ps(src.add(index)));
+        let hi = f32_to_i32_sse2(_mm_loadu_ps(src.add(index + 4)));
+        _mm_storeu_si128(dst.add(index) as *mut __m128i, _mm_packs_epi32(lo, hi));
+    }
+
+    scalar::f32_to_i16(&input[simd_len..], &mut output[simd_len..]);
+}
+
+/// Converts four samples to the range of `i16`, returning them as `i32`s.
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn f32_to_i32_sse2(samples: __m128) -> __m128i {
+    // Replace NaNs with zero, then clamp:
+    let samples = _mm_and_ps(samples, _mm_cmpord_ps(samples, samples));
+    let samples = _mm_min_ps(_mm_max_ps(samples, _mm_set1_ps(-1.0)), _mm_set1_ps(1.0));
+
+    let lo = round_f64_to_i16_sse2(_mm_cvtps_pd(samples));
+    let hi = round_f64_to_i16_sse2(_mm_cvtps_pd(_mm_movehl_ps(samples, samples)));
+    _mm_unpacklo_epi64(lo, hi)
+}
+
+/// Scales two clamped samples to the range of `i16` and rounds them half towards zero,
+/// returning them as the lower two `i32`s.
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn round_f64_to_i16_sse2(samples: __m128d) -> __m128i {
+    let magnitude = _mm_andnot_pd(_mm_set1_pd(-0.0), samples);
+    let is_negative = _mm_cmplt_pd(samples, _mm_setzero_pd());
+
+    // Scale negative samples by `-i16::MIN`, positive ones by `i16::MAX`:
+    let scaled = _mm_add_pd(
+        _mm_mul_pd(magnitude, _mm_set1_pd(i16::MAX as f64)),
+        _mm_and_pd(is_negative, magnitude),
+    );
+
+    // Rounding half towards zero equals `ceil(scaled - 0.5)`, i.e. truncating plus one for any fraction:
+    let shifted = _mm_sub_pd(scaled, _mm_set1_pd(0.5));
+    let truncated = _mm_cvttpd_epi32(shifted);
+    let has_fraction = _mm_cmpgt_pd(shifted, _mm_cvtepi32_pd(truncated));
+
+    // Narrow the 64-bit masks to the lower two 32-bit lanes:
+    let has_fraction = _mm_shuffle_epi32(_mm_castpd_si128(has_fraction), 0b00_00_10_00);
+    let is_negative = _mm_shuffle_epi32(_mm_castpd_si128(is_negative), 0b00_00_10_00);
+
+    let rounded = _mm_sub_epi32(truncated, has_fraction);
+    // Negate where negative, via two's complement:
+    _mm_sub_epi32(_mm_xor_si128(rounded, is_negative), is_negative)
+}
+
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn interleave_u16_avx2(input: &[u16], output: &mut [u16], channels: usize) {
+    if channels != 2 {
+        return interleave_u16_sse2(input, output, channels);
+    }
+
+    let frames = input.len() / 2;
+    let simd_frames = frames - (frames % 16);
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    for frame in (0..simd_frames).step_by(16) {
+        let left = _mm256_loadu_si256(src.add(frame) as *const __m256i);
+        let right = _mm256_loadu_si256(src.add(frames + frame) as *const __m256i);
+        // Frames 0..4 & 8..12, and 4..8 & 12..16:
+        let lo = _mm256_unpacklo_epi16(left, right);
+        let hi = _mm256_unpackhi_epi16(left, right);
+        let dst = dst.add(frame * 2) as *mut __m256i;
+        _mm256_storeu_si256(dst, _mm256_permute2x128_si256(lo, hi, 0x20));
+        _mm256_storeu_si256(dst.add(1), _mm256_permute2x128_si256(lo, hi, 0x31));
+    }
+
+    scalar::interleave_frames(input, output, 2, simd_frames..frames);
+}
+
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn deinterleave_u16_avx2(input: &[u16], output: &mut [u16], channels: usize) {
+    if channels != 2 {
+        return deinterleave_u16_sse2(input, output, channels);
+    }
+
+    let frames = input.len() / 2;
+    let simd_frames = frames - (frames % 16);
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    for frame in (0..simd_frames).step_by(16) {
+        let src = src.add(frame * 2) as *const __m256i;
+        let lo = _mm256_loadu_si256(src);
+        let hi = _mm256_loadu_si256(src.add(1));
+
+        // Packing operates per 128-bit lane, yielding frames 0..4, 8..12, 4..8 and 12..16:
+        let left = _mm256_packs_epi32(
+            _mm256_srai_epi32(_mm256_slli_epi32(lo, 16), 16),
+            _mm256_srai_epi32(_mm256_slli_epi32(hi, 16), 16),
+        );
+        let right = _mm256_packs_epi32(_mm256_srai_epi32(lo, 16), _mm256_srai_epi32(hi, 16));
+
+        let left = _mm256_permute4x64_epi64(left, 0b11_01_10_00);
+        let right = _mm256_permute4x64_epi64(right, 0b11_01_10_00);
+        _mm256_storeu_si256(dst.add(frame) as *mut __m256i, left);
+        _mm256_storeu_si256(dst.add(frames + frame) as *mut __m256i, right);
+    }
+
+    scalar::deinterleave_frames(input, output, 2, simd_frames..frames);
+}
+
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn interleave_u32_avx2(input: &[u32], output: &mut [u32], channels: usize) {
+    if channels != 2 {
+        return interleave_u32_sse2(input, output, channels);
+    }
+
+    let frames = input.len() / 2;
+    let simd_frames = frames - (frames % 8);
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    for frame in (0..simd_frames).step_by(8) {
+        let left = _mm256_loadu_ps(src.add(frame) as *const f32);
+        let right = _mm256_loadu_ps(src.add(frames + frame) as *const f32);
+        // Frames 0..2 & 4..6, and 2..4 & 6..8:
+        let lo = _mm256_unpacklo_ps(left, right);
+        let hi = _mm256_unpackhi_ps(left, right);
+        let dst = dst.add(frame * 2) as *mut f32;
+        _mm256_storeu_ps(dst, _mm256_permute2f128_ps(lo, hi, 0x20));
+        _mm256_storeu_ps(dst.add(8), _mm256_permute2f128_ps(lo, hi, 0x31));
+    }
+
+    scalar::interleave_frames(input, output, 2, simd_frames..frames);
+}
+
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn deinterleave_u32_avx2(input: &[u32], output: &mut [u32], channels: usize) {
+    if channels != 2 {
+        return deinterleave_u32_sse2(input, output, channels);
+    }
+
+    let frames = input.len() / 2;
+    let simd_frames = frames - (frames % 8);
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    for frame in (0..simd_frames).step_by(8) {
+        let src = src.add(frame * 2) as *const f32;
+        let lo = _mm256_loadu_ps(src);
+        let hi = _mm256_loadu_ps(src.add(8));
+
+        // Shuffling operates per 128-bit lane, yielding frames 0..2, 4..6, 2..4 and 6..8:
+        let left = _mm256_castps_pd(_mm256_shuffle_ps(lo, hi, 0b10_00_10_00));
+        let right = _mm256_castps_pd(_mm256_shuffle_ps(lo, hi, 0b11_01_11_01));
+
+        let left = _mm256_permute4x64_pd(left, 0b11_01_10_00);
+        let right = _mm256_permute4x64_pd(right, 0b11_01_10_00);
+        _mm256_storeu_pd(dst.add(frame) as *mut f64, left);
+        _mm256_storeu_pd(dst.add(frames + frame) as *mut f64, right);
+    }
+
+    scalar::deinterleave_frames(input, output, 2, simd_frames..frames);
+}
+
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn i16_to_f32_avx2(input: &[i16], output: &mut [f32]) {
+    let simd_len = input.len() - (input.len() % 16);
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    let zero = _mm256_setzero_ps();
+    let one = _mm256_set1_ps(1.0);
+    let max = _mm256_set1_ps(i16::MAX as f32);
+
+    for index in (0..simd_len).step_by(16) {
+        let samples = _mm256_loadu_si256(src.add(index) as *const __m256i);
+        let lo = _mm256_cvtepi16_epi32(_mm256_castsi256_si128(samples));
+        let hi = _mm256_cvtepi16_epi32(_mm256_extracti128_si256(samples, 1));
+
+        for (offset, samples) in [(0, lo), (8, hi)] {
+            let samples = _mm256_cvtepi32_ps(samples);
+            // Divide negative samples by `-i16::MIN`, positive ones by `i16::MAX`:
+            let is_negative = _mm256_cmp_ps(samples, zero, _CMP_LT_OQ);
+            let divisor = _mm256_add_ps(max, _mm256_and_ps(is_negative, one));
+            _mm256_storeu_ps(dst.add(index + offset), _mm256_div_ps(samples, divisor));
+        }
+    }
+
+    scalar::i16_to_f32(&input[simd_len..], &mut output[simd_len..]);
+}
+
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn f32_to_i16_avx2(input: &[f32], output: &mut [i16]) {
+    let simd_len = input.len() - (input.len() % 8);
+    let (src, dst) = (input.as_ptr(), output.as_mut_ptr());
+
+    for index in (0..simd_len).step_by(8) {
+        let samples = _mm256_loadu_ps(src.add(index));
+
+        // Replace NaNs with zero, then clamp:
+        let samples = _mm256_and_ps(samples, _mm256_cmp_ps(samples, samples, _CMP_ORD_Q));
+        let samples = _mm256_min_ps(
+            _mm256_max_ps(samples, _mm256_set1_ps(-1.0)),
+            _mm256_set1_ps(1.0),
+        );
+
+        let lo = round_f64_to_i16_avx2(_mm256_cvtps_pd(_mm256_castps256_ps128(samples)));
+        let hi = round_f64_to_i16_avx2(_mm256_cvtps_pd(_mm256_extractf128_ps(samples, 1)));
+        _mm_storeu_si128(dst.add(index) as *mut __m128i, _mm_packs_epi32(lo, hi));
+    }
+
+    scalar::f32_to_i16(&input[simd_len..], &mut output[simd_len..]);
+}
+
+/// Scales four clamped samples to the range of `i16` and rounds them half towards zero,
+/// returning them as `i32`s.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn round_f64_to_i16_avx2(samples: __m256d) -> __m128i {
+    let magnitude = _mm256_andnot_pd(_mm256_set1_pd(-0.0), samples);
+    let is_negative = _mm256_cmp_pd(samples, _mm256_setzero_pd(), _CMP_LT_OQ);
+
+    // Scale negative samples by `-i16::MIN`, positive ones by `i16::MAX`:
+    let scaled = _mm256_add_pd(
+        _mm256_mul_pd(magnitude, _mm256_set1_pd(i16::MAX as f64)),
+        _mm256_and_pd(is_negative, magnitude),
+    );
+
+    // Rounding half towards zero equals `ceil(scaled - 0.5)`, i.e. truncating plus one for any fraction:
+    let shifted = _mm256_sub_pd(scaled, _mm256_set1_pd(0.5));
+    let truncated = _mm256_cvttpd_epi32(shifted);
+    let has_fraction = _mm256_cmp_pd(shifted, _mm256_cvtepi32_pd(truncated), _CMP_GT_OQ);
+
+    // Narrow the 64-bit masks to 32-bit lanes:
+    let narrow = _mm256_setr_epi32(0, 2, 4, 6, 0, 2, 4, 6);
+    let has_fraction = _mm256_castsi256_si128(_mm256_permutevar8x32_epi32(
+        _mm256_castpd_si256(has_fraction),
+        narrow,
+    ));
+    let is_negative = _mm256_castsi256_si128(_mm256_permutevar8x32_epi32(
+        _mm256_castpd_si256(is_negative),
+        narrow,
+    ));
+
+    let rounded = _mm_sub_epi32(truncated, has_fraction);
+    // Negate where negative, via two's complement:
+    _mm_sub_epi32(_mm_xor_si128(rounded, is_negative), is_negative)
+}
@@ -0,0 +1,449 @@
+//! Voice activity detection.
+//!
+//! Each frame gets classified by combining its energy, relative to an adaptively tracked noise floor,
+//! with two spectral features: the spectral flatness (i.e. the ratio of the power spectrum's geometric
+//! to its arithmetic mean), which is close to `1.0` for noise and low for tonal signals such as voiced speech,
+//! and the fraction of the frame's energy within the speech band.
+
+use std::f32::consts::PI;
+
+use crate::audio::{
+    buffer::{layout::BufferLayout, BufferRef},
+    RawSample,
+};
+
+/// The frequency range containing most of the energy of speech, in Hz.
+const SPEECH_BAND: (f32, f32) = (300.0, 4000.0);
+
+/// A frame's classification.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum VoiceActivity {
+    Speech,
+    Silence,
+}
+
+impl VoiceActivity {
+    pub fn is_speech(&self) -> bool {
+        matches!(self, Self::Speech)
+    }
+}
+
+/// The tuning parameters of a [`VoiceActivityDetector`].
+#[derive(PartialEq, Clone, Debug)]
+pub struct VadConfig {
+    /// The minimum level above the noise floor for a frame to be considered speech, in dB.
+    pub energy_margin_db: f32,
+    /// The minimum absolute level for a frame to be considered speech, in dBFS.
+    pub min_energy_dbfs: f32,
+    /// The maximum spectral flatness for a frame to be considered speech.
+    pub max_flatness: f32,
+    /// The minimum fraction of energy within the speech band for a frame to be considered speech.
+    pub min_speech_band_ratio: f32,
+    /// The number of frames to keep reporting speech for after the last frame detected as speech,
+    /// bridging short pauses and avoiding clipped word endings.
+    pub hangover_frames: usize,
+    /// The weight of each non-speech frame's level when updating the noise floor.
+    pub noise_adaptation: f32,
+    /// The weight of each speech frame's level when updating the noise floor,
+    /// which lets steady tones (e.g. hum or ringing) eventually be taken for background noise.
+    pub speech_noise_adaptation: f32,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            energy_margin_db: 9.0,
+            min_energy_dbfs: -55.0,
+            max_flatness: 0.3,
+            min_speech_band_ratio: 0.4,
+            hangover_frames: 8,
+            noise_adaptation: 0.05,
+            speech_noise_adaptation: 0.005,
+        }
+    }
+}
+
+/// A streaming voice activity detector, classifying frames (e.g. of 10 ms or 20 ms) one after another.
+#[derive(Clone, Debug)]
+pub struct VoiceActivityDetector {
+    sample_rate: u32,
+    config: VadConfig,
+    noise_floor_dbfs: f32,
+    // The number of frames left before reporting silence again:
+    hangover: usize,
+}
+
+impl VoiceActivityDetector {
+    pub fn new(sample_rate: u32, config: VadConfig) -> Self {
+        assert!(sample_rate > 0);
+
+        let noise_floor_dbfs = config.min_energy_dbfs;
+
+        Self {
+            sample_rate,
+            config,
+            noise_floor_dbfs,
+            hangover: 0,
+        }
+    }
+
+    pub fn config(&self) -> &VadConfig {
+        &self.config
+    }
+
+    /// Returns the currently estimated level of the background noise, in dBFS.
+    pub fn noise_floor_dbfs(&self) -> f32 {
+        self.noise_floor_dbfs
+    }
+
+    pub fn reset(&mut self) {
+        self.noise_floor_dbfs = self.config.min_energy_dbfs;
+        self.hangover = 0;
+    }
+
+    /// Classifies a frame, with all of its channels being mixed down before analysis.
+    pub fn process<T, L>(&mut self, buffer: BufferRef<'_, T, L>) -> VoiceActivity
+    where
+        T: RawSample,
+        L: BufferLayout,
+    {
+        let mono = mix_down(&buffer);
+        let features = Features::analyze(&mono, self.sample_rate);
+
+        let is_loud = features.energy_dbfs >= self.config.min_energy_dbfs
+            && features.energy_dbfs >= self.noise_floor_dbfs + self.config.energy_margin_db;
+        let is_speech_like = features.flatness <= self.config.max_flatness
+            && features.speech_band_ratio >= self.config.min_speech_band_ratio;
+
+        let is_speech = is_loud && is_speech_like;
+
+        // Falling levels get tracked quickly, rising ones slowly, and even more so during speech:
+        let energy_dbfs = features.energy_dbfs.max(-120.0);
+        let adaptation = if energy_dbfs < self.noise_floor_dbfs {
+            0.5
+        } else if is_speech {
+            self.config.speech_noise_adaptation
+        } else {
+            self.config.noise_adaptation
+        };
+        self.noise_floor_dbfs += adaptation * (energy_dbfs - self.noise_floor_dbfs);
+
+        if is_speech {
+            self.hangover = self.config.hangover_frames;
+            VoiceActivity::Speech
+        } else if self.hangover > 0 {
+            self.hangover -= 1;
+            VoiceActivity::Speech
+        } else {
+            VoiceActivity::Silence
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Features {
+    energy_dbfs: f32,
+    flatness: f32,
+    speech_band_ratio: f32,
+}
+
+impl Features {
+    fn analyze(samples: &[f32], sample_rate: u32) -> Self {
+        let power = samples.iter().map(|sample| sample * sample).sum::<f32>()
+            / (samples.len().max(1) as f32);
+        let energy_dbfs = 10.0 * power.log10();
+
+        let spectrum = power_spectrum(samples);
+
+        // Ignore the DC bin, which says nothing about the signal's tonality:
+        let bins = &spectrum[1..];
+        let bin_width = (sample_rate as f32) / ((2 * spectrum.len()) as f32);
+
+        let total: f32 = bins.iter().sum();
+        if total <= f32::EPSILON {
+            return Self {
+                energy_dbfs,
+                flatness: 1.0,
+                speech_band_ratio: 0.0,
+            };
+        }
+
+        let arithmetic_mean = total / (bins.len() as f32);
+        let log_mean = bins
+            .iter()
+            .map(|power| (power + f32::MIN_POSITIVE).ln())
+            .sum::<f32>()
+            / (bins.len() as f32);
+        let flatness = log_mean.exp() / arithmetic_mean;
+
+        let speech_band: f32 = bins
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| {
+                let frequency = ((index + 1) as f32) * bin_width;
+                frequency >= SPEECH_BAND.0 && frequency <= SPEECH_BAND.1
+            })
+            .map(|(_, power)| power)
+            .sum();
+
+        Self {
+            energy_dbfs,
+            flatness,
+            speech_band_ratio: speech_band / total,
+        }
+    }
+}
+
+fn mix_down<T, L>(buffer: &BufferRef<'_, T, L>) -> Vec<f32>
+where
+    T: RawSample,
+    L: BufferLayout,
+{
+    let info = buffer.info();
+    let samples = buffer.samples();
+    let scale = 1.0 / (info.channels() as f32);
+
+    (0..info.frames())
+        .map(|frame| {
+            (0..info.channels())
+                .map(|channel| {
+                    let raw = samples[L::index_of(&info, channel, frame)];
+                    raw.to_f32()
+                })
+                .sum::<f32>()
+                * scale
+        })
+        .collect()
+}
+
+// Returns the power of the non-negative frequency bins of the Hann-windowed, zero-padded samples.
+fn power_spectrum(samples: &[f32]) -> Vec<f32> {
+    let size = samples.len().max(2).next_power_of_two();
+
+    let mut real = vec![0.0; size];
+    let mut imaginary = vec![0.0; size];
+
+    let denominator = (samples.len().max(2) - 1) as f32;
+    for (index, sample) in samples.iter().enumerate() {
+        let window = 0.5 - (0.5 * ((2.0 * PI * (index as f32)) / denominator).cos());
+        real[index] = sample * window;
+    }
+
+    fft(&mut real, &mut imaginary);
+
+    real.iter()
+        .zip(&imaginary)
+        .take(size / 2)
+        .map(|(real, imaginary)| (real * real) + (imaginary * imaginary))
+        .collect()
+}
+
+// An in-place, iterative radix-2 FFT.
+fn fft(real: &mut [f32], imaginary: &mut [f32]) {
+    let size = real.len();
+    debug_assert!(size.is_power_of_two());
+
+    // Bit-reversal permutation:
+    let mut j = 0;
+    for i in 1..size {
+        let mut bit = size >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            real.swap(i, j);
+            imaginary.swap(i, j);
+        }
+    }
+
+    let mut length = 2;
+    while length <= size {
+        let angle = -2.0 * PI / (length as f32);
+        for start in (0..size).step_by(length) {
+            for k in 0..(length / 2) {
+                let (sin, cos) = (angle * (k as f32)).sin_cos();
+                let (even, odd) = (start + k, start + k + (length / 2));
+
+                let odd_real = (real[odd] * cos) - (imaginary[odd] * sin);
+                let odd_imaginary = (real[odd] * sin) + (imaginary[odd] * cos);
+
+                real[odd] = real[even] - odd_real;
+                imaginary[odd] = imaginary[even] - odd_imaginary;
+                real[even] += odd_real;
+                imaginary[even] += odd_imaginary;
+            }
+        }
+        length <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::audio::buffer::{layout::Interleaved, Buffer};
+
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 48_000;
+    const FRAMES: usize = 480;
+
+    // A crude model of a voiced vowel, with a 150 Hz fundamental and decaying harmonics:
+    fn voiced(frame: usize, amplitude: f32) -> Buffer<f32, Interleaved> {
+        let samples = (0..FRAMES)
+            .map(|index| {
+                let time = ((frame * FRAMES) + index) as f32 / (SAMPLE_RATE as f32);
+                (1..=20)
+                    .map(|harmonic| {
+                        let frequency = 150.0 * (harmonic as f32);
+                        let weight = if frequency >= 300.0 { 1.0 } else { 0.2 };
+                        weight * (2.0 * PI * frequency * time).sin() / (harmonic as f32).sqrt()
+                    })
+                    .sum::<f32>()
+                    * amplitude
+            })
+            .collect();
+        Buffer::new(samples, 1)
+    }
+
+    fn noise(rng: &mut StdRng, amplitude: f32) -> Buffer<f32, Interleaved> {
+        let samples = (0..FRAMES)
+            .map(|_| rng.gen_range(-amplitude..amplitude))
+            .collect();
+        Buffer::new(samples, 1)
+    }
+
+    #[test]
+    fn fft() {
+        let mut real = vec![1.0, 0.0, -1.0, 0.0];
+        let mut imaginary = vec![0.0; 4];
+
+        super::fft(&mut real, &mut imaginary);
+
+        nearly_eq::assert_nearly_eq!(real, vec![0.0, 2.0, 0.0, 2.0]);
+        nearly_eq::assert_nearly_eq!(imaginary, vec![0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn silence() {
+        let mut subject = VoiceActivityDetector::new(SAMPLE_RATE, VadConfig::default());
+        let silence = Buffer::<i16, Interleaved>::new(vec![0; FRAMES], 1);
+
+        for _ in 0..10 {
+            assert_eq!(subject.process(silence.as_ref()), VoiceActivity::Silence);
+        }
+    }
+
+    #[test]
+    fn speech() {
+        let mut subject = VoiceActivityDetector::new(SAMPLE_RATE, VadConfig::default());
+
+        for frame in 0..10 {
+            let buffer = voiced(frame, 0.05);
+            assert_eq!(subject.process(buffer.as_ref()), VoiceActivity::Speech);
+        }
+    }
+
+    #[test]
+    fn quiet_speech() {
+        let mut subject = VoiceActivityDetector::new(SAMPLE_RATE, VadConfig::default());
+
+        let buffer = voiced(0, 0.0001);
+        assert_eq!(subject.process(buffer.as_ref()), VoiceActivity::Silence);
+    }
+
+    #[test]
+    fn noise_is_not_speech() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut subject = VoiceActivityDetector::new(SAMPLE_RATE, VadConfig::default());
+
+        for _ in 0..40 {
+            let buffer = noise(&mut rng, 0.1);
+            assert_eq!(subject.process(buffer.as_ref()), VoiceActivity::Silence);
+        }
+
+        // The noise floor has adapted to the noise's level (of ~-25 dBFS):
+        assert!(subject.noise_floor_dbfs() > -30.0);
+    }
+
+    #[test]
+    fn speech_in_noise() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut subject = VoiceActivityDetector::new(SAMPLE_RATE, VadConfig::default());
+
+        for _ in 0..50 {
+            let buffer = noise(&mut rng, 0.01);
+            subject.process(buffer.as_ref());
+        }
+
+        let noisy = voiced(0, 0.1);
+        let noisy = Buffer::<f32, Interleaved>::new(
+            noisy
+                .samples()
+                .iter()
+                .zip(noise(&mut rng, 0.01).samples())
+                .map(|(voice, noise)| voice + noise)
+                .collect(),
+            1,
+        );
+
+        assert_eq!(subject.process(noisy.as_ref()), VoiceActivity::Speech);
+    }
+
+    fn tone(frame: usize, frequency: f32, amplitude: f32) -> Buffer<f32, Interleaved> {
+        let samples = (0..FRAMES)
+            .map(|index| {
+                let time = ((frame * FRAMES) + index) as f32 / (SAMPLE_RATE as f32);
+                amplitude * (2.0 * PI * frequency * time).sin()
+            })
+            .collect();
+        Buffer::new(samples, 1)
+    }
+
+    #[test]
+    fn steady_tone_becomes_background() {
+        for &(frequency, amplitude) in &[(440.0, 0.1), (1000.0, 0.05)] {
+            let mut subject = VoiceActivityDetector::new(SAMPLE_RATE, VadConfig::default());
+
+            // Five seconds of the tone:
+            let activities: Vec<_> = (0..500)
+                .map(|frame| subject.process(tone(frame, frequency, amplitude).as_ref()))
+                .collect();
+
+            // The tone's onset is taken for speech, but not its steady continuation:
+            assert!(activities[0].is_speech());
+            assert!(activities[400..]
+                .iter()
+                .all(|activity| !activity.is_speech()));
+            assert!(subject.noise_floor_dbfs() > -40.0);
+        }
+    }
+
+    #[test]
+    fn hangover() {
+        let config = VadConfig {
+            hangover_frames: 3,
+            ..VadConfig::default()
+        };
+        let mut subject = VoiceActivityDetector::new(SAMPLE_RATE, config);
+
+        let speech = voiced(0, 0.05);
+        let silence = Buffer::<f32, Interleaved>::new(vec![0.0; FRAMES], 1);
+
+        assert!(subject.process(speech.as_ref()).is_speech());
+
+        for _ in 0..3 {
+            assert!(subject.process(silence.as_ref()).is_speech());
+        }
+        assert!(!subject.process(silence.as_ref()).is_speech());
+
+        // Speech re-arms the hangover:
+        assert!(subject.process(speech.as_ref()).is_speech());
+        assert!(subject.process(silence.as_ref()).is_speech());
+
+        subject.reset();
+        assert!(!subject.process(silence.as_ref()).is_speech());
+    }
+}
@@ -1,6 +1,8 @@
 use std::io;
 use thiserror::Error;
 
+use crate::track::constraint::OverconstrainedError;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Error, Debug, PartialEq)]
@@ -47,6 +49,8 @@ pub enum Error {
     Io(#[source] IoError),
     #[error("{0}")]
     Rtp(#[from] rtp::Error),
+    #[error("{0}")]
+    Overconstrained(#[from] OverconstrainedError),
 
     #[error("{0}")]
     Other(String),
@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(clippy::assertions_on_constants)]
 mod h264_reader_test;
 
 use crate::error::{Error, Result};
@@ -9,9 +10,10 @@ use std::io::Read;
 
 /// NalUnitType is the type of a NAL
 /// Enums for NalUnitTypes
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum NalUnitType {
     /// Unspecified
+    #[default]
     Unspecified = 0,
     /// Coded slice of a non-IDR picture
     CodedSliceNonIdr = 1,
@@ -48,12 +50,6 @@ pub enum NalUnitType {
     // 24..31                                            // Unspecified
 }
 
-impl Default for NalUnitType {
-    fn default() -> Self {
-        NalUnitType::Unspecified
-    }
-}
-
 impl fmt::Display for NalUnitType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match *self {
@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(clippy::assertions_on_constants)]
 mod ivf_reader_test;
 
 use crate::error::{Error, Result};
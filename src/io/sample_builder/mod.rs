@@ -1,10 +1,23 @@
 #[cfg(test)]
+#[allow(
+    clippy::bool_assert_comparison,
+    clippy::needless_update,
+    clippy::useless_vec
+)]
 mod sample_builder_test;
 #[cfg(test)]
 mod sample_sequence_location_test;
+#[cfg(test)]
+mod unwrapper_test;
+#[cfg(test)]
+mod wall_clock_test;
 
 pub mod sample_sequence_location;
+mod unwrapper;
+mod wall_clock;
 
+use std::collections::VecDeque;
+use std::ops::Index;
 use std::time::{Duration, SystemTime};
 
 use bytes::Bytes;
@@ -13,6 +26,8 @@ use rtp::{packet::Packet, packetizer::Depacketizer};
 use crate::Sample;
 
 use self::sample_sequence_location::{Comparison, SampleSequenceLocation};
+use self::unwrapper::{SequenceNumberUnwrapper, TimestampUnwrapper};
+use self::wall_clock::WallClock;
 
 /// SampleBuilder buffers packets until media frames are complete.
 pub struct SampleBuilder<T: Depacketizer> {
@@ -20,8 +35,8 @@ pub struct SampleBuilder<T: Depacketizer> {
     max_late: u16,
     /// max timestamp between old and new timestamps before dropping packets
     max_late_timestamp: u32,
-    buffer: Vec<Option<Packet>>,
-    prepared_samples: Vec<Option<Sample>>,
+    buffer: PacketBuffer,
+    prepared_samples: VecDeque<Sample>,
 
     /// Interface that allows us to take RTP packets to samples
     depacketizer: T,
@@ -35,11 +50,15 @@ pub struct SampleBuilder<T: Depacketizer> {
     /// active contains the active head/tail of the timestamp being actively processed
     active: SampleSequenceLocation,
 
-    /// prepared contains the samples that have been processed to date
-    prepared: SampleSequenceLocation,
-
     /// number of packets forced to be dropped
     dropped_packets: u16,
+
+    /// extends pushed sequence numbers and timestamps to 64 bits
+    sequence_numbers: SequenceNumberUnwrapper,
+    timestamps: TimestampUnwrapper,
+
+    /// maps RTP timestamps to the wall-clock capture time of samples
+    wall_clock: WallClock,
 }
 
 impl<T: Depacketizer> SampleBuilder<T> {
@@ -53,14 +72,16 @@ impl<T: Depacketizer> SampleBuilder<T> {
         Self {
             max_late,
             max_late_timestamp: 0,
-            buffer: vec![None; u16::MAX as usize + 1],
-            prepared_samples: (0..=u16::MAX as usize).map(|_| None).collect(),
+            buffer: PacketBuffer::new((max_late as usize + 1).next_power_of_two()),
+            prepared_samples: VecDeque::new(),
             depacketizer,
             sample_rate,
             filled: SampleSequenceLocation::new(),
             active: SampleSequenceLocation::new(),
-            prepared: SampleSequenceLocation::new(),
             dropped_packets: 0,
+            sequence_numbers: SequenceNumberUnwrapper::default(),
+            timestamps: TimestampUnwrapper::default(),
+            wall_clock: WallClock::new(sample_rate),
         }
     }
 
@@ -70,41 +91,55 @@ impl<T: Depacketizer> SampleBuilder<T> {
         self
     }
 
+    /// Records the NTP/RTP timestamp pair of an RTCP Sender Report received for
+    /// this stream, as found in its `ntp_time` and `rtp_time` fields.
+    ///
+    /// Once a Sender Report has been seen, [`Sample::timestamp`] is the capture
+    /// time derived from the latest report, which allows synchronizing streams
+    /// from the same sender. Before that it is estimated from packet arrival times.
+    /// Reports with an NTP time before the UNIX epoch are ignored.
+    pub fn push_sender_report(&mut self, ntp_time: u64, rtp_time: u32) {
+        // Reports may be delayed arbitrarily relative to the packets,
+        // so they mustn't move the reference for unwrapping the packets' timestamps:
+        let timestamp = self.timestamps.peek(rtp_time);
+        self.wall_clock.on_sender_report(ntp_time, timestamp);
+    }
+
     fn too_old(&self, location: &SampleSequenceLocation) -> bool {
         if self.max_late_timestamp == 0 {
             return false;
         }
 
-        let mut found_head: Option<u32> = None;
-        let mut found_tail: Option<u32> = None;
+        let mut found_head: Option<u64> = None;
+        let mut found_tail: Option<u64> = None;
 
         let mut i = location.head;
         while i != location.tail {
-            if let Some(ref packet) = self.buffer[i as usize] {
-                found_head = Some(packet.header.timestamp);
+            if let Some(extended) = self.buffer.extended(i) {
+                found_head = Some(extended.timestamp);
                 break;
             }
             i = i.wrapping_add(1);
         }
 
-        if found_head == None {
+        if found_head.is_none() {
             return false;
         }
 
-        let mut i = location.tail - 1;
+        let mut i = location.tail.wrapping_sub(1);
         while i != location.head {
-            if let Some(ref packet) = self.buffer[i as usize] {
-                found_tail = Some(packet.header.timestamp);
+            if let Some(extended) = self.buffer.extended(i) {
+                found_tail = Some(extended.timestamp);
                 break;
             }
             i = i.wrapping_sub(1);
         }
 
-        if found_tail == None {
+        if found_tail.is_none() {
             return false;
         }
 
-        found_tail.unwrap() - found_head.unwrap() > self.max_late_timestamp
+        found_tail.unwrap().saturating_sub(found_head.unwrap()) > self.max_late_timestamp as u64
     }
 
     /// Returns the timestamp associated with a given sample location
@@ -122,7 +157,7 @@ impl<T: Depacketizer> SampleBuilder<T> {
     }
 
     fn release_packet(&mut self, i: u16) {
-        self.buffer[i as usize] = None;
+        self.buffer.release(i);
     }
 
     /// Clears all buffers that have already been consumed by
@@ -187,7 +222,13 @@ impl<T: Depacketizer> SampleBuilder<T> {
     /// this memory make sure to copy before calling push
     pub fn push(&mut self, p: Packet) {
         let sequence_number = p.header.sequence_number;
-        self.buffer[sequence_number as usize] = Some(p);
+        let extended = Extended {
+            sequence_number: self.sequence_numbers.unwrap(sequence_number),
+            timestamp: self.timestamps.unwrap(p.header.timestamp),
+        };
+        self.wall_clock
+            .on_arrival(SystemTime::now(), extended.timestamp);
+        self.buffer.stage(p, extended);
         match self.filled.compare(sequence_number) {
             Comparison::Void => {
                 self.filled.head = sequence_number;
@@ -202,6 +243,7 @@ impl<T: Depacketizer> SampleBuilder<T> {
             _ => {}
         }
         self.purge_buffers();
+        self.buffer.commit();
     }
 
     /// Creates a sample from a valid collection of RTP Packets by
@@ -257,14 +299,17 @@ impl<T: Depacketizer> SampleBuilder<T> {
         }
 
         let sample_timestamp = self.fetch_timestamp(&self.active).unwrap_or(0);
-        let mut after_timestamp = sample_timestamp;
+        let head = self.buffer.extended(consume.head).unwrap_or_default();
+        let mut after_timestamp = head.timestamp;
 
         // scan for any packet after the current and use that time stamp as the diff point
-        for i in consume.tail..self.active.tail {
-            if let Some(ref packet) = self.buffer[i as usize] {
-                after_timestamp = packet.header.timestamp;
+        let mut i = consume.tail;
+        while i != self.active.tail {
+            if let Some(extended) = self.buffer.extended(i) {
+                after_timestamp = extended.timestamp;
                 break;
             }
+            i = i.wrapping_add(1);
         }
 
         // the head set of packets is now fully consumed
@@ -293,20 +338,24 @@ impl<T: Depacketizer> SampleBuilder<T> {
             data.extend_from_slice(&p);
             i = i.wrapping_add(1);
         }
-        let samples = after_timestamp - sample_timestamp;
+        let samples = after_timestamp.saturating_sub(head.timestamp);
 
         let sample = Sample {
             data: Bytes::copy_from_slice(&data),
-            timestamp: SystemTime::now(),
+            timestamp: self
+                .wall_clock
+                .time_of(head.timestamp)
+                .unwrap_or_else(SystemTime::now),
             duration: Duration::from_secs_f64((samples as f64) / (self.sample_rate as f64)),
             packet_timestamp: sample_timestamp,
+            extended_packet_timestamp: head.timestamp,
+            extended_sequence_number: head.sequence_number,
             prev_dropped_packets: self.dropped_packets,
         };
 
         self.dropped_packets = 0;
 
-        self.prepared_samples[self.prepared.tail as usize] = Some(sample);
-        self.prepared.tail = self.prepared.tail.wrapping_add(1);
+        self.prepared_samples.push_back(sample);
 
         self.purge_consumed_location(&consume, true);
         self.purge_consumed_buffers();
@@ -318,15 +367,7 @@ impl<T: Depacketizer> SampleBuilder<T> {
     /// returns the next valid sample (or None if no sample is compiled).
     pub fn pop(&mut self) -> Option<Sample> {
         self.build_sample(false);
-        if self.prepared.empty() {
-            return None;
-        }
-        let result = std::mem::replace(
-            &mut self.prepared_samples[self.prepared.head as usize],
-            None,
-        );
-        self.prepared.head = self.prepared.head.wrapping_add(1);
-        result
+        self.prepared_samples.pop_front()
     }
 
     /// Compiles pushed RTP packets into media samples and then
@@ -342,7 +383,107 @@ impl<T: Depacketizer> SampleBuilder<T> {
     }
 }
 
-/// Computes the distance between two sequence numbers
+/// Sequence number and timestamp of a buffered packet extended to 64 bits.
+#[derive(Debug, Default, Clone, Copy)]
+struct Extended {
+    sequence_number: u64,
+    timestamp: u64,
+}
+
+/// Ring buffer of RTP packets indexed by sequence number.
+///
+/// Once the buffers are purged the filled range never spans more than `max_late`
+/// sequence numbers, so `max_late + 1` slots are enough to hold every packet that
+/// is still waiting to be consumed. The slot count is rounded up to a power of two
+/// so that consecutive sequence numbers stay in consecutive slots when the 16-bit
+/// sequence number wraps around. A freshly pushed packet is staged outside the
+/// ring until purging is done, since it may share a slot with an older packet that
+/// is about to be consumed or dropped.
+struct PacketBuffer {
+    slots: Vec<Option<Packet>>,
+    extended: Vec<Extended>,
+    staged: Option<Packet>,
+    staged_extended: Extended,
+}
+
+/// Returned for sequence numbers that are not currently buffered.
+static NO_PACKET: Option<Packet> = None;
+
+impl PacketBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            slots: vec![None; capacity],
+            extended: vec![Extended::default(); capacity],
+            staged: None,
+            staged_extended: Extended::default(),
+        }
+    }
+
+    fn slot(&self, sequence_number: u16) -> usize {
+        sequence_number as usize % self.slots.len()
+    }
+
+    /// Holds `packet` aside until [`PacketBuffer::commit`] is called.
+    fn stage(&mut self, packet: Packet, extended: Extended) {
+        self.staged = Some(packet);
+        self.staged_extended = extended;
+    }
+
+    /// Moves the staged packet, if it has not been released, into its slot.
+    fn commit(&mut self) {
+        if let Some(packet) = self.staged.take() {
+            let slot = self.slot(packet.header.sequence_number);
+            self.slots[slot] = Some(packet);
+            self.extended[slot] = self.staged_extended;
+        }
+    }
+
+    fn release(&mut self, sequence_number: u16) {
+        if holds(&self.staged, sequence_number) {
+            self.staged = None;
+        }
+        let slot = self.slot(sequence_number);
+        if holds(&self.slots[slot], sequence_number) {
+            self.slots[slot] = None;
+        }
+    }
+
+    /// Returns the extended sequence number and timestamp of a buffered packet.
+    fn extended(&self, sequence_number: u16) -> Option<Extended> {
+        if holds(&self.staged, sequence_number) {
+            return Some(self.staged_extended);
+        }
+        let slot = self.slot(sequence_number);
+        if holds(&self.slots[slot], sequence_number) {
+            Some(self.extended[slot])
+        } else {
+            None
+        }
+    }
+}
+
+impl Index<usize> for PacketBuffer {
+    type Output = Option<Packet>;
+
+    /// Returns the packet with the sequence number `index`, if buffered.
+    fn index(&self, index: usize) -> &Self::Output {
+        let sequence_number = index as u16;
+        if holds(&self.staged, sequence_number) {
+            return &self.staged;
+        }
+        let packet = &self.slots[self.slot(sequence_number)];
+        if holds(packet, sequence_number) {
+            packet
+        } else {
+            &NO_PACKET
+        }
+    }
+}
+
+fn holds(packet: &Option<Packet>, sequence_number: u16) -> bool {
+    matches!(packet, Some(p) if p.header.sequence_number == sequence_number)
+}
+
 /*pub(crate) fn seqnum_distance(head: u16, tail: u16) -> u16 {
     if head > tail {
         head.wrapping_add(tail)
@@ -351,6 +492,7 @@ impl<T: Depacketizer> SampleBuilder<T> {
     }
 }*/
 
+/// Computes the distance between two sequence numbers
 pub(crate) fn seqnum_distance(x: u16, y: u16) -> u16 {
     let diff = x.wrapping_sub(y);
     if diff > 0xFFFF / 2 {
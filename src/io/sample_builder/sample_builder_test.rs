@@ -1,5 +1,7 @@
 use rtp::{header::Header, packet::Packet, packetizer::Depacketizer};
 
+use std::time::UNIX_EPOCH;
+
 use super::*;
 
 // Turns u8 integers into Bytes Array
@@ -141,14 +143,20 @@ pub fn test_sample_builder() {
                     // First sample
                     data: bytes!(1),
                     duration: Duration::from_secs(1), // technically this is the default value, but since it was in .go source....
+                    timestamp: SystemTime::now() - Duration::from_secs(2),
                     packet_timestamp: 5,
+                    extended_packet_timestamp: (1 << 32) + 5,
+                    extended_sequence_number: (1 << 16) + 5000,
                     ..Default::default()
                 },
                 Sample {
                     // Second sample
                     data: bytes!(2),
                     duration: Duration::from_secs(1),
+                    timestamp: SystemTime::now() - Duration::from_secs(1),
                     packet_timestamp: 6,
+                    extended_packet_timestamp: (1 << 32) + 6,
+                    extended_sequence_number: (1 << 16) + 5001,
                     ..Default::default()
                 },
             ],
@@ -236,7 +244,10 @@ pub fn test_sample_builder() {
                 // First sample
                 data: bytes!(1),
                 duration: Duration::from_secs(2),
+                timestamp: SystemTime::now() - Duration::from_secs(6),
                 packet_timestamp: 5,
+                extended_packet_timestamp: (1 << 32) + 5,
+                extended_sequence_number: (1 << 16) + 5000,
                 ..Default::default()
             }],
             max_late: 5,
@@ -405,16 +416,21 @@ pub fn test_sample_builder() {
                     // First (dropped) sample
                     data: bytes!(1),
                     duration: Duration::from_secs(2),
+                    timestamp: SystemTime::now() - Duration::from_secs(6),
                     packet_timestamp: 5,
+                    extended_packet_timestamp: (1 << 32) + 5,
+                    extended_sequence_number: (1 << 16) + 5000,
                     ..Default::default()
                 },
                 Sample {
                     // First correct sample
                     data: bytes!(2),
                     duration: Duration::from_secs(2),
+                    timestamp: SystemTime::now() - Duration::from_secs(6),
                     packet_timestamp: 7,
+                    extended_packet_timestamp: (1 << 32) + 7,
+                    extended_sequence_number: (1 << 16) + 5002,
                     prev_dropped_packets: 1,
-                    ..Default::default()
                 },
             ],
             max_late: 5,
@@ -471,14 +487,20 @@ pub fn test_sample_builder() {
                     // First sample
                     data: bytes!(1),
                     duration: Duration::from_secs(1),
+                    timestamp: SystemTime::now() - Duration::from_secs(2),
                     packet_timestamp: 5,
+                    extended_packet_timestamp: (1 << 32) + 5,
+                    extended_sequence_number: (1 << 16) + 5000,
                     ..Default::default()
                 },
                 Sample {
                     // Second (duplicate) correct sample
                     data: bytes!(2, 3),
                     duration: Duration::from_secs(1),
+                    timestamp: SystemTime::now() - Duration::from_secs(1),
                     packet_timestamp: 6,
+                    extended_packet_timestamp: (1 << 32) + 6,
+                    extended_sequence_number: (1 << 16) + 5001,
                     ..Default::default()
                 },
             ],
@@ -686,35 +708,50 @@ pub fn test_sample_builder() {
                     // First sample
                     data: bytes!(1),
                     duration: Duration::from_secs(1),
+                    timestamp: SystemTime::now() - Duration::from_secs(5),
                     packet_timestamp: 1,
+                    extended_packet_timestamp: (1 << 32) + 1,
+                    extended_sequence_number: (1 << 16) + 5000,
                     ..Default::default()
                 },
                 Sample {
                     // Second sample
                     data: bytes!(2),
                     duration: Duration::from_secs(1),
+                    timestamp: SystemTime::now() - Duration::from_secs(4),
                     packet_timestamp: 2,
+                    extended_packet_timestamp: (1 << 32) + 2,
+                    extended_sequence_number: (1 << 16) + 5001,
                     ..Default::default()
                 },
                 Sample {
                     // Third sample
                     data: bytes!(3),
                     duration: Duration::from_secs(1),
+                    timestamp: SystemTime::now() - Duration::from_secs(3),
                     packet_timestamp: 3,
+                    extended_packet_timestamp: (1 << 32) + 3,
+                    extended_sequence_number: (1 << 16) + 5002,
                     ..Default::default()
                 },
                 Sample {
                     // Fourth sample
                     data: bytes!(4),
                     duration: Duration::from_secs(1),
+                    timestamp: SystemTime::now() - Duration::from_secs(2),
                     packet_timestamp: 4,
+                    extended_packet_timestamp: (1 << 32) + 4,
+                    extended_sequence_number: (1 << 16) + 5003,
                     ..Default::default()
                 },
                 Sample {
                     // Fifth sample
                     data: bytes!(5),
                     duration: Duration::from_secs(1),
+                    timestamp: SystemTime::now() - Duration::from_secs(1),
                     packet_timestamp: 5,
+                    extended_packet_timestamp: (1 << 32) + 5,
+                    extended_sequence_number: (1 << 16) + 5004,
                     ..Default::default()
                 },
             ],
@@ -811,9 +848,11 @@ pub fn test_sample_builder() {
                 // First sample
                 data: bytes!(4, 5),
                 duration: Duration::from_secs(2),
+                timestamp: SystemTime::now() - Duration::from_secs(3000),
                 packet_timestamp: 4000,
+                extended_packet_timestamp: (1 << 32) + 4000,
+                extended_sequence_number: (1 << 16) + 5013,
                 prev_dropped_packets: 13,
-                ..Default::default()
             }],
             with_head_checker: true,
             head_bytes: vec![bytes!(4)],
@@ -887,7 +926,10 @@ fn test_sample_builder_max_late() {
         Some(Sample {
             data: bytes!(0x01),
             duration: Duration::from_secs(1),
+            timestamp: SystemTime::now() - Duration::from_secs(2),
             packet_timestamp: 1,
+            extended_packet_timestamp: (1 << 32) + 1,
+            extended_sequence_number: 1 << 16,
             ..Default::default()
         }),
         s.pop(),
@@ -923,7 +965,10 @@ fn test_sample_builder_max_late() {
         Some(Sample {
             data: bytes!(0x01),
             duration: Duration::from_secs(1),
+            timestamp: SystemTime::now() - Duration::from_secs(498),
             packet_timestamp: 2,
+            extended_packet_timestamp: (1 << 32) + 2,
+            extended_sequence_number: (1 << 16) + 1,
             ..Default::default()
         }),
         s.pop(),
@@ -943,9 +988,11 @@ fn test_sample_builder_max_late() {
         Some(Sample {
             data: bytes!(0x02),
             duration: Duration::from_secs(1),
+            timestamp: SystemTime::now() - Duration::from_secs(100),
             packet_timestamp: 500,
+            extended_packet_timestamp: (1 << 32) + 500,
+            extended_sequence_number: (1 << 16) + 5000,
             prev_dropped_packets: 4998,
-            ..Default::default()
         }),
         s.pop(),
         "Failed to build samples after large gap"
@@ -954,7 +1001,10 @@ fn test_sample_builder_max_late() {
         Some(Sample {
             data: bytes!(0x02),
             duration: Duration::from_secs(1),
+            timestamp: SystemTime::now() - Duration::from_secs(99),
             packet_timestamp: 501,
+            extended_packet_timestamp: (1 << 32) + 501,
+            extended_sequence_number: (1 << 16) + 5001,
             ..Default::default()
         }),
         s.pop(),
@@ -1126,3 +1176,185 @@ fn test_sample_builder_data() {
     // only the last packet should be dropped
     assert_eq!(j, 0x1FFFF);
 }
+
+#[test]
+fn test_sample_builder_ring_buffer_collision() {
+    // with max_late of 3 the ring holds 4 packets, so sequence number 4 shares
+    // a slot with sequence number 0 which is only consumed while pushing 4
+    let mut s = SampleBuilder::new(3, FakeDepacketizer::new(), 1);
+    for (sequence_number, timestamp, payload) in [(0, 10, bytes!(0x01)), (1, 20, bytes!(0x02))] {
+        s.push(Packet {
+            header: Header {
+                sequence_number,
+                timestamp,
+                ..Default::default()
+            },
+            payload,
+        });
+    }
+    let pkt = Packet {
+        header: Header {
+            sequence_number: 4,
+            timestamp: 40,
+            ..Default::default()
+        },
+        payload: bytes!(0x03),
+    };
+    s.push(pkt.clone());
+
+    assert_eq!(
+        Some(Sample {
+            data: bytes!(0x01),
+            duration: Duration::from_secs(10),
+            timestamp: SystemTime::now() - Duration::from_secs(30),
+            packet_timestamp: 10,
+            extended_packet_timestamp: (1 << 32) + 10,
+            extended_sequence_number: 1 << 16,
+            ..Default::default()
+        }),
+        s.pop(),
+        "Failed to build the sample sharing a slot with the newest packet"
+    );
+    assert_eq!(None, s.buffer[0], "Consumed packet is still buffered");
+    assert_eq!(Some(pkt), s.buffer[4]);
+}
+
+#[test]
+fn test_sample_builder_sequence_number_wrap_around() {
+    let mut s = SampleBuilder::new(50, FakeDepacketizer::new(), 1);
+    for (sequence_number, timestamp) in [(0xfffe, 10), (0xffff, 20), (0, 30), (1, 40)] {
+        s.push(Packet {
+            header: Header {
+                sequence_number,
+                timestamp,
+                ..Default::default()
+            },
+            payload: bytes!(0x01),
+        });
+    }
+
+    for timestamp in [10, 20, 30] {
+        let (sample, packet_timestamp) = s
+            .pop_with_timestamp()
+            .expect("Failed to build sample across the sequence number wrap-around");
+        assert_eq!(timestamp, packet_timestamp);
+        assert_eq!(bytes!(0x01), sample.data);
+        assert_eq!(Duration::from_secs(10), sample.duration);
+    }
+    assert_eq!(None, s.pop());
+}
+
+#[test]
+fn test_sample_builder_timestamp_wrap_around() {
+    let mut s = SampleBuilder::new(50, FakeDepacketizer::new(), 1);
+    for (sequence_number, timestamp) in [(0xfffe, 0xffff_fff6), (0xffff, 0), (0, 10), (1, 20)] {
+        s.push(Packet {
+            header: Header {
+                sequence_number,
+                timestamp,
+                ..Default::default()
+            },
+            payload: bytes!(0x01),
+        });
+    }
+
+    let expected = [
+        (30, 0xffff_fff6, 0x1_ffff_fff6, 0x1_fffe),
+        (20, 0, 0x2_0000_0000, 0x1_ffff),
+        (10, 10, 0x2_0000_000a, 0x2_0000),
+    ];
+    for (age, packet_timestamp, extended_packet_timestamp, extended_sequence_number) in expected {
+        assert_eq!(
+            Some(Sample {
+                data: bytes!(0x01),
+                duration: Duration::from_secs(10),
+                timestamp: SystemTime::now() - Duration::from_secs(age),
+                packet_timestamp,
+                extended_packet_timestamp,
+                extended_sequence_number,
+                ..Default::default()
+            }),
+            s.pop(),
+            "Failed to build sample across the timestamp wrap-around"
+        );
+    }
+    assert_eq!(None, s.pop());
+}
+
+#[test]
+fn test_sample_builder_sender_report() {
+    let mut s = SampleBuilder::new(50, FakeDepacketizer::new(), 90000);
+    let push = |s: &mut SampleBuilder<FakeDepacketizer>, sequence_number: u16| {
+        s.push(Packet {
+            header: Header {
+                sequence_number,
+                timestamp: 90000 + 3000 * sequence_number as u32,
+                ..Default::default()
+            },
+            payload: bytes!(0x01),
+        });
+    };
+
+    // without a sender report the capture time is estimated from arrival times
+    push(&mut s, 0);
+    push(&mut s, 1);
+    let sample = s.pop().expect("Failed to build sample");
+    assert!(
+        sample.timestamp.elapsed().unwrap() < Duration::from_secs(1),
+        "Sample time should be estimated from arrival time"
+    );
+
+    // 2022-01-01T00:00:00Z
+    let unix_seconds = 1_640_995_200;
+    let sender_report_time = UNIX_EPOCH + Duration::from_secs(unix_seconds);
+    s.push_sender_report((unix_seconds + 2_208_988_800) << 32, 90000);
+
+    push(&mut s, 2);
+    push(&mut s, 3);
+    for nanos in [33_333_333, 66_666_666] {
+        let sample = s.pop().expect("Failed to build sample");
+        assert_eq!(
+            sender_report_time + Duration::from_nanos(nanos),
+            sample.timestamp
+        );
+    }
+    assert_eq!(None, s.pop());
+}
+
+#[test]
+fn test_sample_builder_sender_report_in_future() {
+    let mut s = SampleBuilder::new(50, FakeDepacketizer::new(), 1);
+
+    // the sender's clock runs an hour ahead of ours
+    let sender_report_time = SystemTime::now() + Duration::from_secs(3600);
+    let unix_seconds = sender_report_time
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    s.push_sender_report((unix_seconds + 2_208_988_800) << 32, 10);
+
+    for (sequence_number, timestamp) in [(0, 10), (1, 20)] {
+        s.push(Packet {
+            header: Header {
+                sequence_number,
+                timestamp,
+                ..Default::default()
+            },
+            payload: bytes!(0x01),
+        });
+    }
+
+    assert_eq!(
+        Some(Sample {
+            data: bytes!(0x01),
+            duration: Duration::from_secs(10),
+            timestamp: sender_report_time,
+            packet_timestamp: 10,
+            extended_packet_timestamp: (1 << 32) + 10,
+            extended_sequence_number: 1 << 16,
+            ..Default::default()
+        }),
+        s.pop(),
+        "Failed to build a sample with a timestamp in the future"
+    );
+}
@@ -0,0 +1,56 @@
+/// Unwrapper extends a wrapping RTP counter of `BITS` bits (16 for sequence
+/// numbers, 32 for timestamps) to a monotonic 64-bit value.
+///
+/// Each value is placed in the cycle that is closest to the previously
+/// unwrapped value, so reordering of less than half the counter range is
+/// handled correctly. The first value is placed in the second cycle (i.e. at
+/// `value + 2^BITS`), so that values reordered before it still unwrap to smaller
+/// ones. Values that would unwrap to before zero saturate at zero.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Unwrapper<const BITS: u32> {
+    last: Option<i64>,
+}
+
+pub(crate) type SequenceNumberUnwrapper = Unwrapper<16>;
+pub(crate) type TimestampUnwrapper = Unwrapper<32>;
+
+impl<const BITS: u32> Unwrapper<BITS> {
+    const MODULUS: i64 = 1 << BITS;
+
+    fn extend(&mut self, value: i64) -> u64 {
+        let extended = self.place(value);
+        self.last = Some(extended);
+        extended.max(0) as u64
+    }
+
+    fn place(&self, value: i64) -> i64 {
+        match self.last {
+            None => value + Self::MODULUS,
+            Some(last) => {
+                let mut delta = (value - last).rem_euclid(Self::MODULUS);
+                if delta >= Self::MODULUS / 2 {
+                    delta -= Self::MODULUS;
+                }
+                last + delta
+            }
+        }
+    }
+}
+
+impl SequenceNumberUnwrapper {
+    pub(crate) fn unwrap(&mut self, sequence_number: u16) -> u64 {
+        self.extend(sequence_number as i64)
+    }
+}
+
+impl TimestampUnwrapper {
+    pub(crate) fn unwrap(&mut self, timestamp: u32) -> u64 {
+        self.extend(timestamp as i64)
+    }
+
+    /// Unwraps `timestamp` relative to the last unwrapped value, without
+    /// making it the reference for subsequent ones.
+    pub(crate) fn peek(&self, timestamp: u32) -> u64 {
+        self.place(timestamp as i64).max(0) as u64
+    }
+}
@@ -0,0 +1,45 @@
+use super::unwrapper::*;
+
+#[test]
+fn test_sequence_number_unwrapper() {
+    let mut u = SequenceNumberUnwrapper::default();
+    assert_eq!(0x1_fffe, u.unwrap(0xfffe));
+    assert_eq!(0x1_ffff, u.unwrap(0xffff));
+    assert_eq!(0x2_0000, u.unwrap(0));
+    assert_eq!(0x2_0001, u.unwrap(1));
+    // reordered across the wrap
+    assert_eq!(0x1_ffff, u.unwrap(0xffff));
+    assert_eq!(0x2_0002, u.unwrap(2));
+    assert_eq!(0x2_8001, u.unwrap(0x8001));
+    assert_eq!(0x3_0000, u.unwrap(0));
+}
+
+#[test]
+fn test_timestamp_unwrapper() {
+    let mut u = TimestampUnwrapper::default();
+    assert_eq!(0x1_ffff_ff00, u.unwrap(0xffff_ff00));
+    assert_eq!(0x2_0000_0060, u.unwrap(0x60));
+    assert_eq!(0x1_ffff_ffc0, u.unwrap(0xffff_ffc0));
+    assert_eq!(0x2_7fff_ff00, u.unwrap(0x7fff_ff00));
+    assert_eq!(0x2_ffff_0000, u.unwrap(0xffff_0000));
+    assert_eq!(0x3_0000_0000, u.unwrap(0));
+}
+
+#[test]
+fn test_unwrapper_reordered_before_first() {
+    let mut u = SequenceNumberUnwrapper::default();
+    assert_eq!(0x1_0005, u.unwrap(5));
+    assert_eq!(0xfffe, u.unwrap(0xfffe));
+    assert_eq!(0x1_0006, u.unwrap(6));
+}
+
+#[test]
+fn test_timestamp_unwrapper_peek() {
+    let mut u = TimestampUnwrapper::default();
+    assert_eq!(0x1_0000_0060, u.peek(0x60));
+
+    assert_eq!(0x1_0000_0000, u.unwrap(0));
+    assert_eq!(0xc000_0000, u.peek(0xc000_0000));
+    // the peeked value does not become the reference for unwrapping
+    assert_eq!(0x1_4000_0001, u.unwrap(0x4000_0001));
+}
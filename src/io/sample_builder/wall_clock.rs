@@ -0,0 +1,95 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Seconds between the NTP epoch (1900) and the UNIX epoch (1970).
+const NTP_UNIX_OFFSET: u64 = 2_208_988_800;
+
+/// Converts a 64-bit NTP timestamp, as carried in RTCP Sender Reports, to
+/// [`SystemTime`]. Following RFC 4330, timestamps whose most significant bit is
+/// clear are taken to be in NTP era 1, which starts in 2036.
+///
+/// Returns `None` for timestamps before the UNIX epoch (i.e. from 1968 to 1970).
+pub(crate) fn ntp_to_system_time(ntp_time: u64) -> Option<SystemTime> {
+    let mut seconds = ntp_time >> 32;
+    if seconds & 0x8000_0000 == 0 {
+        seconds += 1 << 32;
+    }
+    let nanos = ((ntp_time & 0xFFFF_FFFF) * 1_000_000_000) >> 32;
+    let seconds = seconds.checked_sub(NTP_UNIX_OFFSET)?;
+    UNIX_EPOCH.checked_add(Duration::new(seconds, nanos as u32))
+}
+
+/// A wall-clock time paired with the extended RTP timestamp it corresponds to.
+#[derive(Debug, Clone, Copy)]
+struct Anchor {
+    time: SystemTime,
+    timestamp: u64,
+}
+
+/// WallClock maps extended RTP timestamps to wall-clock time.
+///
+/// The mapping comes from the latest RTCP Sender Report. Until one has been
+/// seen it is estimated from packet arrival times, anchored at the packet with
+/// the smallest transit time so that network jitter and buffering do not leak
+/// into the estimate.
+#[derive(Debug)]
+pub(crate) struct WallClock {
+    sample_rate: u32,
+    sender_report: Option<Anchor>,
+    arrival: Option<Anchor>,
+}
+
+impl WallClock {
+    pub(crate) fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            sender_report: None,
+            arrival: None,
+        }
+    }
+
+    /// Records the NTP time and extended RTP timestamp of a Sender Report,
+    /// ignoring reports whose NTP time has no [`SystemTime`] equivalent.
+    pub(crate) fn on_sender_report(&mut self, ntp_time: u64, timestamp: u64) {
+        if let Some(time) = ntp_to_system_time(ntp_time) {
+            self.sender_report = Some(Anchor { time, timestamp });
+        }
+    }
+
+    /// Records the arrival time of a packet with the given extended RTP timestamp.
+    pub(crate) fn on_arrival(&mut self, time: SystemTime, timestamp: u64) {
+        let earlier = match self.arrival {
+            Some(anchor) => time < self.offset(anchor, timestamp),
+            None => true,
+        };
+        if earlier {
+            self.arrival = Some(Anchor { time, timestamp });
+        }
+    }
+
+    /// Returns the wall-clock time of an extended RTP timestamp, preferring
+    /// the Sender Report mapping over the arrival time estimate.
+    pub(crate) fn time_of(&self, timestamp: u64) -> Option<SystemTime> {
+        let anchor = self.sender_report.or(self.arrival)?;
+        Some(self.offset(anchor, timestamp))
+    }
+
+    fn offset(&self, anchor: Anchor, timestamp: u64) -> SystemTime {
+        if timestamp >= anchor.timestamp {
+            anchor.time + self.duration(timestamp - anchor.timestamp)
+        } else {
+            anchor
+                .time
+                .checked_sub(self.duration(anchor.timestamp - timestamp))
+                .unwrap_or(UNIX_EPOCH)
+        }
+    }
+
+    fn duration(&self, ticks: u64) -> Duration {
+        if self.sample_rate == 0 {
+            return Duration::from_secs(0);
+        }
+        let rate = self.sample_rate as u64;
+        let nanos = (ticks % rate) * 1_000_000_000 / rate;
+        Duration::new(ticks / rate, nanos as u32)
+    }
+}
@@ -0,0 +1,74 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::wall_clock::*;
+
+#[test]
+fn test_ntp_to_system_time() {
+    // 2022-01-01T00:00:00.5Z
+    let ntp_time = ((1_640_995_200 + 2_208_988_800) << 32) | 0x8000_0000;
+    assert_eq!(
+        Some(UNIX_EPOCH + Duration::new(1_640_995_200, 500_000_000)),
+        ntp_to_system_time(ntp_time)
+    );
+
+    // NTP era 1 starts at 2036-02-07T06:28:16Z
+    assert_eq!(
+        Some(UNIX_EPOCH + Duration::from_secs(2_085_978_496 + 1)),
+        ntp_to_system_time(1 << 32)
+    );
+
+    // 1968-01-20T03:14:08Z precedes the UNIX epoch
+    assert_eq!(None, ntp_to_system_time(0x8000_0000 << 32));
+    assert_eq!(None, ntp_to_system_time((2_208_988_800 - 1) << 32));
+    assert_eq!(Some(UNIX_EPOCH), ntp_to_system_time(2_208_988_800 << 32));
+}
+
+#[test]
+fn test_wall_clock_ignores_unmappable_sender_report() {
+    let mut clock = WallClock::new(1000);
+    let start = UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+    clock.on_arrival(start, 0);
+    clock.on_sender_report(0x8000_0000 << 32, 0);
+    assert_eq!(Some(start), clock.time_of(0));
+}
+
+#[test]
+fn test_wall_clock_sender_report() {
+    let mut clock = WallClock::new(48000);
+    assert_eq!(None, clock.time_of(0));
+
+    let ntp_time = (1_640_995_200 + 2_208_988_800) << 32;
+    let sender_report_time = UNIX_EPOCH + Duration::from_secs(1_640_995_200);
+
+    // the sender report takes precedence over arrival times
+    clock.on_arrival(SystemTime::now(), 96_000);
+    clock.on_sender_report(ntp_time, 96_000);
+    assert_eq!(Some(sender_report_time), clock.time_of(96_000));
+    assert_eq!(
+        Some(sender_report_time + Duration::from_millis(20)),
+        clock.time_of(96_960)
+    );
+    assert_eq!(
+        Some(sender_report_time - Duration::from_secs(2)),
+        clock.time_of(0)
+    );
+}
+
+#[test]
+fn test_wall_clock_arrival() {
+    let mut clock = WallClock::new(1000);
+    let start = UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+    clock.on_arrival(start, 0);
+    assert_eq!(Some(start), clock.time_of(0));
+
+    // delayed packets do not move the estimate
+    clock.on_arrival(start + Duration::from_millis(150), 100);
+    assert_eq!(Some(start + Duration::from_millis(100)), clock.time_of(100));
+
+    // a packet with a smaller transit time anchors the estimate
+    clock.on_arrival(start + Duration::from_millis(190), 200);
+    assert_eq!(Some(start - Duration::from_millis(10)), clock.time_of(0));
+    assert_eq!(Some(start + Duration::from_millis(190)), clock.time_of(200));
+}
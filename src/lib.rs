@@ -19,6 +19,17 @@ pub struct Sample {
     pub timestamp: SystemTime,
     pub duration: Duration,
     pub packet_timestamp: u32,
+    /// RTP timestamp of the first packet extended to 64 bits, so that it keeps
+    /// increasing after the 32-bit timestamp wraps around.
+    ///
+    /// Extension starts one cycle in, i.e. the first timestamp seen is placed at
+    /// `timestamp + 2^32`, so that packets preceding it never go below zero.
+    pub extended_packet_timestamp: u64,
+    /// Sequence number of the first packet extended to 64 bits.
+    ///
+    /// Extension starts one cycle in, i.e. the first sequence number seen is placed at
+    /// `sequence_number + 2^16`, so that packets preceding it never go below zero.
+    pub extended_sequence_number: u64,
     pub prev_dropped_packets: u16,
 }
 
@@ -29,6 +40,8 @@ impl Default for Sample {
             timestamp: SystemTime::now(),
             duration: Duration::from_secs(0),
             packet_timestamp: 0,
+            extended_packet_timestamp: 0,
+            extended_sequence_number: 0,
             prev_dropped_packets: 0,
         }
     }
@@ -40,9 +53,12 @@ impl PartialEq for Sample {
         if self.data != other.data {
             equal = false;
         }
-        if self.timestamp.elapsed().unwrap().as_secs()
-            != other.timestamp.elapsed().unwrap().as_secs()
-        {
+        // Timestamps may lie in the future, so take the difference either way:
+        let difference = self
+            .timestamp
+            .duration_since(other.timestamp)
+            .unwrap_or_else(|error| error.duration());
+        if difference >= Duration::from_secs(1) {
             equal = false;
         }
         if self.duration != other.duration {
@@ -51,6 +67,12 @@ impl PartialEq for Sample {
         if self.packet_timestamp != other.packet_timestamp {
             equal = false;
         }
+        if self.extended_packet_timestamp != other.extended_packet_timestamp {
+            equal = false;
+        }
+        if self.extended_sequence_number != other.extended_sequence_number {
+            equal = false;
+        }
         if self.prev_dropped_packets != other.prev_dropped_packets {
             equal = false;
         }
@@ -0,0 +1,233 @@
+use std::{fmt::Debug, ops::RangeInclusive};
+
+use derive_builder::Builder;
+
+use crate::track::{
+    capability::{non_numeric_value, numeric_value},
+    constraint::audio as constraint,
+    setting::audio as setting,
+};
+
+pub type SampleRate = RangeInclusive<setting::SampleRate>;
+pub type SampleSize = RangeInclusive<setting::SampleSize>;
+pub type EchoCancellation = Vec<setting::EchoCancellation>;
+pub type AutoGainControl = Vec<setting::AutoGainControl>;
+pub type NoiseSuppression = Vec<setting::NoiseSuppression>;
+pub type Latency = RangeInclusive<setting::Latency>;
+pub type ChannelCount = RangeInclusive<setting::ChannelCount>;
+pub type RestrictOwnAudio = Vec<setting::RestrictOwnAudio>;
+pub type SuppressLocalAudioPlayback = Vec<setting::SuppressLocalAudioPlayback>;
+
+/// An audio's capabilities
+#[derive(PartialEq, Default, Clone, Builder)]
+pub struct Audio {
+    #[builder(default, setter(strip_option))]
+    pub sample_rate: Option<SampleRate>,
+    #[builder(default, setter(strip_option))]
+    pub sample_size: Option<SampleSize>,
+    #[builder(default, setter(strip_option))]
+    pub echo_cancellation: Option<EchoCancellation>,
+    #[builder(default, setter(strip_option))]
+    pub auto_gain_control: Option<AutoGainControl>,
+    #[builder(default, setter(strip_option))]
+    pub noise_suppression: Option<NoiseSuppression>,
+    #[builder(default, setter(strip_option))]
+    pub latency: Option<Latency>,
+    #[builder(default, setter(strip_option))]
+    pub channel_count: Option<ChannelCount>,
+    #[builder(default, setter(strip_option))]
+    pub restrict_own_audio: Option<RestrictOwnAudio>,
+    #[builder(default, setter(strip_option))]
+    pub suppress_local_audio_playback: Option<SuppressLocalAudioPlayback>,
+}
+
+impl Audio {
+    pub fn builder() -> AudioBuilder {
+        Default::default()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        sample_rate: Option<SampleRate>,
+        sample_size: Option<SampleSize>,
+        echo_cancellation: Option<EchoCancellation>,
+        auto_gain_control: Option<AutoGainControl>,
+        noise_suppression: Option<NoiseSuppression>,
+        latency: Option<Latency>,
+        channel_count: Option<ChannelCount>,
+        restrict_own_audio: Option<RestrictOwnAudio>,
+        suppress_local_audio_playback: Option<SuppressLocalAudioPlayback>,
+    ) -> Self {
+        Self {
+            sample_rate,
+            sample_size,
+            echo_cancellation,
+            auto_gain_control,
+            noise_suppression,
+            latency,
+            channel_count,
+            restrict_own_audio,
+            suppress_local_audio_playback,
+        }
+    }
+
+    /// Returns the settings within `self` that best satisfy `constraints`.
+    pub(crate) fn settings(&self, constraints: &constraint::Audio) -> setting::Audio {
+        setting::Audio {
+            sample_rate: numeric_value(&self.sample_rate, &constraints.sample_rate),
+            sample_size: numeric_value(&self.sample_size, &constraints.sample_size),
+            echo_cancellation: non_numeric_value(
+                &self.echo_cancellation,
+                &constraints.echo_cancellation,
+            ),
+            auto_gain_control: non_numeric_value(
+                &self.auto_gain_control,
+                &constraints.auto_gain_control,
+            ),
+            noise_suppression: non_numeric_value(
+                &self.noise_suppression,
+                &constraints.noise_suppression,
+            ),
+            latency: numeric_value(&self.latency, &constraints.latency),
+            channel_count: numeric_value(&self.channel_count, &constraints.channel_count),
+            restrict_own_audio: non_numeric_value(
+                &self.restrict_own_audio,
+                &constraints.restrict_own_audio,
+            ),
+            suppress_local_audio_playback: non_numeric_value(
+                &self.suppress_local_audio_playback,
+                &constraints.suppress_local_audio_playback,
+            ),
+        }
+    }
+}
+
+impl Debug for Audio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut builder = f.debug_struct("Audio");
+
+        if let Some(sample_rate) = &self.sample_rate {
+            builder.field("sample_rate", &sample_rate);
+        }
+        if let Some(sample_size) = &self.sample_size {
+            builder.field("sample_size", &sample_size);
+        }
+        if let Some(echo_cancellation) = &self.echo_cancellation {
+            builder.field("echo_cancellation", &echo_cancellation);
+        }
+        if let Some(auto_gain_control) = &self.auto_gain_control {
+            builder.field("auto_gain_control", &auto_gain_control);
+        }
+        if let Some(noise_suppression) = &self.noise_suppression {
+            builder.field("noise_suppression", &noise_suppression);
+        }
+        if let Some(latency) = &self.latency {
+            builder.field("latency", &latency);
+        }
+        if let Some(channel_count) = &self.channel_count {
+            builder.field("channel_count", &channel_count);
+        }
+        if let Some(restrict_own_audio) = &self.restrict_own_audio {
+            builder.field("restrict_own_audio", &restrict_own_audio);
+        }
+        if let Some(suppress_local_audio_playback) = &self.suppress_local_audio_playback {
+            builder.field(
+                "suppress_local_audio_playback",
+                &suppress_local_audio_playback,
+            );
+        }
+
+        builder.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default() {
+        let subject = Audio::default();
+        assert_eq!(
+            subject,
+            Audio {
+                sample_rate: None,
+                sample_size: None,
+                echo_cancellation: None,
+                auto_gain_control: None,
+                noise_suppression: None,
+                latency: None,
+                channel_count: None,
+                restrict_own_audio: None,
+                suppress_local_audio_playback: None,
+            }
+        );
+    }
+
+    #[test]
+    fn builder() {
+        let subject = Audio::builder()
+            .sample_rate(8_000..=48_000)
+            .auto_gain_control(vec![setting::AutoGainControl::Off])
+            .build()
+            .unwrap();
+        assert_eq!(
+            subject,
+            Audio {
+                sample_rate: Some(8_000..=48_000),
+                sample_size: None,
+                echo_cancellation: None,
+                auto_gain_control: Some(vec![setting::AutoGainControl::Off]),
+                noise_suppression: None,
+                latency: None,
+                channel_count: None,
+                restrict_own_audio: None,
+                suppress_local_audio_playback: None,
+            }
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let subject = Audio::builder()
+            .sample_rate(8_000..=48_000)
+            .auto_gain_control(vec![setting::AutoGainControl::Off])
+            .build()
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", subject),
+            "Audio { sample_rate: 8000..=48000, auto_gain_control: [Off] }"
+        );
+    }
+
+    #[test]
+    fn settings() {
+        let capabilities = Audio::builder()
+            .sample_rate(8_000..=48_000)
+            .echo_cancellation(vec![
+                setting::EchoCancellation::Off,
+                setting::EchoCancellation::On,
+            ])
+            .channel_count(1..=2)
+            .build()
+            .unwrap();
+
+        let constraints = constraint::Audio::builder()
+            .sample_rate(constraint::SampleRate::within(8_000, 96_000, Some(16_000)))
+            .echo_cancellation(constraint::EchoCancellation::exactly(
+                setting::EchoCancellation::On,
+            ))
+            .build()
+            .unwrap();
+
+        let actual = capabilities.settings(&constraints);
+        let expected = setting::Audio::builder()
+            .sample_rate(16_000)
+            .echo_cancellation(setting::EchoCancellation::On)
+            .channel_count(2)
+            .build()
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,423 @@
+//! Media track capabilities.
+
+use std::{fmt::Debug, ops::RangeInclusive};
+
+use derive_builder::Builder;
+
+use crate::track::{
+    constraint::{
+        self, BareValueMode, MediaTrackConstraints, Merge, NonNumeric, Numeric, Overconstrained,
+        OverconstrainedError, ResolveBareValues,
+    },
+    setting,
+};
+
+pub mod audio;
+pub mod video;
+
+// Constraints and capabilities are short-lived, so boxing the larger variant isn't worth it:
+#[allow(clippy::large_enum_variant)]
+#[derive(PartialEq, Clone)]
+pub enum MediaKind {
+    Audio(audio::Audio),
+    Video(video::Video),
+}
+
+impl From<audio::Audio> for MediaKind {
+    fn from(audio: audio::Audio) -> Self {
+        Self::Audio(audio)
+    }
+}
+
+impl From<video::Video> for MediaKind {
+    fn from(video: video::Video) -> Self {
+        Self::Video(video)
+    }
+}
+
+impl Debug for MediaKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Audio(audio) => audio.fmt(f),
+            Self::Video(video) => video.fmt(f),
+        }
+    }
+}
+
+/// A media source's capabilities, i.e. the ranges and sets of values
+/// that its constrainable properties can take on.
+///
+/// # Specification
+/// - <https://www.w3.org/TR/mediacapture-streams/#dom-mediatrackcapabilities>
+#[derive(PartialEq, Clone, Builder)]
+pub struct Media {
+    #[builder(default, setter(strip_option))]
+    pub device_id: Option<String>,
+    #[builder(default, setter(strip_option))]
+    pub group_id: Option<String>,
+    #[builder(setter(into))]
+    pub kind: MediaKind,
+}
+
+impl Media {
+    pub fn builder() -> MediaBuilder {
+        Default::default()
+    }
+
+    pub fn new(device_id: Option<String>, group_id: Option<String>, kind: MediaKind) -> Self {
+        Self {
+            device_id,
+            group_id,
+            kind,
+        }
+    }
+
+    fn checked_settings<C>(
+        &self,
+        kind: setting::MediaKind,
+        constraints: &C,
+    ) -> Result<setting::Media, OverconstrainedError>
+    where
+        C: Overconstrained<setting::Media>,
+    {
+        let settings = setting::Media::new(self.device_id.clone(), self.group_id.clone(), kind);
+        match first_unsatisfied(constraints, &settings) {
+            Some(error) => Err(error),
+            None => Ok(settings),
+        }
+    }
+}
+
+impl Debug for Media {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut builder = f.debug_struct("Media");
+
+        if let Some(device_id) = &self.device_id {
+            builder.field("device_id", &device_id);
+        }
+        if let Some(group_id) = &self.group_id {
+            builder.field("group_id", &group_id);
+        }
+
+        builder.field("kind", &self.kind);
+
+        builder.finish()
+    }
+}
+
+/// Derivation of concrete settings from capabilities and constraints.
+pub trait DeriveSettings<Constraints> {
+    /// Returns the settings within the capabilities that best satisfy `constraints`.
+    ///
+    /// Unconstrained numeric properties are set to the upper bound of their capability,
+    /// unconstrained non-numeric properties to the first value of their capability.
+    ///
+    /// # Errors
+    /// Returns an `OverconstrainedError` if the capabilities cannot satisfy `constraints`.
+    fn derive_settings(
+        &self,
+        constraints: &Constraints,
+    ) -> Result<setting::Media, OverconstrainedError>;
+
+    /// Returns `true` if the capabilities can satisfy `constraints`.
+    fn satisfies(&self, constraints: &Constraints) -> bool {
+        self.derive_settings(constraints).is_ok()
+    }
+}
+
+impl DeriveSettings<constraint::video::Video> for Media {
+    fn derive_settings(
+        &self,
+        constraints: &constraint::video::Video,
+    ) -> Result<setting::Media, OverconstrainedError> {
+        let kind = match &self.kind {
+            MediaKind::Video(video) => video.settings(constraints).into(),
+            MediaKind::Audio(_) => return Err(mismatched_kind()),
+        };
+        self.checked_settings(kind, constraints)
+    }
+}
+
+impl DeriveSettings<constraint::audio::Audio> for Media {
+    fn derive_settings(
+        &self,
+        constraints: &constraint::audio::Audio,
+    ) -> Result<setting::Media, OverconstrainedError> {
+        let kind = match &self.kind {
+            MediaKind::Audio(audio) => audio.settings(constraints).into(),
+            MediaKind::Video(_) => return Err(mismatched_kind()),
+        };
+        self.checked_settings(kind, constraints)
+    }
+}
+
+impl DeriveSettings<constraint::Media> for Media {
+    fn derive_settings(
+        &self,
+        constraints: &constraint::Media,
+    ) -> Result<setting::Media, OverconstrainedError> {
+        let kind = match (&self.kind, &constraints.kind) {
+            (MediaKind::Audio(audio), constraint::MediaKind::Audio(constraints)) => {
+                audio.settings(constraints).into()
+            }
+            (MediaKind::Video(video), constraint::MediaKind::Video(constraints)) => {
+                video.settings(constraints).into()
+            }
+            _ => return Err(mismatched_kind()),
+        };
+        self.checked_settings(kind, constraints)
+    }
+}
+
+impl<T> DeriveSettings<MediaTrackConstraints<T>> for Media
+where
+    T: Clone + Merge + Overconstrained<setting::Media> + ResolveBareValues,
+    Self: DeriveSettings<T>,
+{
+    /// Returns the settings within the capabilities that best satisfy `constraints`.
+    ///
+    /// Each advanced constraint set is applied in order on top of the basic constraint set
+    /// and all previously applied advanced constraint sets, and gets skipped if the resulting
+    /// settings would fail to satisfy any of them.
+    ///
+    /// Bare values of advanced constraint sets are treated as exact values.
+    fn derive_settings(
+        &self,
+        constraints: &MediaTrackConstraints<T>,
+    ) -> Result<setting::Media, OverconstrainedError> {
+        let mut merged = constraints.basic.clone();
+        let mut settings = self.derive_settings(&merged)?;
+
+        let mut applied = vec![constraints.basic.clone()];
+
+        for advanced in &constraints.advanced {
+            // Bare values of advanced sets are treated as exact values:
+            let mut advanced = advanced.clone();
+            advanced.resolve_bare_values(BareValueMode::Exact);
+
+            let mut candidate = advanced.clone();
+            if candidate.merge(&merged).is_err() {
+                continue;
+            }
+
+            let candidate_settings = match self.derive_settings(&candidate) {
+                Ok(candidate_settings) => candidate_settings,
+                Err(_) => continue,
+            };
+
+            let is_satisfying = applied
+                .iter()
+                .all(|applied| first_unsatisfied(applied, &candidate_settings).is_none());
+
+            if is_satisfying {
+                applied.push(advanced);
+                merged = candidate;
+                settings = candidate_settings;
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+fn mismatched_kind() -> OverconstrainedError {
+    OverconstrainedError::new(
+        "",
+        "capabilities are of a different kind than the constraints",
+    )
+}
+
+fn first_unsatisfied<C>(constraints: &C, settings: &setting::Media) -> Option<OverconstrainedError>
+where
+    C: Overconstrained<setting::Media>,
+{
+    constraints
+        .unsatisfied_constraints(settings)
+        .and_then(|errors| errors.into_iter().next())
+}
+
+/// Returns the value within `capability` that best satisfies `constraint`.
+pub(crate) fn numeric_value<T>(
+    capability: &Option<RangeInclusive<T>>,
+    constraint: &Option<Numeric<T>>,
+) -> Option<T>
+where
+    T: Clone + PartialOrd,
+{
+    let capability = capability.as_ref()?;
+    match constraint {
+        Some(constraint) => constraint.value_within(capability),
+        None => Some(capability.end().clone()),
+    }
+}
+
+/// Returns the value among `capability` that best satisfies `constraint`.
+pub(crate) fn non_numeric_value<T>(
+    capability: &Option<Vec<T>>,
+    constraint: &Option<NonNumeric<T>>,
+) -> Option<T>
+where
+    T: Clone + PartialEq,
+{
+    let capability = capability.as_ref()?;
+    match constraint {
+        Some(constraint) => constraint.value_among(capability),
+        None => capability.first().cloned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::track::{
+        constraint::video::{Height, Width},
+        setting::video::FacingMode,
+    };
+
+    use super::*;
+
+    fn camera() -> Media {
+        Media::builder()
+            .device_id("CAMERA".to_owned())
+            .kind(
+                video::Video::builder()
+                    .width(320..=1920)
+                    .height(240..=1080)
+                    .facing_mode(vec![FacingMode::User])
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(
+            format!("{:?}", camera()),
+            "Media { device_id: \"CAMERA\", kind: Video { width: 320..=1920, height: 240..=1080, facing_mode: [User] } }"
+        );
+    }
+
+    #[test]
+    fn derive_settings() {
+        let constraints = constraint::video::Video::builder()
+            .width(Width::at_most(1280, None).is_required(true))
+            .build()
+            .unwrap();
+
+        let actual = camera().derive_settings(&constraints);
+        let expected = setting::Media::builder()
+            .device_id("CAMERA".to_owned())
+            .kind(
+                setting::video::Video::builder()
+                    .width(1280)
+                    .height(1080)
+                    .facing_mode(FacingMode::User)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn derive_settings_overconstrained() {
+        let constraints = constraint::video::Video::builder()
+            .width(Width::at_least(3840, None).is_required(true))
+            .build()
+            .unwrap();
+
+        let subject = camera();
+
+        assert_eq!(
+            subject
+                .derive_settings(&constraints)
+                .unwrap_err()
+                .constraint,
+            "width"
+        );
+        assert!(!subject.satisfies(&constraints));
+    }
+
+    #[test]
+    fn derive_settings_mismatched_kind() {
+        let constraints = constraint::audio::Audio::default();
+
+        assert!(!camera().satisfies(&constraints));
+    }
+
+    #[test]
+    fn derive_settings_media() {
+        let constraints = constraint::Media::builder()
+            .device_id(constraint::DeviceId::exactly("CAMERA".to_owned()).is_required(true))
+            .kind(constraint::video::Video::default())
+            .build()
+            .unwrap();
+
+        assert!(camera().satisfies(&constraints));
+
+        let constraints = constraint::Media::builder()
+            .device_id(constraint::DeviceId::exactly("OTHER".to_owned()).is_required(true))
+            .kind(constraint::video::Video::default())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            camera()
+                .derive_settings(&constraints)
+                .unwrap_err()
+                .constraint,
+            "device_id"
+        );
+    }
+
+    #[test]
+    fn derive_settings_advanced() {
+        fn height(height: u32) -> constraint::video::Video {
+            constraint::video::Video::builder()
+                .height(Height::exactly(height).is_required(true))
+                .build()
+                .unwrap()
+        }
+
+        let constraints = MediaTrackConstraints::new(
+            constraint::video::Video::default(),
+            vec![height(2160), height(720), height(1080)],
+        );
+
+        let actual = camera().derive_settings(&constraints).unwrap();
+
+        match actual.kind {
+            setting::MediaKind::Video(video) => assert_eq!(video.height, Some(720)),
+            setting::MediaKind::Audio(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn derive_settings_advanced_bare() {
+        fn height(height: u32) -> constraint::video::Video {
+            constraint::video::Video::builder()
+                .height(Height::bare(height))
+                .build()
+                .unwrap()
+        }
+
+        let basic = constraint::video::Video::builder()
+            .height(Height::exactly(480))
+            .build()
+            .unwrap();
+
+        // The bare 2160p set is treated as exact, and hence gets skipped
+        // rather than pulling the height towards it:
+        let constraints = MediaTrackConstraints::new(basic, vec![height(2160)]);
+
+        let actual = camera().derive_settings(&constraints).unwrap();
+
+        match actual.kind {
+            setting::MediaKind::Video(video) => assert_eq!(video.height, Some(480)),
+            setting::MediaKind::Audio(_) => panic!(),
+        }
+    }
+}
@@ -0,0 +1,434 @@
+use std::{fmt::Debug, ops::RangeInclusive};
+
+use derive_builder::Builder;
+
+use crate::track::{
+    capability::{non_numeric_value, numeric_value},
+    constraint::video as constraint,
+    setting::video as setting,
+};
+
+pub type Width = RangeInclusive<setting::Width>;
+pub type Height = RangeInclusive<setting::Height>;
+pub type AspectRatio = RangeInclusive<setting::AspectRatio>;
+pub type FrameRate = RangeInclusive<setting::FrameRate>;
+pub type FacingMode = Vec<setting::FacingMode>;
+pub type ResizeMode = Vec<setting::ResizeMode>;
+pub type WhiteBalanceMode = Vec<setting::WhiteBalanceMode>;
+pub type ExposureMode = Vec<setting::ExposureMode>;
+pub type FocusMode = Vec<setting::FocusMode>;
+pub type ExposureCompensation = RangeInclusive<setting::ExposureCompensation>;
+pub type ExposureTime = RangeInclusive<setting::ExposureTime>;
+pub type ColorTemperature = RangeInclusive<setting::ColorTemperature>;
+pub type Iso = RangeInclusive<setting::Iso>;
+pub type Brightness = RangeInclusive<setting::Brightness>;
+pub type Contrast = RangeInclusive<setting::Contrast>;
+pub type Saturation = RangeInclusive<setting::Saturation>;
+pub type Sharpness = RangeInclusive<setting::Sharpness>;
+pub type FocusDistance = RangeInclusive<setting::FocusDistance>;
+pub type Pan = RangeInclusive<setting::Pan>;
+pub type Tilt = RangeInclusive<setting::Tilt>;
+pub type Zoom = RangeInclusive<setting::Zoom>;
+pub type Torch = Vec<setting::Torch>;
+pub type DisplaySurface = Vec<setting::DisplaySurface>;
+pub type LogicalSurface = Vec<setting::LogicalSurface>;
+pub type Cursor = Vec<setting::Cursor>;
+
+/// A video's capabilities
+#[derive(PartialEq, Default, Clone, Builder)]
+pub struct Video {
+    #[builder(default, setter(strip_option))]
+    pub width: Option<Width>,
+    #[builder(default, setter(strip_option))]
+    pub height: Option<Height>,
+    #[builder(default, setter(strip_option))]
+    pub aspect_ratio: Option<AspectRatio>,
+    #[builder(default, setter(strip_option))]
+    pub frame_rate: Option<FrameRate>,
+    #[builder(default, setter(strip_option))]
+    pub facing_mode: Option<FacingMode>,
+    #[builder(default, setter(strip_option))]
+    pub resize_mode: Option<ResizeMode>,
+    #[builder(default, setter(strip_option))]
+    pub white_balance_mode: Option<WhiteBalanceMode>,
+    #[builder(default, setter(strip_option))]
+    pub exposure_mode: Option<ExposureMode>,
+    #[builder(default, setter(strip_option))]
+    pub focus_mode: Option<FocusMode>,
+    #[builder(default, setter(strip_option))]
+    pub exposure_compensation: Option<ExposureCompensation>,
+    #[builder(default, setter(strip_option))]
+    pub exposure_time: Option<ExposureTime>,
+    #[builder(default, setter(strip_option))]
+    pub color_temperature: Option<ColorTemperature>,
+    #[builder(default, setter(strip_option))]
+    pub iso: Option<Iso>,
+    #[builder(default, setter(strip_option))]
+    pub brightness: Option<Brightness>,
+    #[builder(default, setter(strip_option))]
+    pub contrast: Option<Contrast>,
+    #[builder(default, setter(strip_option))]
+    pub saturation: Option<Saturation>,
+    #[builder(default, setter(strip_option))]
+    pub sharpness: Option<Sharpness>,
+    #[builder(default, setter(strip_option))]
+    pub focus_distance: Option<FocusDistance>,
+    #[builder(default, setter(strip_option))]
+    pub pan: Option<Pan>,
+    #[builder(default, setter(strip_option))]
+    pub tilt: Option<Tilt>,
+    #[builder(default, setter(strip_option))]
+    pub zoom: Option<Zoom>,
+    #[builder(default, setter(strip_option))]
+    pub torch: Option<Torch>,
+    #[builder(default, setter(strip_option))]
+    pub display_surface: Option<DisplaySurface>,
+    #[builder(default, setter(strip_option))]
+    pub logical_surface: Option<LogicalSurface>,
+    #[builder(default, setter(strip_option))]
+    pub cursor: Option<Cursor>,
+}
+
+impl Video {
+    pub fn builder() -> VideoBuilder {
+        Default::default()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        width: Option<Width>,
+        height: Option<Height>,
+        aspect_ratio: Option<AspectRatio>,
+        frame_rate: Option<FrameRate>,
+        facing_mode: Option<FacingMode>,
+        resize_mode: Option<ResizeMode>,
+        white_balance_mode: Option<WhiteBalanceMode>,
+        exposure_mode: Option<ExposureMode>,
+        focus_mode: Option<FocusMode>,
+        exposure_compensation: Option<ExposureCompensation>,
+        exposure_time: Option<ExposureTime>,
+        color_temperature: Option<ColorTemperature>,
+        iso: Option<Iso>,
+        brightness: Option<Brightness>,
+        contrast: Option<Contrast>,
+        saturation: Option<Saturation>,
+        sharpness: Option<Sharpness>,
+        focus_distance: Option<FocusDistance>,
+        pan: Option<Pan>,
+        tilt: Option<Tilt>,
+        zoom: Option<Zoom>,
+        torch: Option<Torch>,
+        display_surface: Option<DisplaySurface>,
+        logical_surface: Option<LogicalSurface>,
+        cursor: Option<Cursor>,
+    ) -> Self {
+        Self {
+            width,
+            height,
+            aspect_ratio,
+            frame_rate,
+            facing_mode,
+            resize_mode,
+            white_balance_mode,
+            exposure_mode,
+            focus_mode,
+            exposure_compensation,
+            exposure_time,
+            color_temperature,
+            iso,
+            brightness,
+            contrast,
+            saturation,
+            sharpness,
+            focus_distance,
+            pan,
+            tilt,
+            zoom,
+            torch,
+            display_surface,
+            logical_surface,
+            cursor,
+        }
+    }
+
+    /// Returns the settings within `self` that best satisfy `constraints`.
+    pub(crate) fn settings(&self, constraints: &constraint::Video) -> setting::Video {
+        setting::Video {
+            width: numeric_value(&self.width, &constraints.width),
+            height: numeric_value(&self.height, &constraints.height),
+            aspect_ratio: numeric_value(&self.aspect_ratio, &constraints.aspect_ratio),
+            frame_rate: numeric_value(&self.frame_rate, &constraints.frame_rate),
+            facing_mode: non_numeric_value(&self.facing_mode, &constraints.facing_mode),
+            resize_mode: non_numeric_value(&self.resize_mode, &constraints.resize_mode),
+            white_balance_mode: non_numeric_value(
+                &self.white_balance_mode,
+                &constraints.white_balance_mode,
+            ),
+            exposure_mode: non_numeric_value(&self.exposure_mode, &constraints.exposure_mode),
+            focus_mode: non_numeric_value(&self.focus_mode, &constraints.focus_mode),
+            exposure_compensation: numeric_value(
+                &self.exposure_compensation,
+                &constraints.exposure_compensation,
+            ),
+            exposure_time: numeric_value(&self.exposure_time, &constraints.exposure_time),
+            color_temperature: numeric_value(
+                &self.color_temperature,
+                &constraints.color_temperature,
+            ),
+            iso: numeric_value(&self.iso, &constraints.iso),
+            brightness: numeric_value(&self.brightness, &constraints.brightness),
+            contrast: numeric_value(&self.contrast, &constraints.contrast),
+            saturation: numeric_value(&self.saturation, &constraints.saturation),
+            sharpness: numeric_value(&self.sharpness, &constraints.sharpness),
+            focus_distance: numeric_value(&self.focus_distance, &constraints.focus_distance),
+            pan: numeric_value(&self.pan, &constraints.pan),
+            tilt: numeric_value(&self.tilt, &constraints.tilt),
+            zoom: numeric_value(&self.zoom, &constraints.zoom),
+            torch: non_numeric_value(&self.torch, &constraints.torch),
+            display_surface: non_numeric_value(&self.display_surface, &constraints.display_surface),
+            logical_surface: non_numeric_value(&self.logical_surface, &constraints.logical_surface),
+            cursor: non_numeric_value(&self.cursor, &constraints.cursor),
+        }
+    }
+}
+
+impl Debug for Video {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut builder = f.debug_struct("Video");
+
+        if let Some(width) = &self.width {
+            builder.field("width", &width);
+        }
+        if let Some(height) = &self.height {
+            builder.field("height", &height);
+        }
+        if let Some(aspect_ratio) = &self.aspect_ratio {
+            builder.field("aspect_ratio", &aspect_ratio);
+        }
+        if let Some(frame_rate) = &self.frame_rate {
+            builder.field("frame_rate", &frame_rate);
+        }
+        if let Some(facing_mode) = &self.facing_mode {
+            builder.field("facing_mode", &facing_mode);
+        }
+        if let Some(resize_mode) = &self.resize_mode {
+            builder.field("resize_mode", &resize_mode);
+        }
+        if let Some(white_balance_mode) = &self.white_balance_mode {
+            builder.field("white_balance_mode", &white_balance_mode);
+        }
+        if let Some(exposure_mode) = &self.exposure_mode {
+            builder.field("exposure_mode", &exposure_mode);
+        }
+        if let Some(focus_mode) = &self.focus_mode {
+            builder.field("focus_mode", &focus_mode);
+        }
+        if let Some(exposure_compensation) = &self.exposure_compensation {
+            builder.field("exposure_compensation", &exposure_compensation);
+        }
+        if let Some(exposure_time) = &self.exposure_time {
+            builder.field("exposure_time", &exposure_time);
+        }
+        if let Some(color_temperature) = &self.color_temperature {
+            builder.field("color_temperature", &color_temperature);
+        }
+        if let Some(iso) = &self.iso {
+            builder.field("iso", &iso);
+        }
+        if let Some(brightness) = &self.brightness {
+            builder.field("brightness", &brightness);
+        }
+        if let Some(contrast) = &self.contrast {
+            builder.field("contrast", &contrast);
+        }
+        if let Some(saturation) = &self.saturation {
+            builder.field("saturation", &saturation);
+        }
+        if let Some(sharpness) = &self.sharpness {
+            builder.field("sharpness", &sharpness);
+        }
+        if let Some(focus_distance) = &self.focus_distance {
+            builder.field("focus_distance", &focus_distance);
+        }
+        if let Some(pan) = &self.pan {
+            builder.field("pan", &pan);
+        }
+        if let Some(tilt) = &self.tilt {
+            builder.field("tilt", &tilt);
+        }
+        if let Some(zoom) = &self.zoom {
+            builder.field("zoom", &zoom);
+        }
+        if let Some(torch) = &self.torch {
+            builder.field("torch", &torch);
+        }
+        if let Some(display_surface) = &self.display_surface {
+            builder.field("display_surface", &display_surface);
+        }
+        if let Some(logical_surface) = &self.logical_surface {
+            builder.field("logical_surface", &logical_surface);
+        }
+        if let Some(cursor) = &self.cursor {
+            builder.field("cursor", &cursor);
+        }
+
+        builder.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default() {
+        let subject = Video::default();
+        assert_eq!(
+            subject,
+            Video {
+                width: None,
+                height: None,
+                aspect_ratio: None,
+                frame_rate: None,
+                facing_mode: None,
+                resize_mode: None,
+                white_balance_mode: None,
+                exposure_mode: None,
+                focus_mode: None,
+                exposure_compensation: None,
+                exposure_time: None,
+                color_temperature: None,
+                iso: None,
+                brightness: None,
+                contrast: None,
+                saturation: None,
+                sharpness: None,
+                focus_distance: None,
+                pan: None,
+                tilt: None,
+                zoom: None,
+                torch: None,
+                display_surface: None,
+                logical_surface: None,
+                cursor: None,
+            }
+        );
+    }
+
+    #[test]
+    fn builder() {
+        let subject = Video::builder()
+            .width(320..=1920)
+            .facing_mode(vec![setting::FacingMode::User])
+            .build()
+            .unwrap();
+        assert_eq!(
+            subject,
+            Video {
+                width: Some(320..=1920),
+                height: None,
+                aspect_ratio: None,
+                frame_rate: None,
+                facing_mode: Some(vec![setting::FacingMode::User]),
+                resize_mode: None,
+                white_balance_mode: None,
+                exposure_mode: None,
+                focus_mode: None,
+                exposure_compensation: None,
+                exposure_time: None,
+                color_temperature: None,
+                iso: None,
+                brightness: None,
+                contrast: None,
+                saturation: None,
+                sharpness: None,
+                focus_distance: None,
+                pan: None,
+                tilt: None,
+                zoom: None,
+                torch: None,
+                display_surface: None,
+                logical_surface: None,
+                cursor: None,
+            }
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let subject = Video::builder()
+            .width(320..=1920)
+            .facing_mode(vec![setting::FacingMode::User])
+            .build()
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", subject),
+            "Video { width: 320..=1920, facing_mode: [User] }"
+        );
+    }
+
+    #[test]
+    fn settings() {
+        let capabilities = Video::builder()
+            .width(320..=1920)
+            .height(240..=1080)
+            .frame_rate(1.0..=30.0)
+            .facing_mode(vec![
+                setting::FacingMode::User,
+                setting::FacingMode::Environment,
+            ])
+            .build()
+            .unwrap();
+
+        let constraints = constraint::Video::builder()
+            .width(constraint::Width::at_least(640, Some(1280)))
+            .height(constraint::Height::at_most(720, None))
+            .facing_mode(constraint::FacingMode::exactly(
+                setting::FacingMode::Environment,
+            ))
+            .build()
+            .unwrap();
+
+        let actual = capabilities.settings(&constraints);
+        let expected = setting::Video::builder()
+            .width(1280)
+            .height(720)
+            .frame_rate(30.0)
+            .facing_mode(setting::FacingMode::Environment)
+            .build()
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn settings_image_capture() {
+        let capabilities = Video::builder()
+            .zoom(1.0..=8.0)
+            .exposure_mode(vec![
+                setting::MeteringMode::Manual,
+                setting::MeteringMode::Continuous,
+            ])
+            .torch(vec![setting::Torch::Off, setting::Torch::On])
+            .build()
+            .unwrap();
+
+        let constraints = constraint::Video::builder()
+            .zoom(constraint::Zoom::exactly(2.5))
+            .exposure_mode(constraint::ExposureMode::exactly(
+                setting::MeteringMode::Continuous,
+            ))
+            .build()
+            .unwrap();
+
+        let actual = capabilities.settings(&constraints);
+        let expected = setting::Video::builder()
+            .zoom(2.5)
+            .exposure_mode(setting::MeteringMode::Continuous)
+            .torch(setting::Torch::Off)
+            .build()
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+}
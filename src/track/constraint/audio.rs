@@ -1,13 +1,17 @@
-use std::fmt::Debug;
+use std::{convert::Infallible, fmt::Debug};
 
 use derive_builder::Builder;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::track::{
-    constraint::{fitness::Fitness, NonNumeric, Numeric},
-    setting::audio as setting,
+    constraint::{
+        fitness::Fitness, NonNumeric, Numeric, Overconstrained, OverconstrainedError, Unsatisfied,
+    },
+    setting::{audio as setting, Media as MediaSetting, MediaKind as MediaKindSetting},
 };
 
-use super::Merge;
+use super::{BareValueMode, Merge, ResolveBareValues};
 
 pub type SampleRate = Numeric<setting::SampleRate>;
 pub type SampleSize = Numeric<setting::SampleSize>;
@@ -16,24 +20,44 @@ pub type AutoGainControl = NonNumeric<setting::AutoGainControl>;
 pub type NoiseSuppression = NonNumeric<setting::NoiseSuppression>;
 pub type Latency = Numeric<setting::Latency>;
 pub type ChannelCount = Numeric<setting::ChannelCount>;
+pub type RestrictOwnAudio = NonNumeric<setting::RestrictOwnAudio>;
+pub type SuppressLocalAudioPlayback = NonNumeric<setting::SuppressLocalAudioPlayback>;
 
 /// An audio's constraints
 #[derive(PartialEq, Default, Clone, Builder)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
 pub struct Audio {
     #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub sample_rate: Option<SampleRate>,
     #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub sample_size: Option<SampleSize>,
     #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub echo_cancellation: Option<EchoCancellation>,
     #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub auto_gain_control: Option<AutoGainControl>,
     #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub noise_suppression: Option<NoiseSuppression>,
     #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub latency: Option<Latency>,
     #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub channel_count: Option<ChannelCount>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub restrict_own_audio: Option<RestrictOwnAudio>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub suppress_local_audio_playback: Option<SuppressLocalAudioPlayback>,
 }
 
 impl Audio {
@@ -41,6 +65,7 @@ impl Audio {
         Default::default()
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         sample_rate: Option<SampleRate>,
         sample_size: Option<SampleSize>,
@@ -49,6 +74,8 @@ impl Audio {
         noise_suppression: Option<NoiseSuppression>,
         latency: Option<Latency>,
         channel_count: Option<ChannelCount>,
+        restrict_own_audio: Option<RestrictOwnAudio>,
+        suppress_local_audio_playback: Option<SuppressLocalAudioPlayback>,
     ) -> Self {
         Self {
             sample_rate,
@@ -58,6 +85,8 @@ impl Audio {
             noise_suppression,
             latency,
             channel_count,
+            restrict_own_audio,
+            suppress_local_audio_playback,
         }
     }
 }
@@ -87,13 +116,24 @@ impl Debug for Audio {
         if let Some(channel_count) = &self.channel_count {
             builder.field("channel_count", &channel_count);
         }
+        if let Some(restrict_own_audio) = &self.restrict_own_audio {
+            builder.field("restrict_own_audio", &restrict_own_audio);
+        }
+        if let Some(suppress_local_audio_playback) = &self.suppress_local_audio_playback {
+            builder.field(
+                "suppress_local_audio_playback",
+                &suppress_local_audio_playback,
+            );
+        }
 
         builder.finish()
     }
 }
 
 impl Merge for Audio {
-    fn merge(&mut self, other: &Self) {
+    type Error = Infallible;
+
+    fn merge(&mut self, other: &Self) -> Result<(), Self::Error> {
         if self.sample_rate.is_none() {
             self.sample_rate = other.sample_rate.clone();
         }
@@ -115,10 +155,31 @@ impl Merge for Audio {
         if self.channel_count.is_none() {
             self.channel_count = other.channel_count.clone();
         }
+        if self.restrict_own_audio.is_none() {
+            self.restrict_own_audio = other.restrict_own_audio.clone();
+        }
+        if self.suppress_local_audio_playback.is_none() {
+            self.suppress_local_audio_playback = other.suppress_local_audio_playback.clone();
+        }
+        Ok(())
     }
 }
 
-impl Audio {
+impl ResolveBareValues for Audio {
+    fn resolve_bare_values(&mut self, mode: BareValueMode) {
+        self.sample_rate.resolve_bare_values(mode);
+        self.sample_size.resolve_bare_values(mode);
+        self.echo_cancellation.resolve_bare_values(mode);
+        self.auto_gain_control.resolve_bare_values(mode);
+        self.noise_suppression.resolve_bare_values(mode);
+        self.latency.resolve_bare_values(mode);
+        self.channel_count.resolve_bare_values(mode);
+        self.restrict_own_audio.resolve_bare_values(mode);
+        self.suppress_local_audio_playback.resolve_bare_values(mode);
+    }
+}
+
+impl Fitness<setting::Audio> for Audio {
     fn fitness_distance(&self, settings: Option<&setting::Audio>) -> f64 {
         // TODO(regexident): replace with `let_else` once stabilized:
         // Tracking issue: https://github.com/rust-lang/rust/issues/87335
@@ -154,11 +215,89 @@ impl Audio {
         if let Some(channel_count) = &self.channel_count {
             fitness += channel_count.fitness_distance(settings.channel_count.as_ref());
         }
+        if let Some(restrict_own_audio) = &self.restrict_own_audio {
+            fitness += restrict_own_audio.fitness_distance(settings.restrict_own_audio.as_ref());
+        }
+        if let Some(suppress_local_audio_playback) = &self.suppress_local_audio_playback {
+            fitness += suppress_local_audio_playback
+                .fitness_distance(settings.suppress_local_audio_playback.as_ref());
+        }
 
         fitness
     }
 }
 
+impl Fitness<MediaSetting> for Audio {
+    fn fitness_distance(&self, settings: Option<&MediaSetting>) -> f64 {
+        match settings.map(|settings| &settings.kind) {
+            Some(MediaKindSetting::Audio(settings)) => self.fitness_distance(Some(settings)),
+            Some(MediaKindSetting::Video(_)) => f64::INFINITY,
+            None => 0.0,
+        }
+    }
+}
+
+impl Overconstrained<MediaSetting> for Audio {
+    fn unsatisfied_constraints(
+        &self,
+        settings: &MediaSetting,
+    ) -> Option<Vec<OverconstrainedError>> {
+        let settings = match &settings.kind {
+            MediaKindSetting::Audio(settings) => settings,
+            MediaKindSetting::Video(_) => return None,
+        };
+
+        let errors = vec![
+            self.sample_rate.as_ref().and_then(|sample_rate| {
+                sample_rate.unsatisfied_by("sample_rate", settings.sample_rate.as_ref())
+            }),
+            self.sample_size.as_ref().and_then(|sample_size| {
+                sample_size.unsatisfied_by("sample_size", settings.sample_size.as_ref())
+            }),
+            self.echo_cancellation
+                .as_ref()
+                .and_then(|echo_cancellation| {
+                    echo_cancellation
+                        .unsatisfied_by("echo_cancellation", settings.echo_cancellation.as_ref())
+                }),
+            self.auto_gain_control
+                .as_ref()
+                .and_then(|auto_gain_control| {
+                    auto_gain_control
+                        .unsatisfied_by("auto_gain_control", settings.auto_gain_control.as_ref())
+                }),
+            self.noise_suppression
+                .as_ref()
+                .and_then(|noise_suppression| {
+                    noise_suppression
+                        .unsatisfied_by("noise_suppression", settings.noise_suppression.as_ref())
+                }),
+            self.latency
+                .as_ref()
+                .and_then(|latency| latency.unsatisfied_by("latency", settings.latency.as_ref())),
+            self.channel_count.as_ref().and_then(|channel_count| {
+                channel_count.unsatisfied_by("channel_count", settings.channel_count.as_ref())
+            }),
+            self.restrict_own_audio
+                .as_ref()
+                .and_then(|restrict_own_audio| {
+                    restrict_own_audio
+                        .unsatisfied_by("restrict_own_audio", settings.restrict_own_audio.as_ref())
+                }),
+            self.suppress_local_audio_playback
+                .as_ref()
+                .and_then(|suppress_local_audio_playback| {
+                    suppress_local_audio_playback.unsatisfied_by(
+                        "suppress_local_audio_playback",
+                        settings.suppress_local_audio_playback.as_ref(),
+                    )
+                }),
+        ];
+
+        Some(errors.into_iter().flatten().collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,6 +315,8 @@ mod tests {
                 noise_suppression: None,
                 latency: None,
                 channel_count: None,
+                restrict_own_audio: None,
+                suppress_local_audio_playback: None,
             }
         );
     }
@@ -198,6 +339,8 @@ mod tests {
                 noise_suppression: None,
                 latency: None,
                 channel_count: Some(ChannelCount::within(2, 5, Some(2))),
+                restrict_own_audio: None,
+                suppress_local_audio_playback: None,
             }
         );
     }
@@ -220,4 +363,58 @@ mod tests {
 
         assert_eq!(constraint.fitness_distance(Some(&setting)), 0.5);
     }
+
+    #[test]
+    fn fitness_distance_screen_capture() {
+        let constraint = Audio::builder()
+            .restrict_own_audio(RestrictOwnAudio::exactly(setting::RestrictOwnAudio::On))
+            .suppress_local_audio_playback(
+                SuppressLocalAudioPlayback::exactly(setting::SuppressLocalAudioPlayback::On)
+                    .is_required(true),
+            )
+            .build()
+            .unwrap();
+
+        let setting = setting::Audio::builder()
+            .restrict_own_audio(setting::RestrictOwnAudio::Off)
+            .suppress_local_audio_playback(setting::SuppressLocalAudioPlayback::On)
+            .build()
+            .unwrap();
+
+        assert_eq!(constraint.fitness_distance(Some(&setting)), 1.0);
+
+        let setting = setting::Audio::builder()
+            .restrict_own_audio(setting::RestrictOwnAudio::On)
+            .build()
+            .unwrap();
+
+        assert_eq!(constraint.fitness_distance(Some(&setting)), f64::INFINITY);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let json = serde_json::json!({
+            "sampleRate": { "min": 44100 },
+            "echoCancellation": true,
+            "autoGainControl": { "exact": false },
+            "channelCount": 2,
+        });
+
+        let subject = Audio::builder()
+            .sample_rate(SampleRate::at_least(44_100, None).is_required(true))
+            .echo_cancellation(EchoCancellation::bare(setting::EchoCancellation::On))
+            .auto_gain_control(
+                AutoGainControl::exactly(setting::AutoGainControl::Off).is_required(true),
+            )
+            .channel_count(ChannelCount::bare(2))
+            .build()
+            .unwrap();
+
+        let deserialized: Audio = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(deserialized, subject);
+
+        let serialized = serde_json::to_value(&subject).unwrap();
+        assert_eq!(serialized, json);
+    }
 }
@@ -1,5 +1,12 @@
 //! Media track constraints.
 
+use std::{convert::Infallible, fmt::Debug};
+
+use derive_builder::Builder;
+use thiserror::Error;
+
+use crate::track::setting::{Media as MediaSetting, MediaKind as MediaKindSetting};
+
 pub mod audio;
 pub mod video;
 
@@ -7,49 +14,402 @@ mod non_numeric;
 mod numeric;
 
 mod fitness;
+mod overconstrained;
+mod select;
+mod track_constraints;
 
 pub use non_numeric::*;
 pub use numeric::*;
+pub use overconstrained::OverconstrainedError;
+pub use select::*;
+pub use track_constraints::*;
 
 pub(crate) use fitness::*;
+pub(crate) use overconstrained::{
+    overconstrained_error, unsatisfied, Overconstrained, Unsatisfied,
+};
 
-type DeviceId = NonNumeric<String>;
-type GroupId = NonNumeric<String>;
+pub type DeviceId = NonNumeric<String>;
+pub type GroupId = NonNumeric<String>;
 
 pub trait Merge {
-    fn merge(&mut self, other: &Self);
+    type Error;
+
+    /// Fills in the constraints missing from `self` with those of `other`.
+    fn merge(&mut self, other: &Self) -> Result<(), Self::Error>;
+}
+
+/// How bare values, i.e. values given without stating whether they are ideal or exact,
+/// are to be interpreted.
+///
+/// # W3C Spec:
+///
+/// > Unless specified otherwise, any bare value is treated as an ideal value
+/// > in the basic constraint set, and as an exact value in advanced constraint sets.
+/// >
+/// > <https://www.w3.org/TR/mediacapture-streams/#dom-mediatrackconstraintset>
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum BareValueMode {
+    /// Bare values are treated as ideal values (i.e. they are not required).
+    Ideal,
+    /// Bare values are treated as exact values (i.e. they are required).
+    Exact,
+}
+
+pub trait ResolveBareValues {
+    /// Resolves all bare values within `self` according to `mode`.
+    fn resolve_bare_values(&mut self, mode: BareValueMode);
+}
+
+impl<T> ResolveBareValues for Option<T>
+where
+    T: ResolveBareValues,
+{
+    fn resolve_bare_values(&mut self, mode: BareValueMode) {
+        if let Some(value) = self {
+            value.resolve_bare_values(mode);
+        }
+    }
 }
 
-#[derive(PartialEq, Clone, Debug)]
-enum MediaKind {
+#[derive(Error, PartialEq, Eq, Debug)]
+pub enum MergeError {
+    #[error("Cannot merge {kind} constraints with {other_kind} constraints")]
+    IncompatibleKinds {
+        kind: &'static str,
+        other_kind: &'static str,
+    },
+}
+
+impl From<Infallible> for MergeError {
+    fn from(infallible: Infallible) -> Self {
+        match infallible {}
+    }
+}
+
+// Constraints and capabilities are short-lived, so boxing the larger variant isn't worth it:
+#[allow(clippy::large_enum_variant)]
+#[derive(PartialEq, Clone)]
+pub enum MediaKind {
     Audio(audio::Audio),
     Video(video::Video),
 }
 
-#[derive(PartialEq, Clone, Debug)]
-struct Media {
+impl MediaKind {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Audio(_) => "audio",
+            Self::Video(_) => "video",
+        }
+    }
+}
+
+impl From<audio::Audio> for MediaKind {
+    fn from(audio: audio::Audio) -> Self {
+        Self::Audio(audio)
+    }
+}
+
+impl From<video::Video> for MediaKind {
+    fn from(video: video::Video) -> Self {
+        Self::Video(video)
+    }
+}
+
+impl Debug for MediaKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Audio(audio) => audio.fmt(f),
+            Self::Video(video) => video.fmt(f),
+        }
+    }
+}
+
+/// A media's constraints
+#[derive(PartialEq, Clone, Builder)]
+pub struct Media {
+    #[builder(default, setter(into, strip_option))]
     pub device_id: Option<DeviceId>,
+    #[builder(default, setter(into, strip_option))]
     pub group_id: Option<GroupId>,
+    #[builder(setter(into))]
     pub kind: MediaKind,
 }
 
+impl Media {
+    pub fn builder() -> MediaBuilder {
+        Default::default()
+    }
+
+    pub fn new(device_id: Option<DeviceId>, group_id: Option<GroupId>, kind: MediaKind) -> Self {
+        Self {
+            device_id,
+            group_id,
+            kind,
+        }
+    }
+}
+
+impl Debug for Media {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut builder = f.debug_struct("Media");
+
+        if let Some(device_id) = &self.device_id {
+            builder.field("device_id", &device_id);
+        }
+        if let Some(group_id) = &self.group_id {
+            builder.field("group_id", &group_id);
+        }
+
+        builder.field("kind", &self.kind);
+
+        builder.finish()
+    }
+}
+
 impl Merge for Media {
-    fn merge(&mut self, other: &Self) {
+    type Error = MergeError;
+
+    fn merge(&mut self, other: &Self) -> Result<(), Self::Error> {
+        match (&mut self.kind, &other.kind) {
+            (MediaKind::Audio(lhs), MediaKind::Audio(rhs)) => lhs.merge(rhs)?,
+            (MediaKind::Video(lhs), MediaKind::Video(rhs)) => lhs.merge(rhs)?,
+            (kind, other_kind) => {
+                return Err(MergeError::IncompatibleKinds {
+                    kind: kind.name(),
+                    other_kind: other_kind.name(),
+                });
+            }
+        }
         if self.device_id.is_none() {
             self.device_id = other.device_id.clone();
         }
         if self.group_id.is_none() {
             self.group_id = other.group_id.clone();
         }
-        match (&mut self.kind, &other.kind) {
-            (MediaKind::Audio(lhs), MediaKind::Audio(rhs)) => lhs.merge(rhs),
-            (MediaKind::Video(lhs), MediaKind::Video(rhs)) => lhs.merge(rhs),
-            (MediaKind::Video(_), MediaKind::Audio(_)) => {
-                eprintln!("Cannot merge video constraints with audio constraints")
-            }
-            (MediaKind::Audio(_), MediaKind::Video(_)) => {
-                eprintln!("Cannot merge audio constraints with video constraints")
+        Ok(())
+    }
+}
+
+impl ResolveBareValues for Media {
+    fn resolve_bare_values(&mut self, mode: BareValueMode) {
+        self.device_id.resolve_bare_values(mode);
+        self.group_id.resolve_bare_values(mode);
+        match &mut self.kind {
+            MediaKind::Audio(audio) => audio.resolve_bare_values(mode),
+            MediaKind::Video(video) => video.resolve_bare_values(mode),
+        }
+    }
+}
+
+impl Fitness<MediaSetting> for Media {
+    fn fitness_distance(&self, settings: Option<&MediaSetting>) -> f64 {
+        // TODO(regexident): replace with `let_else` once stabilized:
+        // Tracking issue: https://github.com/rust-lang/rust/issues/87335
+        let settings = match settings {
+            Some(settings) => settings,
+            None => {
+                return 0.0;
             }
+        };
+
+        let mut fitness: f64 = 0.0;
+
+        if let Some(device_id) = &self.device_id {
+            fitness += device_id.fitness_distance(settings.device_id.as_ref());
+        }
+        if let Some(group_id) = &self.group_id {
+            fitness += group_id.fitness_distance(settings.group_id.as_ref());
         }
+
+        fitness += match &self.kind {
+            MediaKind::Audio(audio) => audio.fitness_distance(Some(settings)),
+            MediaKind::Video(video) => video.fitness_distance(Some(settings)),
+        };
+
+        fitness
+    }
+}
+
+impl Overconstrained<MediaSetting> for Media {
+    fn unsatisfied_constraints(
+        &self,
+        settings: &MediaSetting,
+    ) -> Option<Vec<OverconstrainedError>> {
+        let mut errors = match (&self.kind, &settings.kind) {
+            (MediaKind::Audio(audio), MediaKindSetting::Audio(_)) => {
+                audio.unsatisfied_constraints(settings)?
+            }
+            (MediaKind::Video(video), MediaKindSetting::Video(_)) => {
+                video.unsatisfied_constraints(settings)?
+            }
+            _ => return None,
+        };
+
+        let id_errors = vec![
+            self.device_id.as_ref().and_then(|device_id| {
+                device_id.unsatisfied_by("device_id", settings.device_id.as_ref())
+            }),
+            self.group_id.as_ref().and_then(|group_id| {
+                group_id.unsatisfied_by("group_id", settings.group_id.as_ref())
+            }),
+        ];
+
+        errors.splice(0..0, id_errors.into_iter().flatten());
+
+        Some(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::track::setting;
+
+    use super::*;
+
+    fn video(device_id: &str, height: u32) -> MediaSetting {
+        MediaSetting::builder()
+            .device_id(device_id.to_owned())
+            .kind(
+                setting::video::Video::builder()
+                    .height(height)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn builder() {
+        let subject = Media::builder()
+            .device_id(DeviceId::exactly("DEVICE".to_owned()))
+            .kind(video::Video::default())
+            .build()
+            .unwrap();
+        assert_eq!(
+            subject,
+            Media {
+                device_id: Some(DeviceId::exactly("DEVICE".to_owned())),
+                group_id: None,
+                kind: MediaKind::Video(video::Video::default()),
+            }
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let subject = Media::builder()
+            .group_id(GroupId::exists(true))
+            .kind(audio::Audio::default())
+            .build()
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", subject),
+            "Media { group_id: NonNumeric { is_required: false, is_bare: false, kind: Exists { is_expected: true } }, kind: Audio }"
+        );
+    }
+
+    #[test]
+    fn merge() {
+        let mut subject = Media::builder()
+            .kind(
+                video::Video::builder()
+                    .height(video::Height::exactly(42))
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let other = Media::builder()
+            .device_id(DeviceId::exactly("DEVICE".to_owned()))
+            .kind(
+                video::Video::builder()
+                    .height(video::Height::exactly(100))
+                    .width(video::Width::exactly(100))
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(subject.merge(&other), Ok(()));
+        assert_eq!(
+            subject,
+            Media::builder()
+                .device_id(DeviceId::exactly("DEVICE".to_owned()))
+                .kind(
+                    video::Video::builder()
+                        .height(video::Height::exactly(42))
+                        .width(video::Width::exactly(100))
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn merge_incompatible_kinds() {
+        let mut subject = Media::builder()
+            .kind(video::Video::default())
+            .build()
+            .unwrap();
+        let other = Media::builder()
+            .device_id(DeviceId::exactly("DEVICE".to_owned()))
+            .kind(audio::Audio::default())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            subject.merge(&other),
+            Err(MergeError::IncompatibleKinds {
+                kind: "video",
+                other_kind: "audio"
+            })
+        );
+        assert_eq!(subject.device_id, None);
+    }
+
+    #[test]
+    fn fitness_distance() {
+        let constraint = Media::builder()
+            .device_id(DeviceId::exactly("DEVICE".to_owned()))
+            .kind(
+                video::Video::builder()
+                    .height(video::Height::exactly(42))
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(constraint.fitness_distance(Some(&video("DEVICE", 42))), 0.0);
+        assert_eq!(constraint.fitness_distance(Some(&video("OTHER", 42))), 1.0);
+        assert_eq!(constraint.fitness_distance(Some(&video("OTHER", 0))), 2.0);
+    }
+
+    #[test]
+    fn select_settings() {
+        let candidates = vec![video("FRONT", 720), video("BACK", 720)];
+
+        let constraint = Media::builder()
+            .device_id(DeviceId::exactly("BACK".to_owned()).is_required(true))
+            .kind(video::Video::default())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            constraint.select_settings(&candidates),
+            vec![&candidates[1]]
+        );
+        assert_eq!(
+            constraint
+                .try_select_settings(&candidates[..1])
+                .unwrap_err()
+                .constraint,
+            "device_id"
+        );
     }
 }
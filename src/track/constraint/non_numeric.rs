@@ -1,4 +1,8 @@
-use crate::track::constraint::Fitness;
+use std::fmt::Debug;
+
+use crate::track::constraint::{
+    unsatisfied, BareValueMode, Fitness, OverconstrainedError, ResolveBareValues, Unsatisfied,
+};
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum NonNumericKind<T> {
@@ -10,6 +14,7 @@ pub enum NonNumericKind<T> {
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct NonNumeric<T> {
     is_required: bool,
+    is_bare: bool,
     kind: NonNumericKind<T>,
 }
 
@@ -18,7 +23,11 @@ where
     T: Clone + PartialEq,
 {
     pub fn new(is_required: bool, kind: NonNumericKind<T>) -> Self {
-        Self { is_required, kind }
+        Self {
+            is_required,
+            is_bare: false,
+            kind,
+        }
     }
 
     pub fn exists(is_expected: bool) -> Self {
@@ -29,6 +38,16 @@ where
         Self::new(false, NonNumericKind::Exactly { value })
     }
 
+    /// Creates a bare value, i.e. one given without stating whether it is ideal or exact.
+    ///
+    /// Bare values behave like ideal values until resolved via [`ResolveBareValues`].
+    pub fn bare(value: T) -> Self {
+        Self {
+            is_bare: true,
+            ..Self::exactly(value)
+        }
+    }
+
     pub fn any_of(values: Vec<T>, ideal: Option<T>) -> Self {
         if let Some(ideal) = &ideal {
             assert!(values.contains(ideal));
@@ -37,10 +56,58 @@ where
         Self::new(false, NonNumericKind::AnyOf { values, ideal })
     }
 
+    /// Creates a bare list of values, i.e. one given without stating whether it is ideal or exact.
+    ///
+    /// Bare lists behave like ideal lists until resolved via [`ResolveBareValues`].
+    pub fn bare_any_of(values: Vec<T>) -> Self {
+        Self {
+            is_bare: true,
+            ..Self::any_of(values, None)
+        }
+    }
+
+    /// Sets whether `self` is required, which also stops it from being a bare value.
     pub fn is_required(mut self, is_required: bool) -> Self {
         self.is_required = is_required;
+        self.is_bare = false;
         self
     }
+
+    /// Returns the value among `values` that best satisfies `self`.
+    ///
+    /// The value is the ideal (or exact) value if contained in `values`,
+    /// or else the first of `values` that satisfies `self`.
+    /// If none of `values` satisfy `self`, the first of `values` is used instead.
+    pub(crate) fn value_among(&self, values: &[T]) -> Option<T> {
+        let preferred: Vec<&T> = match &self.kind {
+            NonNumericKind::Exists { is_expected } => {
+                if self.is_required && !is_expected {
+                    return None;
+                }
+                vec![]
+            }
+            NonNumericKind::Exactly { value } => vec![value],
+            NonNumericKind::AnyOf {
+                values: any_of,
+                ideal,
+            } => ideal.iter().chain(any_of.iter()).collect(),
+        };
+
+        preferred
+            .into_iter()
+            .find(|value| values.contains(value))
+            .or_else(|| values.first())
+            .cloned()
+    }
+}
+
+impl<T> ResolveBareValues for NonNumeric<T> {
+    fn resolve_bare_values(&mut self, mode: BareValueMode) {
+        if self.is_bare {
+            self.is_required = mode == BareValueMode::Exact;
+            self.is_bare = false;
+        }
+    }
 }
 
 impl<T> Fitness<T> for NonNumeric<T>
@@ -97,6 +164,277 @@ where
     }
 }
 
+impl<T> Unsatisfied<T> for NonNumeric<T>
+where
+    T: Clone + PartialEq + Debug,
+{
+    fn unsatisfied_by(&self, name: &str, actual: Option<&T>) -> Option<OverconstrainedError> {
+        unsatisfied(
+            name,
+            self.is_required,
+            self.fitness_distance(actual),
+            actual,
+            &self.kind,
+        )
+    }
+}
+
+/// (De)serialization in the shape of the W3C `ConstrainDOMString`/`ConstrainBoolean` types.
+///
+/// - `true`/`false` maps to `Exists`, unless `T` itself deserializes from booleans.
+/// - A bare value maps to a bare `Exactly`, see [`NonNumeric::bare`].
+/// - A bare list maps to a bare `AnyOf`, see [`NonNumeric::bare_any_of`].
+/// - `{ "ideal": value }` maps to a non-required `Exactly`.
+/// - `{ "ideal": [...] }` maps to a non-required `AnyOf`.
+/// - `{ "exact": value }` maps to a required `Exactly`.
+/// - `{ "exact": [...], "ideal": value }` maps to a required `AnyOf`.
+///
+/// # Specification
+/// - <https://www.w3.org/TR/mediacapture-streams/#dom-constraindomstring>
+/// - <https://www.w3.org/TR/mediacapture-streams/#dom-constrainboolean>
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Repr<T> {
+        Bare(T),
+        List(Vec<T>),
+        Exists(bool),
+        Object(Object<T>),
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        One(T),
+        Many(Vec<T>),
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Object<T> {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        exact: Option<OneOrMany<T>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        ideal: Option<OneOrMany<T>>,
+    }
+
+    impl<T> Serialize for NonNumeric<T>
+    where
+        T: Serialize,
+    {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let repr: Repr<&T> = match &self.kind {
+                NonNumericKind::Exists { is_expected } => Repr::Exists(*is_expected),
+                NonNumericKind::Exactly { value } if self.is_bare => Repr::Bare(value),
+                NonNumericKind::Exactly { value } if !self.is_required => Repr::Object(Object {
+                    exact: None,
+                    ideal: Some(OneOrMany::One(value)),
+                }),
+                NonNumericKind::Exactly { value } => Repr::Object(Object {
+                    exact: Some(OneOrMany::One(value)),
+                    ideal: None,
+                }),
+                NonNumericKind::AnyOf { values, .. } if self.is_bare => {
+                    Repr::List(values.iter().collect())
+                }
+                // A non-required list has no way of expressing an ideal value:
+                NonNumericKind::AnyOf { values, .. } if !self.is_required => Repr::Object(Object {
+                    exact: None,
+                    ideal: Some(OneOrMany::Many(values.iter().collect())),
+                }),
+                NonNumericKind::AnyOf { values, ideal } => Repr::Object(Object {
+                    exact: Some(OneOrMany::Many(values.iter().collect())),
+                    ideal: ideal.as_ref().map(OneOrMany::One),
+                }),
+            };
+
+            repr.serialize(serializer)
+        }
+    }
+
+    impl<'de, T> Deserialize<'de> for NonNumeric<T>
+    where
+        T: Deserialize<'de> + PartialEq,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let (is_required, kind) = match Repr::<T>::deserialize(deserializer)? {
+                Repr::Bare(value) => {
+                    return Ok(Self {
+                        is_required: false,
+                        is_bare: true,
+                        kind: NonNumericKind::Exactly { value },
+                    })
+                }
+                Repr::List(values) => {
+                    return Ok(Self {
+                        is_required: false,
+                        is_bare: true,
+                        kind: NonNumericKind::AnyOf {
+                            values,
+                            ideal: None,
+                        },
+                    })
+                }
+                Repr::Exists(is_expected) => (false, NonNumericKind::Exists { is_expected }),
+                Repr::Object(Object { exact, ideal }) => match (exact, ideal) {
+                    (Some(OneOrMany::One(value)), None) => {
+                        (true, NonNumericKind::Exactly { value })
+                    }
+                    (Some(OneOrMany::One(_)), Some(_)) => {
+                        return Err(D::Error::custom(
+                            "a single `exact` value cannot be combined with `ideal`",
+                        ));
+                    }
+                    (Some(OneOrMany::Many(values)), None) => (
+                        true,
+                        NonNumericKind::AnyOf {
+                            values,
+                            ideal: None,
+                        },
+                    ),
+                    (Some(OneOrMany::Many(values)), Some(OneOrMany::One(ideal)))
+                        if values.contains(&ideal) =>
+                    {
+                        (
+                            true,
+                            NonNumericKind::AnyOf {
+                                values,
+                                ideal: Some(ideal),
+                            },
+                        )
+                    }
+                    (Some(OneOrMany::Many(_)), Some(_)) => {
+                        return Err(D::Error::custom(
+                            "`ideal` must be one of the `exact` values",
+                        ));
+                    }
+                    (None, Some(OneOrMany::One(value))) => {
+                        (false, NonNumericKind::Exactly { value })
+                    }
+                    (None, Some(OneOrMany::Many(values))) => (
+                        false,
+                        NonNumericKind::AnyOf {
+                            values,
+                            ideal: None,
+                        },
+                    ),
+                    (None, None) => {
+                        return Err(D::Error::custom(
+                            "expected at least one of `exact` or `ideal`",
+                        ));
+                    }
+                },
+            };
+
+            Ok(Self {
+                is_required,
+                is_bare: false,
+                kind,
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use serde_json::json;
+
+        use super::*;
+
+        #[test]
+        fn serialize() {
+            let scenarios = [
+                (NonNumeric::exists(true), json!(true)),
+                (NonNumeric::bare("x".to_owned()), json!("x")),
+                (NonNumeric::exactly("x".to_owned()), json!({ "ideal": "x" })),
+                (
+                    NonNumeric::exactly("x".to_owned()).is_required(true),
+                    json!({ "exact": "x" }),
+                ),
+                (
+                    NonNumeric::bare_any_of(vec!["x".to_owned(), "o".to_owned()]),
+                    json!(["x", "o"]),
+                ),
+                (
+                    NonNumeric::any_of(vec!["x".to_owned(), "o".to_owned()], None),
+                    json!({ "ideal": ["x", "o"] }),
+                ),
+                (
+                    NonNumeric::any_of(vec!["x".to_owned(), "o".to_owned()], Some("o".to_owned()))
+                        .is_required(true),
+                    json!({ "exact": ["x", "o"], "ideal": "o" }),
+                ),
+            ];
+
+            for (subject, expected) in scenarios {
+                let actual = serde_json::to_value(&subject).unwrap();
+                assert_eq!(actual, expected);
+            }
+        }
+
+        #[test]
+        fn deserialize() {
+            let scenarios = [
+                (json!(false), NonNumeric::exists(false)),
+                (json!("x"), NonNumeric::bare("x".to_owned())),
+                (json!({ "ideal": "x" }), NonNumeric::exactly("x".to_owned())),
+                (
+                    json!({ "exact": "x" }),
+                    NonNumeric::exactly("x".to_owned()).is_required(true),
+                ),
+                (
+                    json!(["x", "o"]),
+                    NonNumeric::bare_any_of(vec!["x".to_owned(), "o".to_owned()]),
+                ),
+                (
+                    json!({ "ideal": ["x", "o"] }),
+                    NonNumeric::any_of(vec!["x".to_owned(), "o".to_owned()], None),
+                ),
+                (
+                    json!({ "exact": ["x", "o"], "ideal": "o" }),
+                    NonNumeric::any_of(vec!["x".to_owned(), "o".to_owned()], Some("o".to_owned()))
+                        .is_required(true),
+                ),
+            ];
+
+            for (json, expected) in scenarios {
+                let actual: NonNumeric<String> = serde_json::from_value(json).unwrap();
+                assert_eq!(actual, expected);
+            }
+        }
+
+        #[test]
+        fn deserialize_empty() {
+            let actual = serde_json::from_value::<NonNumeric<String>>(json!({}));
+            assert!(actual.is_err());
+        }
+
+        #[test]
+        fn deserialize_exact_with_ideal() {
+            let scenarios = [
+                json!({ "exact": "x", "ideal": "x" }),
+                json!({ "exact": "x", "ideal": ["x", "o"] }),
+                json!({ "exact": ["x", "o"], "ideal": "-" }),
+                json!({ "exact": ["x", "o"], "ideal": ["x"] }),
+            ];
+
+            for json in scenarios {
+                let actual = serde_json::from_value::<NonNumeric<String>>(json);
+                assert!(actual.is_err());
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,6 +455,7 @@ mod tests {
             subject,
             NonNumeric {
                 is_required: false,
+                is_bare: false,
                 kind: NonNumericKind::Exists { is_expected: true }
             }
         );
@@ -129,6 +468,7 @@ mod tests {
             subject,
             NonNumeric {
                 is_required: false,
+                is_bare: false,
                 kind: NonNumericKind::Exactly { value: Dummy("x") }
             }
         );
@@ -142,6 +482,7 @@ mod tests {
             subject,
             NonNumeric {
                 is_required: false,
+                is_bare: false,
                 kind: NonNumericKind::AnyOf {
                     values: vec![Dummy("x"), Dummy("o")],
                     ideal: Some(Dummy("x"))
@@ -153,11 +494,43 @@ mod tests {
     #[test]
     fn is_required() {
         let subject = NonNumeric::<Dummy>::exists(true);
-        assert_eq!(subject.is_required, false);
+        assert!(!subject.is_required);
         let subject = subject.is_required(true);
-        assert_eq!(subject.is_required, true);
+        assert!(subject.is_required);
         let subject = subject.is_required(false);
-        assert_eq!(subject.is_required, false);
+        assert!(!subject.is_required);
+    }
+
+    #[test]
+    fn bare() {
+        let subject = NonNumeric::bare(Dummy("x"));
+        assert_eq!(
+            subject,
+            NonNumeric {
+                is_required: false,
+                is_bare: true,
+                kind: NonNumericKind::Exactly { value: Dummy("x") }
+            }
+        );
+        assert!(!subject.is_required(true).is_bare);
+    }
+
+    #[test]
+    fn resolve_bare_values() {
+        let mut subject = NonNumeric::bare(Dummy("x"));
+        subject.resolve_bare_values(BareValueMode::Ideal);
+        assert_eq!(subject, NonNumeric::exactly(Dummy("x")));
+
+        let mut subject = NonNumeric::bare_any_of(vec![Dummy("x"), Dummy("o")]);
+        subject.resolve_bare_values(BareValueMode::Exact);
+        assert_eq!(
+            subject,
+            NonNumeric::any_of(vec![Dummy("x"), Dummy("o")], None).is_required(true)
+        );
+
+        let mut subject = NonNumeric::exactly(Dummy("x"));
+        subject.resolve_bare_values(BareValueMode::Exact);
+        assert_eq!(subject, NonNumeric::exactly(Dummy("x")));
     }
 
     #[test]
@@ -234,4 +607,37 @@ mod tests {
             f64::INFINITY
         );
     }
+
+    #[test]
+    fn value_among() {
+        let values = vec![Dummy("x"), Dummy("o")];
+
+        assert_eq!(
+            NonNumeric::<Dummy>::exists(true).value_among(&values),
+            Some(Dummy("x"))
+        );
+        assert_eq!(
+            NonNumeric::<Dummy>::exists(false)
+                .is_required(true)
+                .value_among(&values),
+            None
+        );
+        assert_eq!(
+            NonNumeric::exactly(Dummy("o")).value_among(&values),
+            Some(Dummy("o"))
+        );
+        assert_eq!(
+            NonNumeric::exactly(Dummy("-")).value_among(&values),
+            Some(Dummy("x"))
+        );
+        assert_eq!(
+            NonNumeric::any_of(vec![Dummy("-"), Dummy("o")], None).value_among(&values),
+            Some(Dummy("o"))
+        );
+        assert_eq!(
+            NonNumeric::any_of(vec![Dummy("x"), Dummy("o")], Some(Dummy("o"))).value_among(&values),
+            Some(Dummy("o"))
+        );
+        assert_eq!(NonNumeric::exactly(Dummy("x")).value_among(&[]), None);
+    }
 }
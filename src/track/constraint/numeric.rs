@@ -1,4 +1,8 @@
-use crate::track::constraint::Fitness;
+use std::{fmt::Debug, ops::RangeInclusive};
+
+use crate::track::constraint::{
+    unsatisfied, BareValueMode, Fitness, OverconstrainedError, ResolveBareValues, Unsatisfied,
+};
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum NumericKind<T> {
@@ -16,6 +20,7 @@ pub(crate) trait NumericSetting<T> {
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct Numeric<T> {
     is_required: bool,
+    is_bare: bool,
     kind: NumericKind<T>,
 }
 
@@ -36,7 +41,11 @@ where
     T: Clone + PartialOrd,
 {
     pub fn new(is_required: bool, kind: NumericKind<T>) -> Self {
-        Self { is_required, kind }
+        Self {
+            is_required,
+            is_bare: false,
+            kind,
+        }
     }
 
     pub fn exists(is_expected: bool) -> Self {
@@ -47,6 +56,16 @@ where
         Self::new(false, NumericKind::Exactly { value })
     }
 
+    /// Creates a bare value, i.e. one given without stating whether it is ideal or exact.
+    ///
+    /// Bare values behave like ideal values until resolved via [`ResolveBareValues`].
+    pub fn bare(value: T) -> Self {
+        Self {
+            is_bare: true,
+            ..Self::exactly(value)
+        }
+    }
+
     pub fn at_least(min: T, ideal: Option<T>) -> Self {
         if let Some(ideal) = &ideal {
             assert!(min <= *ideal);
@@ -69,10 +88,64 @@ where
         Self::new(false, NumericKind::Within { min, max, ideal })
     }
 
+    /// Sets whether `self` is required, which also stops it from being a bare value.
     pub fn is_required(mut self, is_required: bool) -> Self {
         self.is_required = is_required;
+        self.is_bare = false;
         self
     }
+
+    /// Returns the value within `range` that best satisfies `self`.
+    ///
+    /// The value is the ideal (or exact) value, clamped to the part of `range`
+    /// that satisfies `self`, or that part's upper bound if there is no ideal value.
+    /// If no part of `range` satisfies `self`, the whole `range` is used instead.
+    pub(crate) fn value_within(&self, range: &RangeInclusive<T>) -> Option<T> {
+        let (min, max, ideal) = match &self.kind {
+            NumericKind::Exists { is_expected } => {
+                if self.is_required && !is_expected {
+                    return None;
+                }
+                (None, None, None)
+            }
+            NumericKind::Exactly { value } => (Some(value), Some(value), Some(value)),
+            NumericKind::AtLeast { min, ideal } => (Some(min), None, ideal.as_ref()),
+            NumericKind::AtMost { max, ideal } => (None, Some(max), ideal.as_ref()),
+            NumericKind::Within { min, max, ideal } => (Some(min), Some(max), ideal.as_ref()),
+        };
+
+        let mut lower = range.start();
+        let mut upper = range.end();
+
+        if let Some(min) = min.filter(|min| *min > lower) {
+            lower = min;
+        }
+        if let Some(max) = max.filter(|max| *max < upper) {
+            upper = max;
+        }
+        if lower > upper {
+            lower = range.start();
+            upper = range.end();
+        }
+
+        let value = match ideal {
+            Some(ideal) if ideal < lower => lower,
+            Some(ideal) if ideal > upper => upper,
+            Some(ideal) => ideal,
+            None => upper,
+        };
+
+        Some(value.clone())
+    }
+}
+
+impl<T> ResolveBareValues for Numeric<T> {
+    fn resolve_bare_values(&mut self, mode: BareValueMode) {
+        if self.is_bare {
+            self.is_required = mode == BareValueMode::Exact;
+            self.is_bare = false;
+        }
+    }
 }
 
 impl<T> Fitness<T> for Numeric<T>
@@ -139,12 +212,354 @@ where
 
                 let numerator = (actual - ideal).abs();
                 let denominator = actual.abs().max(ideal.abs());
-                numerator / denominator
+                if denominator == 0.0 {
+                    // Both are zero, so a perfect fit:
+                    0.0
+                } else {
+                    numerator / denominator
+                }
             }
         }
     }
 }
 
+impl<T> Unsatisfied<T> for Numeric<T>
+where
+    T: Clone + PartialOrd + Debug,
+    Self: NumericSetting<T>,
+{
+    fn unsatisfied_by(&self, name: &str, actual: Option<&T>) -> Option<OverconstrainedError> {
+        unsatisfied(
+            name,
+            self.is_required,
+            self.fitness_distance(actual),
+            actual,
+            &self.kind,
+        )
+    }
+}
+
+/// (De)serialization in the shape of the W3C `ConstrainULong`/`ConstrainDouble` types.
+///
+/// - A bare value maps to a bare `Exactly`, see [`Numeric::bare`].
+/// - `{ "ideal": value }` maps to a non-required `Exactly`.
+/// - `{ "exact": value }` maps to a required `Exactly`.
+/// - `{ "min": ..., "max": ..., "ideal": ... }` maps to a required `AtLeast`, `AtMost` or `Within`.
+///
+/// `exact` cannot be combined with any other member. Booleans are not valid
+/// numeric constraints, so `Exists` fails to serialize and `true`/`false` fail
+/// to deserialize, except for the `(boolean or ConstrainDouble)` constraints
+/// `pan`, `tilt` and `zoom`, which use [`boolean_or`].
+///
+/// # Specification
+/// - <https://www.w3.org/TR/mediacapture-streams/#dom-constrainulong>
+/// - <https://www.w3.org/TR/mediacapture-streams/#dom-constraindouble>
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use serde::{
+        de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Repr<T> {
+        Exists(bool),
+        Bare(T),
+        Object(Object<T>),
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Object<T> {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        exact: Option<T>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        ideal: Option<T>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min: Option<T>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max: Option<T>,
+    }
+
+    impl<T> Serialize for Numeric<T>
+    where
+        T: Serialize,
+    {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let object = |exact, ideal, min, max| {
+                Repr::Object(Object {
+                    exact,
+                    ideal,
+                    min,
+                    max,
+                })
+            };
+
+            let repr: Repr<&T> = match &self.kind {
+                NumericKind::Exists { .. } => {
+                    return Err(S::Error::custom(
+                        "`Exists` is not a valid numeric constraint",
+                    ));
+                }
+                NumericKind::Exactly { value } if self.is_bare => Repr::Bare(value),
+                NumericKind::Exactly { value } if !self.is_required => {
+                    object(None, Some(value), None, None)
+                }
+                NumericKind::Exactly { value } => object(Some(value), None, None, None),
+                NumericKind::AtLeast { min, ideal } => {
+                    object(None, ideal.as_ref(), Some(min), None)
+                }
+                NumericKind::AtMost { max, ideal } => object(None, ideal.as_ref(), None, Some(max)),
+                NumericKind::Within { min, max, ideal } => {
+                    object(None, ideal.as_ref(), Some(min), Some(max))
+                }
+            };
+
+            repr.serialize(serializer)
+        }
+    }
+
+    impl<'de, T> Deserialize<'de> for Numeric<T>
+    where
+        T: Deserialize<'de> + PartialOrd,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let (is_required, kind) = match Repr::<T>::deserialize(deserializer)? {
+                Repr::Exists(_) => {
+                    return Err(D::Error::custom(
+                        "expected a number or an object, found a boolean",
+                    ));
+                }
+                Repr::Bare(value) => {
+                    return Ok(Self {
+                        is_required: false,
+                        is_bare: true,
+                        kind: NumericKind::Exactly { value },
+                    })
+                }
+                Repr::Object(Object {
+                    exact: Some(value),
+                    ideal: None,
+                    min: None,
+                    max: None,
+                }) => (true, NumericKind::Exactly { value }),
+                Repr::Object(Object { exact: Some(_), .. }) => {
+                    return Err(D::Error::custom(
+                        "`exact` cannot be combined with `ideal`, `min` or `max`",
+                    ));
+                }
+                Repr::Object(Object {
+                    exact: None,
+                    ideal,
+                    min,
+                    max,
+                }) => {
+                    if let (Some(min), Some(max)) = (&min, &max) {
+                        if min > max {
+                            return Err(D::Error::custom("`min` cannot be greater than `max`"));
+                        }
+                    }
+                    if let Some(ideal) = &ideal {
+                        let is_below = min.as_ref().map_or(false, |min| ideal < min);
+                        let is_above = max.as_ref().map_or(false, |max| ideal > max);
+                        if is_below || is_above {
+                            return Err(D::Error::custom(
+                                "`ideal` must lie between `min` and `max`",
+                            ));
+                        }
+                    }
+
+                    match (min, max, ideal) {
+                        (Some(min), Some(max), ideal) => {
+                            (true, NumericKind::Within { min, max, ideal })
+                        }
+                        (Some(min), None, ideal) => (true, NumericKind::AtLeast { min, ideal }),
+                        (None, Some(max), ideal) => (true, NumericKind::AtMost { max, ideal }),
+                        (None, None, Some(value)) => (false, NumericKind::Exactly { value }),
+                        (None, None, None) => {
+                            return Err(D::Error::custom(
+                                "expected at least one of `exact`, `ideal`, `min` or `max`",
+                            ));
+                        }
+                    }
+                }
+            };
+
+            Ok(Self {
+                is_required,
+                is_bare: false,
+                kind,
+            })
+        }
+    }
+
+    /// (De)serialization of optional `(boolean or ConstrainDouble)` constraints,
+    /// where `true`/`false` map to `Exists`.
+    ///
+    /// Use with `#[serde(with = "...")]` on `Option<Numeric<T>>` fields.
+    pub(crate) mod boolean_or {
+        use super::*;
+
+        #[derive(Deserialize)]
+        #[serde(untagged, bound(deserialize = "T: Deserialize<'de> + PartialOrd"))]
+        enum BooleanOr<T> {
+            Boolean(bool),
+            Numeric(Numeric<T>),
+        }
+
+        pub(crate) fn serialize<T, S>(
+            constraint: &Option<Numeric<T>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            T: Serialize,
+            S: Serializer,
+        {
+            match constraint {
+                Some(Numeric {
+                    kind: NumericKind::Exists { is_expected },
+                    ..
+                }) => serializer.serialize_bool(*is_expected),
+                Some(constraint) => constraint.serialize(serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub(crate) fn deserialize<'de, T, D>(
+            deserializer: D,
+        ) -> Result<Option<Numeric<T>>, D::Error>
+        where
+            T: Deserialize<'de> + PartialOrd,
+            D: Deserializer<'de>,
+        {
+            Ok(Some(match BooleanOr::deserialize(deserializer)? {
+                BooleanOr::Boolean(is_expected) => Numeric {
+                    is_required: false,
+                    is_bare: false,
+                    kind: NumericKind::Exists { is_expected },
+                },
+                BooleanOr::Numeric(constraint) => constraint,
+            }))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use serde_json::json;
+
+        use super::*;
+
+        #[test]
+        fn serialize() {
+            let scenarios = [
+                (Numeric::bare(42), json!(42)),
+                (Numeric::exactly(42), json!({ "ideal": 42 })),
+                (
+                    Numeric::exactly(42).is_required(true),
+                    json!({ "exact": 42 }),
+                ),
+                (
+                    Numeric::at_least(10, Some(42)).is_required(true),
+                    json!({ "ideal": 42, "min": 10 }),
+                ),
+                (
+                    Numeric::at_most(100, None).is_required(true),
+                    json!({ "max": 100 }),
+                ),
+                (
+                    Numeric::within(10, 100, Some(42)).is_required(true),
+                    json!({ "ideal": 42, "min": 10, "max": 100 }),
+                ),
+            ];
+
+            for (subject, expected) in scenarios {
+                let actual = serde_json::to_value(&subject).unwrap();
+                assert_eq!(actual, expected);
+            }
+        }
+
+        #[test]
+        fn deserialize() {
+            let scenarios = [
+                (json!(42), Numeric::bare(42)),
+                (json!({ "ideal": 42 }), Numeric::exactly(42)),
+                (
+                    json!({ "exact": 42 }),
+                    Numeric::exactly(42).is_required(true),
+                ),
+                (
+                    json!({ "min": 10, "ideal": 42 }),
+                    Numeric::at_least(10, Some(42)).is_required(true),
+                ),
+                (
+                    json!({ "max": 100 }),
+                    Numeric::at_most(100, None).is_required(true),
+                ),
+                (
+                    json!({ "min": 10, "max": 100 }),
+                    Numeric::within(10, 100, None).is_required(true),
+                ),
+            ];
+
+            for (json, expected) in scenarios {
+                let actual: Numeric<u32> = serde_json::from_value(json).unwrap();
+                assert_eq!(actual, expected);
+            }
+        }
+
+        #[test]
+        fn deserialize_empty() {
+            let actual = serde_json::from_value::<Numeric<u32>>(json!({}));
+            assert!(actual.is_err());
+        }
+
+        #[test]
+        fn exists() {
+            assert!(serde_json::to_value(Numeric::<u32>::exists(true)).is_err());
+            assert!(serde_json::from_value::<Numeric<u32>>(json!(true)).is_err());
+        }
+
+        #[test]
+        fn deserialize_exact_with_others() {
+            let scenarios = [
+                json!({ "exact": 42, "ideal": 50 }),
+                json!({ "exact": 42, "min": 10 }),
+                json!({ "exact": 42, "max": 100 }),
+            ];
+
+            for json in scenarios {
+                let actual = serde_json::from_value::<Numeric<u32>>(json);
+                assert!(actual.is_err());
+            }
+        }
+
+        #[test]
+        fn deserialize_inconsistent_bounds() {
+            let scenarios = [
+                json!({ "min": 100, "max": 10 }),
+                json!({ "min": 10, "ideal": 5 }),
+                json!({ "max": 100, "ideal": 500 }),
+                json!({ "min": 10, "max": 100, "ideal": 500 }),
+            ];
+
+            for json in scenarios {
+                let actual = serde_json::from_value::<Numeric<u32>>(json);
+                assert!(actual.is_err());
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+pub(crate) use serde_impl::boolean_or;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,6 +571,7 @@ mod tests {
             subject,
             Numeric {
                 is_required: false,
+                is_bare: false,
                 kind: NumericKind::Exists { is_expected: true }
             }
         );
@@ -168,6 +584,7 @@ mod tests {
             subject,
             Numeric {
                 is_required: false,
+                is_bare: false,
                 kind: NumericKind::Exactly { value: 42 }
             }
         );
@@ -180,6 +597,7 @@ mod tests {
             subject,
             Numeric {
                 is_required: false,
+                is_bare: false,
                 kind: NumericKind::AtLeast {
                     min: 10,
                     ideal: Some(42)
@@ -195,6 +613,7 @@ mod tests {
             subject,
             Numeric {
                 is_required: false,
+                is_bare: false,
                 kind: NumericKind::AtMost {
                     max: 100,
                     ideal: Some(42)
@@ -210,6 +629,7 @@ mod tests {
             subject,
             Numeric {
                 is_required: false,
+                is_bare: false,
                 kind: NumericKind::Within {
                     min: 10,
                     max: 100,
@@ -222,11 +642,40 @@ mod tests {
     #[test]
     fn is_required() {
         let subject = Numeric::<u32>::exists(true);
-        assert_eq!(subject.is_required, false);
+        assert!(!subject.is_required);
         let subject = subject.is_required(true);
-        assert_eq!(subject.is_required, true);
+        assert!(subject.is_required);
         let subject = subject.is_required(false);
-        assert_eq!(subject.is_required, false);
+        assert!(!subject.is_required);
+    }
+
+    #[test]
+    fn bare() {
+        let subject = Numeric::<u32>::bare(42);
+        assert_eq!(
+            subject,
+            Numeric {
+                is_required: false,
+                is_bare: true,
+                kind: NumericKind::Exactly { value: 42 }
+            }
+        );
+        assert!(!subject.is_required(true).is_bare);
+    }
+
+    #[test]
+    fn resolve_bare_values() {
+        let mut subject = Numeric::<u32>::bare(42);
+        subject.resolve_bare_values(BareValueMode::Ideal);
+        assert_eq!(subject, Numeric::exactly(42));
+
+        let mut subject = Numeric::<u32>::bare(42);
+        subject.resolve_bare_values(BareValueMode::Exact);
+        assert_eq!(subject, Numeric::exactly(42).is_required(true));
+
+        let mut subject = Numeric::<u32>::exactly(42);
+        subject.resolve_bare_values(BareValueMode::Exact);
+        assert_eq!(subject, Numeric::exactly(42));
     }
 
     #[test]
@@ -270,7 +719,6 @@ mod tests {
     #[test]
     fn fitness_distance_at_least() {
         fn fitness(min: u32, ideal: Option<u32>, is_required: bool, setting: Option<u32>) -> f64 {
-            let ideal = ideal;
             let actual = setting;
             Numeric::<u32>::at_least(min, ideal)
                 .is_required(is_required)
@@ -298,7 +746,6 @@ mod tests {
     #[test]
     fn fitness_distance_at_most() {
         fn fitness(max: u32, ideal: Option<u32>, is_required: bool, setting: Option<u32>) -> f64 {
-            let ideal = ideal;
             let actual = setting;
             Numeric::<u32>::at_most(max, ideal)
                 .is_required(is_required)
@@ -332,7 +779,6 @@ mod tests {
             is_required: bool,
             setting: Option<u32>,
         ) -> f64 {
-            let ideal = ideal;
             let actual = setting;
             Numeric::<u32>::within(min, max, ideal)
                 .is_required(is_required)
@@ -356,4 +802,91 @@ mod tests {
         assert_eq!(fitness(10, 20, Some(15), true, None), f64::INFINITY);
         assert_eq!(fitness(10, 20, Some(15), true, Some(42)), f64::INFINITY);
     }
+
+    #[test]
+    fn fitness_distance_zero_and_negative() {
+        assert_eq!(
+            Numeric::<f64>::exactly(0.0).fitness_distance(Some(&0.0)),
+            0.0
+        );
+        assert_eq!(
+            Numeric::<f64>::exactly(0.0)
+                .is_required(true)
+                .fitness_distance(Some(&0.0)),
+            0.0
+        );
+        assert_eq!(
+            Numeric::<f64>::at_least(-10.0, Some(0.0)).fitness_distance(Some(&0.0)),
+            0.0
+        );
+        assert_eq!(
+            Numeric::<f64>::at_least(-10.0, Some(0.0)).fitness_distance(Some(&-5.0)),
+            1.0
+        );
+        assert_eq!(
+            Numeric::<f64>::within(-10.0, 10.0, Some(-4.0)).fitness_distance(Some(&-2.0)),
+            0.5
+        );
+        assert_eq!(
+            Numeric::<f64>::within(-10.0, 10.0, Some(-4.0)).fitness_distance(Some(&4.0)),
+            2.0
+        );
+        assert_eq!(
+            Numeric::<f64>::exactly(-4.0)
+                .is_required(true)
+                .fitness_distance(Some(&4.0)),
+            f64::INFINITY
+        );
+    }
+
+    #[test]
+    fn unsatisfied_by() {
+        let subject = Numeric::<f64>::at_least(-10.0, Some(0.0));
+        assert_eq!(subject.unsatisfied_by("pan", Some(&-20.0)), None);
+        assert_eq!(subject.unsatisfied_by("pan", Some(&0.0)), None);
+
+        let subject = subject.is_required(true);
+        assert_eq!(subject.unsatisfied_by("pan", Some(&0.0)), None);
+        assert_eq!(
+            subject
+                .unsatisfied_by("pan", Some(&-20.0))
+                .map(|error| error.constraint),
+            Some("pan".to_owned())
+        );
+    }
+
+    #[test]
+    fn value_within() {
+        let range = 10..=100;
+
+        assert_eq!(Numeric::<u32>::exists(true).value_within(&range), Some(100));
+        assert_eq!(
+            Numeric::<u32>::exists(false)
+                .is_required(true)
+                .value_within(&range),
+            None
+        );
+        assert_eq!(Numeric::<u32>::exactly(42).value_within(&range), Some(42));
+        assert_eq!(Numeric::<u32>::exactly(420).value_within(&range), Some(100));
+        assert_eq!(
+            Numeric::<u32>::at_least(20, None).value_within(&range),
+            Some(100)
+        );
+        assert_eq!(
+            Numeric::<u32>::at_least(20, Some(5_000)).value_within(&range),
+            Some(100)
+        );
+        assert_eq!(
+            Numeric::<u32>::at_most(50, None).value_within(&range),
+            Some(50)
+        );
+        assert_eq!(
+            Numeric::<u32>::within(20, 50, Some(30)).value_within(&range),
+            Some(30)
+        );
+        assert_eq!(
+            Numeric::<u32>::within(200, 500, Some(300)).value_within(&range),
+            Some(100)
+        );
+    }
 }
@@ -0,0 +1,160 @@
+//! Overconstrained error
+//!
+//! # W3C Spec:
+//!
+//! > If candidates is empty, let failedConstraint be any required constraint
+//! > whose fitness distance was infinity for all settings dictionaries examined
+//! > while executing the SelectSettings algorithm, or "" if there isn't one,
+//! > and let message be either undefined or an informative human-readable message.
+//! >
+//! > <https://www.w3.org/TR/mediacapture-streams/#dom-overconstrainederror>
+
+use std::fmt::Debug;
+
+use thiserror::Error;
+
+/// An error indicating that no settings could be found that satisfy the required constraints.
+#[derive(Error, PartialEq, Eq, Clone, Debug)]
+#[error("constraint '{constraint}' cannot be satisfied: {message}")]
+pub struct OverconstrainedError {
+    /// The name of the constraint that could not be satisfied,
+    /// or an empty string if no single constraint is to blame.
+    pub constraint: String,
+    /// A human-readable description of why the constraint could not be satisfied.
+    pub message: String,
+}
+
+impl OverconstrainedError {
+    pub fn new(constraint: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            constraint: constraint.into(),
+            message: message.into(),
+        }
+    }
+}
+
+pub(crate) trait Unsatisfied<Value> {
+    /// Returns an error describing why `actual` does not satisfy `self`,
+    /// or `None` if it does, or if `self` is not required.
+    fn unsatisfied_by(&self, name: &str, actual: Option<&Value>) -> Option<OverconstrainedError>;
+}
+
+/// Returns an error describing why `actual` does not satisfy the constraint `kind`
+/// named `name`, or `None` if the constraint is not required or its `fitness_distance` is finite.
+pub(crate) fn unsatisfied<Value, Kind>(
+    name: &str,
+    is_required: bool,
+    fitness_distance: f64,
+    actual: Option<&Value>,
+    kind: &Kind,
+) -> Option<OverconstrainedError>
+where
+    Value: Debug,
+    Kind: Debug,
+{
+    if !is_required || fitness_distance.is_finite() {
+        return None;
+    }
+
+    let message = match actual {
+        Some(actual) => format!("{:?} does not satisfy {:?}", actual, kind),
+        None => format!("missing value does not satisfy {:?}", kind),
+    };
+
+    Some(OverconstrainedError::new(name, message))
+}
+
+pub(crate) trait Overconstrained<Value> {
+    /// Returns an error for each required constraint that is not satisfied by `value`,
+    /// or `None` if the constraints do not apply to `value` at all.
+    fn unsatisfied_constraints(&self, value: &Value) -> Option<Vec<OverconstrainedError>>;
+}
+
+/// Returns the error for a required constraint that is unsatisfied by any of the `candidates`.
+///
+/// Candidates to which the constraints do not apply are not examined.
+pub(crate) fn overconstrained_error<C, Value>(
+    constraints: &C,
+    candidates: &[Value],
+) -> OverconstrainedError
+where
+    C: Overconstrained<Value>,
+{
+    let mut examined = candidates
+        .iter()
+        .filter_map(|candidate| constraints.unsatisfied_constraints(candidate));
+
+    let first = match examined.next() {
+        Some(first) => first,
+        None => return OverconstrainedError::new("", "no applicable candidates"),
+    };
+
+    let rest: Vec<_> = examined.collect();
+
+    first
+        .into_iter()
+        .find(|error| {
+            rest.iter().all(|errors| {
+                errors
+                    .iter()
+                    .any(|other| other.constraint == error.constraint)
+            })
+        })
+        .unwrap_or_else(|| {
+            OverconstrainedError::new("", "no candidate satisfies all required constraints")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Dummy;
+
+    impl Overconstrained<Vec<&'static str>> for Dummy {
+        fn unsatisfied_constraints(
+            &self,
+            value: &Vec<&'static str>,
+        ) -> Option<Vec<OverconstrainedError>> {
+            if value.is_empty() {
+                return None;
+            }
+            Some(
+                value
+                    .iter()
+                    .map(|name| OverconstrainedError::new(*name, "unsatisfied"))
+                    .collect(),
+            )
+        }
+    }
+
+    #[test]
+    fn display() {
+        let subject = OverconstrainedError::new("width", "value is missing");
+        assert_eq!(
+            subject.to_string(),
+            "constraint 'width' cannot be satisfied: value is missing"
+        );
+    }
+
+    #[test]
+    fn common_constraint() {
+        let candidates = vec![vec!["width", "height"], vec![], vec!["height"]];
+        let actual = overconstrained_error(&Dummy, &candidates);
+        assert_eq!(actual, OverconstrainedError::new("height", "unsatisfied"));
+    }
+
+    #[test]
+    fn no_common_constraint() {
+        let candidates = vec![vec!["width"], vec!["height"]];
+        let actual = overconstrained_error(&Dummy, &candidates);
+        assert_eq!(actual.constraint, "");
+    }
+
+    #[test]
+    fn no_applicable_candidates() {
+        let candidates = vec![vec![]];
+        let actual = overconstrained_error(&Dummy, &candidates);
+        assert_eq!(actual.constraint, "");
+    }
+}
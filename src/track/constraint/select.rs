@@ -0,0 +1,238 @@
+//! Settings selection
+//!
+//! # W3C Spec:
+//!
+//! > 1. For every possible settings dictionary of copy compute its fitness distance,
+//! > treating bare values of properties as ideal values. Let candidates be the set of
+//! > settings dictionaries for which the fitness distance is finite.
+//! >
+//! > 2. If candidates is empty, return `undefined` as the result of the `SelectSettings()` algorithm.
+//! >
+//! > 3. Iterate over the '`advanced`' ConstraintSets in newConstraints in the order in which they were specified.
+//! > For each ConstraintSet:
+//! >
+//! >    1. compute the fitness distance between it and each settings dictionary in candidates,
+//! >       treating bare values of properties as exact.
+//! >
+//! >    2. If the fitness distance is finite for one or more settings dictionaries in candidates,
+//! >       keep those settings dictionaries in candidates, discarding others.
+//! >       If the fitness distance is infinite for all settings dictionaries in candidates,
+//! >       ignore this ConstraintSet.
+//! >
+//! > 4. Select one settings dictionary from candidates, and return it as the result
+//! > of the `SelectSettings()` algorithm. The User Agent MUST use the one with the
+//! > smallest fitness distance, as calculated in step 1.
+//! >
+//! > <https://www.w3.org/TR/mediacapture-streams/#dfn-selectsettings>
+
+use std::cmp::Ordering;
+
+use crate::track::{
+    constraint::{
+        audio::Audio, overconstrained_error, video::Video, Fitness, Media, Overconstrained,
+        OverconstrainedError,
+    },
+    setting::Media as MediaSetting,
+};
+
+/// Selection of the settings that best satisfy a set of constraints.
+pub trait SelectSettings {
+    /// Returns those `candidates` that satisfy the constraints,
+    /// ordered by ascending fitness distance (i.e. best fit first).
+    ///
+    /// Candidates with an infinite fitness distance are dropped.
+    /// Candidates of equal fitness distance retain their relative order.
+    ///
+    /// # Errors
+    /// Returns an `OverconstrainedError` if none of the `candidates` satisfy the constraints.
+    fn try_select_settings<'a>(
+        &self,
+        candidates: &'a [MediaSetting],
+    ) -> Result<Vec<&'a MediaSetting>, OverconstrainedError>;
+
+    /// Returns those `candidates` that satisfy the constraints,
+    /// ordered by ascending fitness distance (i.e. best fit first).
+    ///
+    /// Returns an empty vec if none of the `candidates` satisfy the constraints.
+    fn select_settings<'a>(&self, candidates: &'a [MediaSetting]) -> Vec<&'a MediaSetting> {
+        self.try_select_settings(candidates).unwrap_or_default()
+    }
+}
+
+impl SelectSettings for Video {
+    fn try_select_settings<'a>(
+        &self,
+        candidates: &'a [MediaSetting],
+    ) -> Result<Vec<&'a MediaSetting>, OverconstrainedError> {
+        try_select_settings_by(self, candidates)
+    }
+}
+
+impl SelectSettings for Audio {
+    fn try_select_settings<'a>(
+        &self,
+        candidates: &'a [MediaSetting],
+    ) -> Result<Vec<&'a MediaSetting>, OverconstrainedError> {
+        try_select_settings_by(self, candidates)
+    }
+}
+
+impl SelectSettings for Media {
+    fn try_select_settings<'a>(
+        &self,
+        candidates: &'a [MediaSetting],
+    ) -> Result<Vec<&'a MediaSetting>, OverconstrainedError> {
+        try_select_settings_by(self, candidates)
+    }
+}
+
+/// Returns the `candidates` with a finite fitness distance to `constraints`,
+/// ordered by ascending fitness distance.
+pub(crate) fn try_select_settings_by<'a, C>(
+    constraints: &C,
+    candidates: &'a [MediaSetting],
+) -> Result<Vec<&'a MediaSetting>, OverconstrainedError>
+where
+    C: Fitness<MediaSetting> + Overconstrained<MediaSetting>,
+{
+    let mut ranked: Vec<(f64, &'a MediaSetting)> = candidates
+        .iter()
+        .map(|candidate| (constraints.fitness_distance(Some(candidate)), candidate))
+        .filter(|(distance, _)| distance.is_finite())
+        .collect();
+
+    if ranked.is_empty() {
+        return Err(overconstrained_error(constraints, candidates));
+    }
+
+    // `sort_by` is stable, so equally fit candidates keep their order:
+    ranked.sort_by(|(lhs, _), (rhs, _)| lhs.partial_cmp(rhs).unwrap_or(Ordering::Equal));
+
+    Ok(ranked.into_iter().map(|(_, candidate)| candidate).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::track::{
+        constraint::{audio, video},
+        setting,
+    };
+
+    use super::*;
+
+    fn video_candidates() -> Vec<MediaSetting> {
+        [(640, 480), (1280, 720), (1920, 1080)]
+            .iter()
+            .map(|&(width, height)| {
+                MediaSetting::builder()
+                    .kind(
+                        setting::video::Video::builder()
+                            .width(width)
+                            .height(height)
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn audio_candidate() -> MediaSetting {
+        MediaSetting::builder()
+            .kind(
+                setting::audio::Audio::builder()
+                    .sample_rate(48_000)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn select_settings_ranks_by_fitness_distance() {
+        let candidates = video_candidates();
+
+        let constraints = Video::builder()
+            .width(video::Width::at_least(0, Some(1280)))
+            .build()
+            .unwrap();
+
+        let actual = constraints.select_settings(&candidates);
+        let expected = vec![&candidates[1], &candidates[2], &candidates[0]];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn select_settings_drops_unsatisfiable_candidates() {
+        let candidates = video_candidates();
+
+        let constraints = Video::builder()
+            .width(video::Width::at_least(1000, None).is_required(true))
+            .build()
+            .unwrap();
+
+        let actual = constraints.select_settings(&candidates);
+        let expected = vec![&candidates[1], &candidates[2]];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn select_settings_drops_other_kinds() {
+        let mut candidates = video_candidates();
+        candidates.push(audio_candidate());
+
+        let actual = Audio::default().select_settings(&candidates);
+        let expected = vec![&candidates[3]];
+
+        assert_eq!(actual, expected);
+
+        let actual = Video::default().select_settings(&candidates);
+        let expected = vec![&candidates[0], &candidates[1], &candidates[2]];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn select_settings_empty() {
+        let candidates = vec![audio_candidate()];
+
+        let constraints = Audio::builder()
+            .sample_rate(audio::SampleRate::exactly(44_100).is_required(true))
+            .build()
+            .unwrap();
+
+        assert!(constraints.select_settings(&candidates).is_empty());
+    }
+
+    #[test]
+    fn try_select_settings_overconstrained() {
+        let candidates = video_candidates();
+
+        let constraints = Video::builder()
+            .width(video::Width::at_most(1920, None).is_required(true))
+            .height(video::Height::at_least(1440, None).is_required(true))
+            .build()
+            .unwrap();
+
+        let actual = constraints.try_select_settings(&candidates);
+        let expected = Err(OverconstrainedError::new(
+            "height",
+            "480 does not satisfy AtLeast { min: 1440, ideal: None }",
+        ));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn try_select_settings_overconstrained_without_culprit() {
+        let candidates = vec![audio_candidate()];
+
+        let actual = Video::default().try_select_settings(&candidates);
+
+        assert_eq!(actual.unwrap_err().constraint, "");
+    }
+}
@@ -0,0 +1,276 @@
+use derive_builder::Builder;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::track::{
+    constraint::{
+        try_select_settings_by, BareValueMode, Fitness, Overconstrained, OverconstrainedError,
+        ResolveBareValues, SelectSettings,
+    },
+    setting::Media as MediaSetting,
+};
+
+/// A track's constraints, consisting of a basic constraint set
+/// and an ordered list of advanced constraint sets.
+///
+/// The advanced constraint sets are applied in order during settings selection,
+/// each one narrowing down the candidates that satisfied the basic set and all
+/// previously applied advanced sets. An advanced set that cannot be satisfied
+/// by any of the remaining candidates is skipped.
+///
+/// # Important
+/// Bare values of advanced sets are treated as exact values during selection,
+/// so an advanced set can only ever be unsatisfiable if it contains required
+/// constraints or bare values.
+///
+/// # Specification
+/// - <https://www.w3.org/TR/mediacapture-streams/#dom-mediatrackconstraints>
+/// - <https://www.w3.org/TR/mediacapture-streams/#dom-mediatrackconstraints-advanced>
+#[derive(PartialEq, Default, Clone, Debug, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MediaTrackConstraints<T>
+where
+    T: Clone,
+{
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub basic: T,
+    #[builder(default, setter(into))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub advanced: Vec<T>,
+}
+
+impl<T> MediaTrackConstraints<T>
+where
+    T: Clone,
+{
+    pub fn builder() -> MediaTrackConstraintsBuilder<T> {
+        Default::default()
+    }
+
+    pub fn new(basic: T, advanced: Vec<T>) -> Self {
+        Self { basic, advanced }
+    }
+}
+
+impl<T> ResolveBareValues for MediaTrackConstraints<T>
+where
+    T: Clone + ResolveBareValues,
+{
+    /// Resolves the bare values of the basic constraint set according to `mode`.
+    ///
+    /// Bare values of advanced constraint sets are always resolved as exact values,
+    /// since an advanced constraint set without required constraints would always be satisfied.
+    fn resolve_bare_values(&mut self, mode: BareValueMode) {
+        self.basic.resolve_bare_values(mode);
+        for advanced in &mut self.advanced {
+            advanced.resolve_bare_values(BareValueMode::Exact);
+        }
+    }
+}
+
+impl<T> From<T> for MediaTrackConstraints<T>
+where
+    T: Clone,
+{
+    fn from(basic: T) -> Self {
+        Self::new(basic, vec![])
+    }
+}
+
+impl<T> SelectSettings for MediaTrackConstraints<T>
+where
+    T: Clone + Fitness<MediaSetting> + Overconstrained<MediaSetting> + ResolveBareValues,
+{
+    fn try_select_settings<'a>(
+        &self,
+        candidates: &'a [MediaSetting],
+    ) -> Result<Vec<&'a MediaSetting>, OverconstrainedError> {
+        let mut candidates = try_select_settings_by(&self.basic, candidates)?;
+
+        for advanced in &self.advanced {
+            // Bare values of advanced sets are treated as exact values:
+            let mut advanced = advanced.clone();
+            advanced.resolve_bare_values(BareValueMode::Exact);
+
+            let satisfying: Vec<_> = candidates
+                .iter()
+                .copied()
+                .filter(|candidate| advanced.fitness_distance(Some(candidate)).is_finite())
+                .collect();
+
+            // Unsatisfiable advanced constraint sets get ignored:
+            if !satisfying.is_empty() {
+                candidates = satisfying;
+            }
+        }
+
+        Ok(candidates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::track::{
+        constraint::video::{Height, Video},
+        setting,
+    };
+
+    use super::*;
+
+    fn candidates() -> Vec<MediaSetting> {
+        [(640, 480), (1280, 720), (1920, 1080)]
+            .iter()
+            .map(|&(width, height)| {
+                MediaSetting::builder()
+                    .kind(
+                        setting::video::Video::builder()
+                            .width(width)
+                            .height(height)
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn height(height: u32) -> Video {
+        Video::builder()
+            .height(Height::exactly(height).is_required(true))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn builder() {
+        let subject = MediaTrackConstraints::builder()
+            .basic(height(720))
+            .advanced(vec![height(1080)])
+            .build()
+            .unwrap();
+        assert_eq!(
+            subject,
+            MediaTrackConstraints {
+                basic: height(720),
+                advanced: vec![height(1080)],
+            }
+        );
+    }
+
+    #[test]
+    fn select_settings_without_advanced() {
+        let candidates = candidates();
+
+        let constraints = MediaTrackConstraints::from(Video::default());
+
+        let actual = constraints.select_settings(&candidates);
+        let expected = vec![&candidates[0], &candidates[1], &candidates[2]];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn select_settings_with_advanced() {
+        let candidates = candidates();
+
+        // Prefer 1080p, else 720p:
+        let constraints = MediaTrackConstraints::new(
+            Video::builder()
+                .height(Height::at_least(720, None).is_required(true))
+                .build()
+                .unwrap(),
+            vec![height(1080), height(720)],
+        );
+
+        let actual = constraints.select_settings(&candidates);
+        let expected = vec![&candidates[2]];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn select_settings_skips_unsatisfiable_advanced() {
+        let candidates = candidates();
+
+        let constraints = MediaTrackConstraints::new(
+            Video::default(),
+            vec![height(2160), height(720), height(1080)],
+        );
+
+        let actual = constraints.select_settings(&candidates);
+        let expected = vec![&candidates[1]];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn resolve_bare_values() {
+        fn bare_height(height: u32) -> Video {
+            Video::builder()
+                .height(Height::bare(height))
+                .build()
+                .unwrap()
+        }
+
+        let candidates = candidates();
+
+        let mut constraints = MediaTrackConstraints::new(
+            bare_height(720),
+            vec![bare_height(2160), bare_height(1080)],
+        );
+
+        // Unresolved bare values in advanced sets are treated as exact,
+        // so the unsatisfiable 2160p set gets ignored:
+        let actual = constraints.select_settings(&candidates);
+        let expected = vec![&candidates[2]];
+
+        assert_eq!(actual, expected);
+
+        constraints.resolve_bare_values(BareValueMode::Ideal);
+
+        assert_eq!(
+            constraints.basic,
+            Video::builder()
+                .height(Height::exactly(720))
+                .build()
+                .unwrap()
+        );
+        assert_eq!(constraints.advanced, vec![height(2160), height(1080)]);
+
+        let actual = constraints.select_settings(&candidates);
+        let expected = vec![&candidates[2]];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let json = serde_json::json!({
+            "height": { "min": 720 },
+            "advanced": [
+                { "height": { "exact": 1080 } },
+                { "height": { "exact": 720 } },
+            ],
+        });
+
+        let subject = MediaTrackConstraints::new(
+            Video::builder()
+                .height(Height::at_least(720, None).is_required(true))
+                .build()
+                .unwrap(),
+            vec![height(1080), height(720)],
+        );
+
+        let deserialized: MediaTrackConstraints<Video> =
+            serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(deserialized, subject);
+
+        let serialized = serde_json::to_value(&subject).unwrap();
+        assert_eq!(serialized, json);
+    }
+}
@@ -1,13 +1,17 @@
-use std::fmt::Debug;
+use std::{convert::Infallible, fmt::Debug};
 
 use derive_builder::Builder;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::track::{
-    constraint::{fitness::Fitness, NonNumeric, Numeric},
-    setting::video as setting,
+    constraint::{
+        fitness::Fitness, NonNumeric, Numeric, Overconstrained, OverconstrainedError, Unsatisfied,
+    },
+    setting::{video as setting, Media as MediaSetting, MediaKind as MediaKindSetting},
 };
 
-use super::Merge;
+use super::{BareValueMode, Merge, ResolveBareValues};
 
 pub type Width = Numeric<setting::Width>;
 pub type Height = Numeric<setting::Height>;
@@ -15,22 +19,127 @@ pub type AspectRatio = Numeric<setting::AspectRatio>;
 pub type FrameRate = Numeric<setting::FrameRate>;
 pub type FacingMode = NonNumeric<setting::FacingMode>;
 pub type ResizeMode = NonNumeric<setting::ResizeMode>;
+pub type WhiteBalanceMode = NonNumeric<setting::WhiteBalanceMode>;
+pub type ExposureMode = NonNumeric<setting::ExposureMode>;
+pub type FocusMode = NonNumeric<setting::FocusMode>;
+pub type ExposureCompensation = Numeric<setting::ExposureCompensation>;
+pub type ExposureTime = Numeric<setting::ExposureTime>;
+pub type ColorTemperature = Numeric<setting::ColorTemperature>;
+pub type Iso = Numeric<setting::Iso>;
+pub type Brightness = Numeric<setting::Brightness>;
+pub type Contrast = Numeric<setting::Contrast>;
+pub type Saturation = Numeric<setting::Saturation>;
+pub type Sharpness = Numeric<setting::Sharpness>;
+pub type FocusDistance = Numeric<setting::FocusDistance>;
+pub type Pan = Numeric<setting::Pan>;
+pub type Tilt = Numeric<setting::Tilt>;
+pub type Zoom = Numeric<setting::Zoom>;
+pub type Torch = NonNumeric<setting::Torch>;
+pub type DisplaySurface = NonNumeric<setting::DisplaySurface>;
+pub type LogicalSurface = NonNumeric<setting::LogicalSurface>;
+pub type Cursor = NonNumeric<setting::Cursor>;
 
 /// A video's constraints
 #[derive(PartialEq, Default, Clone, Builder)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
 pub struct Video {
     #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub width: Option<Width>,
     #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub height: Option<Height>,
     #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub aspect_ratio: Option<AspectRatio>,
     #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub frame_rate: Option<FrameRate>,
     #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub facing_mode: Option<FacingMode>,
     #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub resize_mode: Option<ResizeMode>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub white_balance_mode: Option<WhiteBalanceMode>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub exposure_mode: Option<ExposureMode>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub focus_mode: Option<FocusMode>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub exposure_compensation: Option<ExposureCompensation>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub exposure_time: Option<ExposureTime>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub color_temperature: Option<ColorTemperature>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub iso: Option<Iso>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub brightness: Option<Brightness>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub contrast: Option<Contrast>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub saturation: Option<Saturation>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub sharpness: Option<Sharpness>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub focus_distance: Option<FocusDistance>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            skip_serializing_if = "Option::is_none",
+            with = "crate::track::constraint::numeric::boolean_or"
+        )
+    )]
+    pub pan: Option<Pan>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            skip_serializing_if = "Option::is_none",
+            with = "crate::track::constraint::numeric::boolean_or"
+        )
+    )]
+    pub tilt: Option<Tilt>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            skip_serializing_if = "Option::is_none",
+            with = "crate::track::constraint::numeric::boolean_or"
+        )
+    )]
+    pub zoom: Option<Zoom>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub torch: Option<Torch>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub display_surface: Option<DisplaySurface>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub logical_surface: Option<LogicalSurface>,
+    #[builder(default, setter(into, strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub cursor: Option<Cursor>,
 }
 
 impl Video {
@@ -38,6 +147,7 @@ impl Video {
         Default::default()
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         width: Option<Width>,
         height: Option<Height>,
@@ -45,6 +155,25 @@ impl Video {
         frame_rate: Option<FrameRate>,
         facing_mode: Option<FacingMode>,
         resize_mode: Option<ResizeMode>,
+        white_balance_mode: Option<WhiteBalanceMode>,
+        exposure_mode: Option<ExposureMode>,
+        focus_mode: Option<FocusMode>,
+        exposure_compensation: Option<ExposureCompensation>,
+        exposure_time: Option<ExposureTime>,
+        color_temperature: Option<ColorTemperature>,
+        iso: Option<Iso>,
+        brightness: Option<Brightness>,
+        contrast: Option<Contrast>,
+        saturation: Option<Saturation>,
+        sharpness: Option<Sharpness>,
+        focus_distance: Option<FocusDistance>,
+        pan: Option<Pan>,
+        tilt: Option<Tilt>,
+        zoom: Option<Zoom>,
+        torch: Option<Torch>,
+        display_surface: Option<DisplaySurface>,
+        logical_surface: Option<LogicalSurface>,
+        cursor: Option<Cursor>,
     ) -> Self {
         Self {
             width,
@@ -53,6 +182,25 @@ impl Video {
             frame_rate,
             facing_mode,
             resize_mode,
+            white_balance_mode,
+            exposure_mode,
+            focus_mode,
+            exposure_compensation,
+            exposure_time,
+            color_temperature,
+            iso,
+            brightness,
+            contrast,
+            saturation,
+            sharpness,
+            focus_distance,
+            pan,
+            tilt,
+            zoom,
+            torch,
+            display_surface,
+            logical_surface,
+            cursor,
         }
     }
 }
@@ -79,13 +227,72 @@ impl Debug for Video {
         if let Some(resize_mode) = &self.resize_mode {
             builder.field("resize_mode", &resize_mode);
         }
+        if let Some(white_balance_mode) = &self.white_balance_mode {
+            builder.field("white_balance_mode", &white_balance_mode);
+        }
+        if let Some(exposure_mode) = &self.exposure_mode {
+            builder.field("exposure_mode", &exposure_mode);
+        }
+        if let Some(focus_mode) = &self.focus_mode {
+            builder.field("focus_mode", &focus_mode);
+        }
+        if let Some(exposure_compensation) = &self.exposure_compensation {
+            builder.field("exposure_compensation", &exposure_compensation);
+        }
+        if let Some(exposure_time) = &self.exposure_time {
+            builder.field("exposure_time", &exposure_time);
+        }
+        if let Some(color_temperature) = &self.color_temperature {
+            builder.field("color_temperature", &color_temperature);
+        }
+        if let Some(iso) = &self.iso {
+            builder.field("iso", &iso);
+        }
+        if let Some(brightness) = &self.brightness {
+            builder.field("brightness", &brightness);
+        }
+        if let Some(contrast) = &self.contrast {
+            builder.field("contrast", &contrast);
+        }
+        if let Some(saturation) = &self.saturation {
+            builder.field("saturation", &saturation);
+        }
+        if let Some(sharpness) = &self.sharpness {
+            builder.field("sharpness", &sharpness);
+        }
+        if let Some(focus_distance) = &self.focus_distance {
+            builder.field("focus_distance", &focus_distance);
+        }
+        if let Some(pan) = &self.pan {
+            builder.field("pan", &pan);
+        }
+        if let Some(tilt) = &self.tilt {
+            builder.field("tilt", &tilt);
+        }
+        if let Some(zoom) = &self.zoom {
+            builder.field("zoom", &zoom);
+        }
+        if let Some(torch) = &self.torch {
+            builder.field("torch", &torch);
+        }
+        if let Some(display_surface) = &self.display_surface {
+            builder.field("display_surface", &display_surface);
+        }
+        if let Some(logical_surface) = &self.logical_surface {
+            builder.field("logical_surface", &logical_surface);
+        }
+        if let Some(cursor) = &self.cursor {
+            builder.field("cursor", &cursor);
+        }
 
         builder.finish()
     }
 }
 
 impl Merge for Video {
-    fn merge(&mut self, other: &Self) {
+    type Error = Infallible;
+
+    fn merge(&mut self, other: &Self) -> Result<(), Self::Error> {
         if self.width.is_none() {
             self.width = other.width.clone();
         }
@@ -104,10 +311,98 @@ impl Merge for Video {
         if self.resize_mode.is_none() {
             self.resize_mode = other.resize_mode.clone();
         }
+        if self.white_balance_mode.is_none() {
+            self.white_balance_mode = other.white_balance_mode.clone();
+        }
+        if self.exposure_mode.is_none() {
+            self.exposure_mode = other.exposure_mode.clone();
+        }
+        if self.focus_mode.is_none() {
+            self.focus_mode = other.focus_mode.clone();
+        }
+        if self.exposure_compensation.is_none() {
+            self.exposure_compensation = other.exposure_compensation.clone();
+        }
+        if self.exposure_time.is_none() {
+            self.exposure_time = other.exposure_time.clone();
+        }
+        if self.color_temperature.is_none() {
+            self.color_temperature = other.color_temperature.clone();
+        }
+        if self.iso.is_none() {
+            self.iso = other.iso.clone();
+        }
+        if self.brightness.is_none() {
+            self.brightness = other.brightness.clone();
+        }
+        if self.contrast.is_none() {
+            self.contrast = other.contrast.clone();
+        }
+        if self.saturation.is_none() {
+            self.saturation = other.saturation.clone();
+        }
+        if self.sharpness.is_none() {
+            self.sharpness = other.sharpness.clone();
+        }
+        if self.focus_distance.is_none() {
+            self.focus_distance = other.focus_distance.clone();
+        }
+        if self.pan.is_none() {
+            self.pan = other.pan.clone();
+        }
+        if self.tilt.is_none() {
+            self.tilt = other.tilt.clone();
+        }
+        if self.zoom.is_none() {
+            self.zoom = other.zoom.clone();
+        }
+        if self.torch.is_none() {
+            self.torch = other.torch.clone();
+        }
+        if self.display_surface.is_none() {
+            self.display_surface = other.display_surface.clone();
+        }
+        if self.logical_surface.is_none() {
+            self.logical_surface = other.logical_surface.clone();
+        }
+        if self.cursor.is_none() {
+            self.cursor = other.cursor.clone();
+        }
+        Ok(())
     }
 }
 
-impl Video {
+impl ResolveBareValues for Video {
+    fn resolve_bare_values(&mut self, mode: BareValueMode) {
+        self.width.resolve_bare_values(mode);
+        self.height.resolve_bare_values(mode);
+        self.aspect_ratio.resolve_bare_values(mode);
+        self.frame_rate.resolve_bare_values(mode);
+        self.facing_mode.resolve_bare_values(mode);
+        self.resize_mode.resolve_bare_values(mode);
+        self.white_balance_mode.resolve_bare_values(mode);
+        self.exposure_mode.resolve_bare_values(mode);
+        self.focus_mode.resolve_bare_values(mode);
+        self.exposure_compensation.resolve_bare_values(mode);
+        self.exposure_time.resolve_bare_values(mode);
+        self.color_temperature.resolve_bare_values(mode);
+        self.iso.resolve_bare_values(mode);
+        self.brightness.resolve_bare_values(mode);
+        self.contrast.resolve_bare_values(mode);
+        self.saturation.resolve_bare_values(mode);
+        self.sharpness.resolve_bare_values(mode);
+        self.focus_distance.resolve_bare_values(mode);
+        self.pan.resolve_bare_values(mode);
+        self.tilt.resolve_bare_values(mode);
+        self.zoom.resolve_bare_values(mode);
+        self.torch.resolve_bare_values(mode);
+        self.display_surface.resolve_bare_values(mode);
+        self.logical_surface.resolve_bare_values(mode);
+        self.cursor.resolve_bare_values(mode);
+    }
+}
+
+impl Fitness<setting::Video> for Video {
     fn fitness_distance(&self, settings: Option<&setting::Video>) -> f64 {
         // TODO(regexident): replace with `let_else` once stabilized:
         // Tracking issue: https://github.com/rust-lang/rust/issues/87335
@@ -140,11 +435,182 @@ impl Video {
         if let Some(resize_mode) = &self.resize_mode {
             fitness += resize_mode.fitness_distance(settings.resize_mode.as_ref());
         }
+        if let Some(white_balance_mode) = &self.white_balance_mode {
+            fitness += white_balance_mode.fitness_distance(settings.white_balance_mode.as_ref());
+        }
+        if let Some(exposure_mode) = &self.exposure_mode {
+            fitness += exposure_mode.fitness_distance(settings.exposure_mode.as_ref());
+        }
+        if let Some(focus_mode) = &self.focus_mode {
+            fitness += focus_mode.fitness_distance(settings.focus_mode.as_ref());
+        }
+        if let Some(exposure_compensation) = &self.exposure_compensation {
+            fitness +=
+                exposure_compensation.fitness_distance(settings.exposure_compensation.as_ref());
+        }
+        if let Some(exposure_time) = &self.exposure_time {
+            fitness += exposure_time.fitness_distance(settings.exposure_time.as_ref());
+        }
+        if let Some(color_temperature) = &self.color_temperature {
+            fitness += color_temperature.fitness_distance(settings.color_temperature.as_ref());
+        }
+        if let Some(iso) = &self.iso {
+            fitness += iso.fitness_distance(settings.iso.as_ref());
+        }
+        if let Some(brightness) = &self.brightness {
+            fitness += brightness.fitness_distance(settings.brightness.as_ref());
+        }
+        if let Some(contrast) = &self.contrast {
+            fitness += contrast.fitness_distance(settings.contrast.as_ref());
+        }
+        if let Some(saturation) = &self.saturation {
+            fitness += saturation.fitness_distance(settings.saturation.as_ref());
+        }
+        if let Some(sharpness) = &self.sharpness {
+            fitness += sharpness.fitness_distance(settings.sharpness.as_ref());
+        }
+        if let Some(focus_distance) = &self.focus_distance {
+            fitness += focus_distance.fitness_distance(settings.focus_distance.as_ref());
+        }
+        if let Some(pan) = &self.pan {
+            fitness += pan.fitness_distance(settings.pan.as_ref());
+        }
+        if let Some(tilt) = &self.tilt {
+            fitness += tilt.fitness_distance(settings.tilt.as_ref());
+        }
+        if let Some(zoom) = &self.zoom {
+            fitness += zoom.fitness_distance(settings.zoom.as_ref());
+        }
+        if let Some(torch) = &self.torch {
+            fitness += torch.fitness_distance(settings.torch.as_ref());
+        }
+        if let Some(display_surface) = &self.display_surface {
+            fitness += display_surface.fitness_distance(settings.display_surface.as_ref());
+        }
+        if let Some(logical_surface) = &self.logical_surface {
+            fitness += logical_surface.fitness_distance(settings.logical_surface.as_ref());
+        }
+        if let Some(cursor) = &self.cursor {
+            fitness += cursor.fitness_distance(settings.cursor.as_ref());
+        }
 
         fitness
     }
 }
 
+impl Fitness<MediaSetting> for Video {
+    fn fitness_distance(&self, settings: Option<&MediaSetting>) -> f64 {
+        match settings.map(|settings| &settings.kind) {
+            Some(MediaKindSetting::Video(settings)) => self.fitness_distance(Some(settings)),
+            Some(MediaKindSetting::Audio(_)) => f64::INFINITY,
+            None => 0.0,
+        }
+    }
+}
+
+impl Overconstrained<MediaSetting> for Video {
+    fn unsatisfied_constraints(
+        &self,
+        settings: &MediaSetting,
+    ) -> Option<Vec<OverconstrainedError>> {
+        let settings = match &settings.kind {
+            MediaKindSetting::Video(settings) => settings,
+            MediaKindSetting::Audio(_) => return None,
+        };
+
+        let errors = vec![
+            self.width
+                .as_ref()
+                .and_then(|width| width.unsatisfied_by("width", settings.width.as_ref())),
+            self.height
+                .as_ref()
+                .and_then(|height| height.unsatisfied_by("height", settings.height.as_ref())),
+            self.aspect_ratio.as_ref().and_then(|aspect_ratio| {
+                aspect_ratio.unsatisfied_by("aspect_ratio", settings.aspect_ratio.as_ref())
+            }),
+            self.frame_rate.as_ref().and_then(|frame_rate| {
+                frame_rate.unsatisfied_by("frame_rate", settings.frame_rate.as_ref())
+            }),
+            self.facing_mode.as_ref().and_then(|facing_mode| {
+                facing_mode.unsatisfied_by("facing_mode", settings.facing_mode.as_ref())
+            }),
+            self.resize_mode.as_ref().and_then(|resize_mode| {
+                resize_mode.unsatisfied_by("resize_mode", settings.resize_mode.as_ref())
+            }),
+            self.white_balance_mode
+                .as_ref()
+                .and_then(|white_balance_mode| {
+                    white_balance_mode
+                        .unsatisfied_by("white_balance_mode", settings.white_balance_mode.as_ref())
+                }),
+            self.exposure_mode.as_ref().and_then(|exposure_mode| {
+                exposure_mode.unsatisfied_by("exposure_mode", settings.exposure_mode.as_ref())
+            }),
+            self.focus_mode.as_ref().and_then(|focus_mode| {
+                focus_mode.unsatisfied_by("focus_mode", settings.focus_mode.as_ref())
+            }),
+            self.exposure_compensation
+                .as_ref()
+                .and_then(|exposure_compensation| {
+                    exposure_compensation.unsatisfied_by(
+                        "exposure_compensation",
+                        settings.exposure_compensation.as_ref(),
+                    )
+                }),
+            self.exposure_time.as_ref().and_then(|exposure_time| {
+                exposure_time.unsatisfied_by("exposure_time", settings.exposure_time.as_ref())
+            }),
+            self.color_temperature
+                .as_ref()
+                .and_then(|color_temperature| {
+                    color_temperature
+                        .unsatisfied_by("color_temperature", settings.color_temperature.as_ref())
+                }),
+            self.iso
+                .as_ref()
+                .and_then(|iso| iso.unsatisfied_by("iso", settings.iso.as_ref())),
+            self.brightness.as_ref().and_then(|brightness| {
+                brightness.unsatisfied_by("brightness", settings.brightness.as_ref())
+            }),
+            self.contrast.as_ref().and_then(|contrast| {
+                contrast.unsatisfied_by("contrast", settings.contrast.as_ref())
+            }),
+            self.saturation.as_ref().and_then(|saturation| {
+                saturation.unsatisfied_by("saturation", settings.saturation.as_ref())
+            }),
+            self.sharpness.as_ref().and_then(|sharpness| {
+                sharpness.unsatisfied_by("sharpness", settings.sharpness.as_ref())
+            }),
+            self.focus_distance.as_ref().and_then(|focus_distance| {
+                focus_distance.unsatisfied_by("focus_distance", settings.focus_distance.as_ref())
+            }),
+            self.pan
+                .as_ref()
+                .and_then(|pan| pan.unsatisfied_by("pan", settings.pan.as_ref())),
+            self.tilt
+                .as_ref()
+                .and_then(|tilt| tilt.unsatisfied_by("tilt", settings.tilt.as_ref())),
+            self.zoom
+                .as_ref()
+                .and_then(|zoom| zoom.unsatisfied_by("zoom", settings.zoom.as_ref())),
+            self.torch
+                .as_ref()
+                .and_then(|torch| torch.unsatisfied_by("torch", settings.torch.as_ref())),
+            self.display_surface.as_ref().and_then(|display_surface| {
+                display_surface.unsatisfied_by("display_surface", settings.display_surface.as_ref())
+            }),
+            self.logical_surface.as_ref().and_then(|logical_surface| {
+                logical_surface.unsatisfied_by("logical_surface", settings.logical_surface.as_ref())
+            }),
+            self.cursor
+                .as_ref()
+                .and_then(|cursor| cursor.unsatisfied_by("cursor", settings.cursor.as_ref())),
+        ];
+
+        Some(errors.into_iter().flatten().collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,6 +627,25 @@ mod tests {
                 frame_rate: None,
                 facing_mode: None,
                 resize_mode: None,
+                white_balance_mode: None,
+                exposure_mode: None,
+                focus_mode: None,
+                exposure_compensation: None,
+                exposure_time: None,
+                color_temperature: None,
+                iso: None,
+                brightness: None,
+                contrast: None,
+                saturation: None,
+                sharpness: None,
+                focus_distance: None,
+                pan: None,
+                tilt: None,
+                zoom: None,
+                torch: None,
+                display_surface: None,
+                logical_surface: None,
+                cursor: None,
             }
         );
     }
@@ -188,6 +673,25 @@ mod tests {
                     vec![setting::ResizeMode::CropAndScale],
                     None
                 )),
+                white_balance_mode: None,
+                exposure_mode: None,
+                focus_mode: None,
+                exposure_compensation: None,
+                exposure_time: None,
+                color_temperature: None,
+                iso: None,
+                brightness: None,
+                contrast: None,
+                saturation: None,
+                sharpness: None,
+                focus_distance: None,
+                pan: None,
+                tilt: None,
+                zoom: None,
+                torch: None,
+                display_surface: None,
+                logical_surface: None,
+                cursor: None,
             }
         );
     }
@@ -213,4 +717,139 @@ mod tests {
 
         assert_eq!(constraint.fitness_distance(Some(&setting)), 0.2);
     }
+
+    #[test]
+    fn fitness_distance_image_capture() {
+        let constraint = Video::builder()
+            .zoom(Zoom::within(1.0, 4.0, Some(2.0)))
+            .focus_mode(FocusMode::exactly(setting::MeteringMode::Continuous))
+            .torch(Torch::exactly(setting::Torch::On).is_required(true))
+            .build()
+            .unwrap();
+
+        let setting = setting::Video::builder()
+            .zoom(4.0)
+            .focus_mode(setting::MeteringMode::Continuous)
+            .torch(setting::Torch::On)
+            .build()
+            .unwrap();
+
+        assert_eq!(constraint.fitness_distance(Some(&setting)), 0.5);
+
+        let setting = setting::Video::builder()
+            .zoom(2.0)
+            .torch(setting::Torch::Off)
+            .build()
+            .unwrap();
+
+        assert_eq!(constraint.fitness_distance(Some(&setting)), f64::INFINITY);
+    }
+
+    #[test]
+    fn fitness_distance_screen_capture() {
+        let constraint = Video::builder()
+            .display_surface(DisplaySurface::exactly(setting::DisplaySurface::Window))
+            .cursor(Cursor::any_of(
+                vec![setting::Cursor::Always, setting::Cursor::Motion],
+                None,
+            ))
+            .logical_surface(LogicalSurface::exactly(setting::LogicalSurface::On).is_required(true))
+            .build()
+            .unwrap();
+
+        let setting = setting::Video::builder()
+            .display_surface(setting::DisplaySurface::Monitor)
+            .cursor(setting::Cursor::Motion)
+            .logical_surface(setting::LogicalSurface::On)
+            .build()
+            .unwrap();
+
+        assert_eq!(constraint.fitness_distance(Some(&setting)), 1.0);
+
+        let setting = setting::Video::builder()
+            .display_surface(setting::DisplaySurface::Window)
+            .cursor(setting::Cursor::Never)
+            .build()
+            .unwrap();
+
+        assert_eq!(constraint.fitness_distance(Some(&setting)), f64::INFINITY);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let json = serde_json::json!({
+            "width": { "min": 640, "ideal": 1280 },
+            "frameRate": 30.0,
+            "facingMode": "user",
+            "resizeMode": { "exact": "crop-and-scale" },
+        });
+
+        let subject = Video::builder()
+            .width(Width::at_least(640, Some(1280)).is_required(true))
+            .frame_rate(FrameRate::bare(30.0))
+            .facing_mode(FacingMode::bare(setting::FacingMode::User))
+            .resize_mode(ResizeMode::exactly(setting::ResizeMode::CropAndScale).is_required(true))
+            .build()
+            .unwrap();
+
+        let deserialized: Video = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(deserialized, subject);
+
+        let serialized = serde_json::to_value(&subject).unwrap();
+        assert_eq!(serialized, json);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_image_capture() {
+        let json = serde_json::json!({
+            "whiteBalanceMode": "single-shot",
+            "colorTemperature": { "min": 2500.0, "max": 6500.0 },
+            "pan": true,
+            "zoom": { "exact": 2.0 },
+            "torch": true,
+        });
+
+        let subject = Video::builder()
+            .white_balance_mode(WhiteBalanceMode::bare(setting::MeteringMode::SingleShot))
+            .color_temperature(ColorTemperature::within(2500.0, 6500.0, None).is_required(true))
+            .pan(Pan::exists(true))
+            .zoom(Zoom::exactly(2.0).is_required(true))
+            .torch(Torch::bare(setting::Torch::On))
+            .build()
+            .unwrap();
+
+        let deserialized: Video = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(deserialized, subject);
+
+        let serialized = serde_json::to_value(&subject).unwrap();
+        assert_eq!(serialized, json);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_screen_capture() {
+        let json = serde_json::json!({
+            "displaySurface": "monitor",
+            "logicalSurface": { "exact": true },
+            "cursor": ["always", "motion"],
+        });
+
+        let subject = Video::builder()
+            .display_surface(DisplaySurface::bare(setting::DisplaySurface::Monitor))
+            .logical_surface(LogicalSurface::exactly(setting::LogicalSurface::On).is_required(true))
+            .cursor(Cursor::bare_any_of(vec![
+                setting::Cursor::Always,
+                setting::Cursor::Motion,
+            ]))
+            .build()
+            .unwrap();
+
+        let deserialized: Video = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(deserialized, subject);
+
+        let serialized = serde_json::to_value(&subject).unwrap();
+        assert_eq!(serialized, json);
+    }
 }
@@ -1,4 +1,5 @@
-//! Media settings & constraints.
+//! Media capabilities, settings & constraints.
 
+pub mod capability;
 pub mod constraint;
 pub mod setting;
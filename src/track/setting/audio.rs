@@ -1,14 +1,20 @@
 use std::fmt::Debug;
 
 use derive_builder::Builder;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 mod auto_gain_control;
 mod echo_cancellation;
 mod noise_suppression;
+mod restrict_own_audio;
+mod suppress_local_audio_playback;
 
 pub use auto_gain_control::*;
 pub use echo_cancellation::*;
 pub use noise_suppression::*;
+pub use restrict_own_audio::*;
+pub use suppress_local_audio_playback::*;
 
 pub type SampleRate = u32;
 pub type SampleSize = u32;
@@ -17,21 +23,39 @@ pub type ChannelCount = u32;
 
 /// An audio's settings
 #[derive(PartialEq, Default, Clone, Builder)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
 pub struct Audio {
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub sample_rate: Option<SampleRate>,
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub sample_size: Option<SampleSize>,
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub echo_cancellation: Option<EchoCancellation>,
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub auto_gain_control: Option<AutoGainControl>,
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub noise_suppression: Option<NoiseSuppression>,
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub latency: Option<Latency>,
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub channel_count: Option<ChannelCount>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub restrict_own_audio: Option<RestrictOwnAudio>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub suppress_local_audio_playback: Option<SuppressLocalAudioPlayback>,
 }
 
 impl Audio {
@@ -39,6 +63,7 @@ impl Audio {
         Default::default()
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         sample_rate: Option<SampleRate>,
         sample_size: Option<SampleSize>,
@@ -47,6 +72,8 @@ impl Audio {
         noise_suppression: Option<NoiseSuppression>,
         latency: Option<Latency>,
         channel_count: Option<ChannelCount>,
+        restrict_own_audio: Option<RestrictOwnAudio>,
+        suppress_local_audio_playback: Option<SuppressLocalAudioPlayback>,
     ) -> Self {
         Self {
             sample_rate,
@@ -56,6 +83,8 @@ impl Audio {
             noise_suppression,
             latency,
             channel_count,
+            restrict_own_audio,
+            suppress_local_audio_playback,
         }
     }
 }
@@ -85,6 +114,15 @@ impl Debug for Audio {
         if let Some(channel_count) = self.channel_count {
             builder.field("channel_count", &channel_count);
         }
+        if let Some(restrict_own_audio) = self.restrict_own_audio {
+            builder.field("restrict_own_audio", &restrict_own_audio);
+        }
+        if let Some(suppress_local_audio_playback) = self.suppress_local_audio_playback {
+            builder.field(
+                "suppress_local_audio_playback",
+                &suppress_local_audio_playback,
+            );
+        }
 
         builder.finish()
     }
@@ -107,6 +145,8 @@ mod tests {
                 noise_suppression: None,
                 latency: None,
                 channel_count: None,
+                restrict_own_audio: None,
+                suppress_local_audio_playback: None,
             }
         );
     }
@@ -129,6 +169,8 @@ mod tests {
                 noise_suppression: None,
                 latency: None,
                 channel_count: Some(42),
+                restrict_own_audio: None,
+                suppress_local_audio_playback: None,
             }
         );
     }
@@ -143,6 +185,8 @@ mod tests {
             noise_suppression: None,
             latency: None,
             channel_count: Some(42),
+            restrict_own_audio: None,
+            suppress_local_audio_playback: None,
         };
         assert_eq!(
             format!("{:?}", subject),
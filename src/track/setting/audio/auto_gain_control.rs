@@ -1,5 +1,8 @@
 use std::fmt::Debug;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Automatic gain control is often desirable on the input signal recorded by the microphone.
 ///
 /// There are cases where it is not needed and it is desirable to turn it off so that
@@ -8,6 +11,11 @@ use std::fmt::Debug;
 /// # Specification
 /// - <https://www.w3.org/TR/mediacapture-streams/#dfn-autogaincontrol>
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "bool", into = "bool")
+)]
 pub enum AutoGainControl {
     Off,
     On,
@@ -23,6 +31,12 @@ impl From<bool> for AutoGainControl {
     }
 }
 
+impl From<AutoGainControl> for bool {
+    fn from(value: AutoGainControl) -> Self {
+        value == AutoGainControl::On
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -34,6 +48,7 @@ mod tests {
         for (flag, expected) in scenarios {
             let actual = AutoGainControl::from(flag);
             assert_eq!(actual, expected);
+            assert_eq!(bool::from(actual), flag);
         }
     }
 }
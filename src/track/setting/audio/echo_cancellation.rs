@@ -1,5 +1,8 @@
 use std::fmt::Debug;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// When one or more audio streams is being played in the processes of various microphones,
 /// it is often desirable to attempt to remove all the sound being played from the input signals
 /// recorded by the microphones. This is referred to as echo cancellation.
@@ -10,6 +13,11 @@ use std::fmt::Debug;
 /// # Specification
 /// - <https://www.w3.org/TR/mediacapture-streams/#dfn-echocancellation>
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "bool", into = "bool")
+)]
 pub enum EchoCancellation {
     Off,
     On,
@@ -25,6 +33,12 @@ impl From<bool> for EchoCancellation {
     }
 }
 
+impl From<EchoCancellation> for bool {
+    fn from(value: EchoCancellation) -> Self {
+        value == EchoCancellation::On
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,6 +50,7 @@ mod tests {
         for (flag, expected) in scenarios {
             let actual = EchoCancellation::from(flag);
             assert_eq!(actual, expected);
+            assert_eq!(bool::from(actual), flag);
         }
     }
 }
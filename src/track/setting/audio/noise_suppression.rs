@@ -1,5 +1,8 @@
 use std::fmt::Debug;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Noise suppression is often desirable on the input signal recorded by the microphone.
 ///
 /// There are cases where it is not needed and it is desirable to turn it off so that
@@ -8,6 +11,11 @@ use std::fmt::Debug;
 /// # Specification
 /// - <https://www.w3.org/TR/mediacapture-streams/#dfn-noisesuppression>
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "bool", into = "bool")
+)]
 pub enum NoiseSuppression {
     Off,
     On,
@@ -23,6 +31,12 @@ impl From<bool> for NoiseSuppression {
     }
 }
 
+impl From<NoiseSuppression> for bool {
+    fn from(value: NoiseSuppression) -> Self {
+        value == NoiseSuppression::On
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -34,6 +48,7 @@ mod tests {
         for (flag, expected) in scenarios {
             let actual = NoiseSuppression::from(flag);
             assert_eq!(actual, expected);
+            assert_eq!(bool::from(actual), flag);
         }
     }
 }
@@ -0,0 +1,52 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Whether the audio being captured is to be stripped of audio
+/// produced by the capturing document itself.
+///
+/// # Specification
+/// - <https://w3c.github.io/mediacapture-screen-share/#dfn-restrictownaudio>
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "bool", into = "bool")
+)]
+pub enum RestrictOwnAudio {
+    Off,
+    On,
+}
+
+impl From<bool> for RestrictOwnAudio {
+    fn from(boolean: bool) -> Self {
+        if boolean {
+            Self::On
+        } else {
+            Self::Off
+        }
+    }
+}
+
+impl From<RestrictOwnAudio> for bool {
+    fn from(value: RestrictOwnAudio) -> Self {
+        value == RestrictOwnAudio::On
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from() {
+        let scenarios = [(false, RestrictOwnAudio::Off), (true, RestrictOwnAudio::On)];
+
+        for (flag, expected) in scenarios {
+            let actual = RestrictOwnAudio::from(flag);
+            assert_eq!(actual, expected);
+            assert_eq!(bool::from(actual), flag);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Whether the audio being captured is to be prevented from being relayed
+/// to the local speakers of the capturing user agent.
+///
+/// # Specification
+/// - <https://w3c.github.io/mediacapture-screen-share/#dfn-suppresslocalaudioplayback>
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "bool", into = "bool")
+)]
+pub enum SuppressLocalAudioPlayback {
+    Off,
+    On,
+}
+
+impl From<bool> for SuppressLocalAudioPlayback {
+    fn from(boolean: bool) -> Self {
+        if boolean {
+            Self::On
+        } else {
+            Self::Off
+        }
+    }
+}
+
+impl From<SuppressLocalAudioPlayback> for bool {
+    fn from(value: SuppressLocalAudioPlayback) -> Self {
+        value == SuppressLocalAudioPlayback::On
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from() {
+        let scenarios = [
+            (false, SuppressLocalAudioPlayback::Off),
+            (true, SuppressLocalAudioPlayback::On),
+        ];
+
+        for (flag, expected) in scenarios {
+            let actual = SuppressLocalAudioPlayback::from(flag);
+            assert_eq!(actual, expected);
+            assert_eq!(bool::from(actual), flag);
+        }
+    }
+}
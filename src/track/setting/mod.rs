@@ -3,10 +3,14 @@
 use std::fmt::Debug;
 
 use derive_builder::Builder;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 pub mod audio;
 pub mod video;
 
+// Settings are short-lived, so boxing the larger variant isn't worth it:
+#[allow(clippy::large_enum_variant)]
 #[derive(PartialEq, Clone)]
 pub enum MediaKind {
     Audio(audio::Audio),
@@ -25,6 +29,58 @@ impl From<video::Video> for MediaKind {
     }
 }
 
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{audio, video, MediaKind};
+
+    /// The settings dictionary doesn't carry the track's kind,
+    /// so it gets inferred from which of the members are present.
+    #[derive(Deserialize)]
+    struct Repr {
+        #[serde(flatten)]
+        audio: audio::Audio,
+        #[serde(flatten)]
+        video: video::Video,
+    }
+
+    impl Serialize for MediaKind {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match self {
+                Self::Audio(audio) => audio.serialize(serializer),
+                Self::Video(video) => video.serialize(serializer),
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for MediaKind {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let Repr { audio, video } = Repr::deserialize(deserializer)?;
+
+            let is_audio = audio != audio::Audio::default();
+            let is_video = video != video::Video::default();
+
+            match (is_audio, is_video) {
+                (true, false) => Ok(Self::Audio(audio)),
+                (false, true) => Ok(Self::Video(video)),
+                (true, true) => Err(D::Error::custom(
+                    "settings contain both audio and video members",
+                )),
+                (false, false) => Err(D::Error::custom(
+                    "settings contain neither audio nor video members",
+                )),
+            }
+        }
+    }
+}
+
 impl Debug for MediaKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -34,13 +90,34 @@ impl Debug for MediaKind {
     }
 }
 
+/// A track's settings.
+///
+/// When serialized, the settings of `kind` are flattened into the
+/// settings dictionary, as is the case for `MediaTrackSettings`.
+///
+/// # Specification
+/// - <https://www.w3.org/TR/mediacapture-streams/#dom-mediatracksettings>
 #[derive(PartialEq, Clone, Builder)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Media {
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub device_id: Option<String>,
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub group_id: Option<String>,
     #[builder(setter(into))]
+    #[cfg_attr(feature = "serde", serde(flatten))]
     pub kind: MediaKind,
 }
 
@@ -110,6 +187,25 @@ mod tests {
                 frame_rate: None,
                 facing_mode: None,
                 resize_mode: None,
+                white_balance_mode: None,
+                exposure_mode: None,
+                focus_mode: None,
+                exposure_compensation: None,
+                exposure_time: None,
+                color_temperature: None,
+                iso: None,
+                brightness: None,
+                contrast: None,
+                saturation: None,
+                sharpness: None,
+                focus_distance: None,
+                pan: None,
+                tilt: None,
+                zoom: None,
+                torch: None,
+                display_surface: None,
+                logical_surface: None,
+                cursor: None,
             }),
         };
         assert_eq!(
@@ -117,4 +213,72 @@ mod tests {
             "Media { device_id: \"DEVICE\", kind: Video { width: 42 } }"
         );
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let json = serde_json::json!({
+            "deviceId": "DEVICE",
+            "width": 1280,
+            "height": 720,
+            "facingMode": "user",
+            "resizeMode": "crop-and-scale",
+        });
+
+        let subject = Media::builder()
+            .device_id("DEVICE".to_owned())
+            .kind(
+                Video::builder()
+                    .width(1280)
+                    .height(720)
+                    .facing_mode(video::FacingMode::User)
+                    .resize_mode(video::ResizeMode::CropAndScale)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        let deserialized: Media = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(deserialized, subject);
+
+        let serialized = serde_json::to_value(&subject).unwrap();
+        assert_eq!(serialized, json);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_audio() {
+        let json = serde_json::json!({
+            "sampleRate": 48000,
+            "echoCancellation": true,
+        });
+
+        let subject = Media::builder()
+            .kind(
+                audio::Audio::builder()
+                    .sample_rate(48_000)
+                    .echo_cancellation(audio::EchoCancellation::On)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        let deserialized: Media = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(deserialized, subject);
+
+        let serialized = serde_json::to_value(&subject).unwrap();
+        assert_eq!(serialized, json);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_ambiguous_kind() {
+        let json = serde_json::json!({ "deviceId": "DEVICE" });
+        assert!(serde_json::from_value::<Media>(json).is_err());
+
+        let json = serde_json::json!({ "width": 1280, "sampleRate": 48000 });
+        assert!(serde_json::from_value::<Media>(json).is_err());
+    }
 }
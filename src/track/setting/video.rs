@@ -1,33 +1,128 @@
 use std::fmt::Debug;
 
 use derive_builder::Builder;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
+mod cursor;
+mod display_surface;
 mod facing_mode;
+mod logical_surface;
+mod metering_mode;
 mod resize_mode;
+mod torch;
 
+pub use cursor::*;
+pub use display_surface::*;
 pub use facing_mode::*;
+pub use logical_surface::*;
+pub use metering_mode::*;
 pub use resize_mode::*;
+pub use torch::*;
 
 pub type Width = u32;
 pub type Height = u32;
 pub type AspectRatio = f64;
 pub type FrameRate = f64;
+pub type WhiteBalanceMode = MeteringMode;
+pub type ExposureMode = MeteringMode;
+pub type FocusMode = MeteringMode;
+pub type ExposureCompensation = f64;
+pub type ExposureTime = f64;
+pub type ColorTemperature = f64;
+pub type Iso = f64;
+pub type Brightness = f64;
+pub type Contrast = f64;
+pub type Saturation = f64;
+pub type Sharpness = f64;
+pub type FocusDistance = f64;
+pub type Pan = f64;
+pub type Tilt = f64;
+pub type Zoom = f64;
 
 /// A video's settings
 #[derive(PartialEq, Default, Clone, Builder)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
 pub struct Video {
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub width: Option<Width>,
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub height: Option<Height>,
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub aspect_ratio: Option<AspectRatio>,
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub frame_rate: Option<FrameRate>,
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub facing_mode: Option<FacingMode>,
     #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub resize_mode: Option<ResizeMode>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub white_balance_mode: Option<WhiteBalanceMode>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub exposure_mode: Option<ExposureMode>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub focus_mode: Option<FocusMode>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub exposure_compensation: Option<ExposureCompensation>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub exposure_time: Option<ExposureTime>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub color_temperature: Option<ColorTemperature>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub iso: Option<Iso>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub brightness: Option<Brightness>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub contrast: Option<Contrast>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub saturation: Option<Saturation>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub sharpness: Option<Sharpness>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub focus_distance: Option<FocusDistance>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub pan: Option<Pan>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub tilt: Option<Tilt>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub zoom: Option<Zoom>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub torch: Option<Torch>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub display_surface: Option<DisplaySurface>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub logical_surface: Option<LogicalSurface>,
+    #[builder(default, setter(strip_option))]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub cursor: Option<Cursor>,
 }
 
 impl Video {
@@ -35,6 +130,7 @@ impl Video {
         Default::default()
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         width: Option<Width>,
         height: Option<Height>,
@@ -42,6 +138,25 @@ impl Video {
         frame_rate: Option<FrameRate>,
         facing_mode: Option<FacingMode>,
         resize_mode: Option<ResizeMode>,
+        white_balance_mode: Option<WhiteBalanceMode>,
+        exposure_mode: Option<ExposureMode>,
+        focus_mode: Option<FocusMode>,
+        exposure_compensation: Option<ExposureCompensation>,
+        exposure_time: Option<ExposureTime>,
+        color_temperature: Option<ColorTemperature>,
+        iso: Option<Iso>,
+        brightness: Option<Brightness>,
+        contrast: Option<Contrast>,
+        saturation: Option<Saturation>,
+        sharpness: Option<Sharpness>,
+        focus_distance: Option<FocusDistance>,
+        pan: Option<Pan>,
+        tilt: Option<Tilt>,
+        zoom: Option<Zoom>,
+        torch: Option<Torch>,
+        display_surface: Option<DisplaySurface>,
+        logical_surface: Option<LogicalSurface>,
+        cursor: Option<Cursor>,
     ) -> Self {
         Self {
             width,
@@ -50,6 +165,25 @@ impl Video {
             frame_rate,
             facing_mode,
             resize_mode,
+            white_balance_mode,
+            exposure_mode,
+            focus_mode,
+            exposure_compensation,
+            exposure_time,
+            color_temperature,
+            iso,
+            brightness,
+            contrast,
+            saturation,
+            sharpness,
+            focus_distance,
+            pan,
+            tilt,
+            zoom,
+            torch,
+            display_surface,
+            logical_surface,
+            cursor,
         }
     }
 }
@@ -76,6 +210,63 @@ impl Debug for Video {
         if let Some(resize_mode) = self.resize_mode {
             builder.field("resize_mode", &resize_mode);
         }
+        if let Some(white_balance_mode) = self.white_balance_mode {
+            builder.field("white_balance_mode", &white_balance_mode);
+        }
+        if let Some(exposure_mode) = self.exposure_mode {
+            builder.field("exposure_mode", &exposure_mode);
+        }
+        if let Some(focus_mode) = self.focus_mode {
+            builder.field("focus_mode", &focus_mode);
+        }
+        if let Some(exposure_compensation) = self.exposure_compensation {
+            builder.field("exposure_compensation", &exposure_compensation);
+        }
+        if let Some(exposure_time) = self.exposure_time {
+            builder.field("exposure_time", &exposure_time);
+        }
+        if let Some(color_temperature) = self.color_temperature {
+            builder.field("color_temperature", &color_temperature);
+        }
+        if let Some(iso) = self.iso {
+            builder.field("iso", &iso);
+        }
+        if let Some(brightness) = self.brightness {
+            builder.field("brightness", &brightness);
+        }
+        if let Some(contrast) = self.contrast {
+            builder.field("contrast", &contrast);
+        }
+        if let Some(saturation) = self.saturation {
+            builder.field("saturation", &saturation);
+        }
+        if let Some(sharpness) = self.sharpness {
+            builder.field("sharpness", &sharpness);
+        }
+        if let Some(focus_distance) = self.focus_distance {
+            builder.field("focus_distance", &focus_distance);
+        }
+        if let Some(pan) = self.pan {
+            builder.field("pan", &pan);
+        }
+        if let Some(tilt) = self.tilt {
+            builder.field("tilt", &tilt);
+        }
+        if let Some(zoom) = self.zoom {
+            builder.field("zoom", &zoom);
+        }
+        if let Some(torch) = self.torch {
+            builder.field("torch", &torch);
+        }
+        if let Some(display_surface) = self.display_surface {
+            builder.field("display_surface", &display_surface);
+        }
+        if let Some(logical_surface) = self.logical_surface {
+            builder.field("logical_surface", &logical_surface);
+        }
+        if let Some(cursor) = self.cursor {
+            builder.field("cursor", &cursor);
+        }
 
         builder.finish()
     }
@@ -97,6 +288,25 @@ mod tests {
                 frame_rate: None,
                 facing_mode: None,
                 resize_mode: None,
+                white_balance_mode: None,
+                exposure_mode: None,
+                focus_mode: None,
+                exposure_compensation: None,
+                exposure_time: None,
+                color_temperature: None,
+                iso: None,
+                brightness: None,
+                contrast: None,
+                saturation: None,
+                sharpness: None,
+                focus_distance: None,
+                pan: None,
+                tilt: None,
+                zoom: None,
+                torch: None,
+                display_surface: None,
+                logical_surface: None,
+                cursor: None,
             }
         );
     }
@@ -118,6 +328,25 @@ mod tests {
                 frame_rate: Some(30.0),
                 facing_mode: None,
                 resize_mode: Some(ResizeMode::CropAndScale),
+                white_balance_mode: None,
+                exposure_mode: None,
+                focus_mode: None,
+                exposure_compensation: None,
+                exposure_time: None,
+                color_temperature: None,
+                iso: None,
+                brightness: None,
+                contrast: None,
+                saturation: None,
+                sharpness: None,
+                focus_distance: None,
+                pan: None,
+                tilt: None,
+                zoom: None,
+                torch: None,
+                display_surface: None,
+                logical_surface: None,
+                cursor: None,
             }
         );
     }
@@ -131,6 +360,25 @@ mod tests {
             frame_rate: Some(30.0),
             facing_mode: None,
             resize_mode: Some(ResizeMode::CropAndScale),
+            white_balance_mode: None,
+            exposure_mode: None,
+            focus_mode: None,
+            exposure_compensation: None,
+            exposure_time: None,
+            color_temperature: None,
+            iso: None,
+            brightness: None,
+            contrast: None,
+            saturation: None,
+            sharpness: None,
+            focus_distance: None,
+            pan: None,
+            tilt: None,
+            zoom: None,
+            torch: None,
+            display_surface: None,
+            logical_surface: None,
+            cursor: None,
         };
         assert_eq!(
             format!("{:?}", subject),
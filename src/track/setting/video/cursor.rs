@@ -0,0 +1,81 @@
+use std::{borrow::Cow, fmt::Debug, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, PartialEq, Eq, Debug)]
+pub enum CursorParsingError {
+    #[error("Unknown cursor capture mode: {value}")]
+    UnknownValue { value: String },
+}
+
+/// Whether and when the cursor is included in the captured display surface.
+///
+/// # Specification
+/// - <https://w3c.github.io/mediacapture-screen-share/#dom-cursorcaptureconstraint>
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Cursor {
+    /// The cursor is never included in the captured display surface.
+    Never,
+
+    /// The cursor is always included in the captured display surface.
+    Always,
+
+    /// The cursor is included in the captured display surface while it is moving.
+    Motion,
+}
+
+impl FromStr for Cursor {
+    type Err = CursorParsingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut string = Cow::from(s);
+
+        if !s.chars().all(|c| c.is_lowercase()) {
+            string.to_mut().make_ascii_lowercase();
+        }
+
+        match string.as_ref() {
+            "never" => Ok(Self::Never),
+            "always" => Ok(Self::Always),
+            "motion" => Ok(Self::Motion),
+            _ => Err(Self::Err::UnknownValue {
+                value: s.to_owned(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_success() {
+        let scenarios = [
+            ("never", Cursor::Never),
+            ("always", Cursor::Always),
+            ("motion", Cursor::Motion),
+        ];
+
+        for (string, expected) in scenarios {
+            let actual = Cursor::from_str(string).unwrap();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn from_str_failure() {
+        let actual = Cursor::from_str("INVALID");
+        let expected = Err(CursorParsingError::UnknownValue {
+            value: "INVALID".to_owned(),
+        });
+        assert_eq!(actual, expected);
+    }
+}
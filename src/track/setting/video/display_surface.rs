@@ -0,0 +1,81 @@
+use std::{borrow::Cow, fmt::Debug, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, PartialEq, Eq, Debug)]
+pub enum DisplaySurfaceParsingError {
+    #[error("Unknown display surface: {value}")]
+    UnknownValue { value: String },
+}
+
+/// The types of display surface that can be captured.
+///
+/// # Specification
+/// - <https://w3c.github.io/mediacapture-screen-share/#dom-displaycapturesurfacetype>
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum DisplaySurface {
+    /// The source is a monitor, or a collection of monitors.
+    Monitor,
+
+    /// The source is a single window.
+    Window,
+
+    /// The source is a single browser tab.
+    Browser,
+}
+
+impl FromStr for DisplaySurface {
+    type Err = DisplaySurfaceParsingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut string = Cow::from(s);
+
+        if !s.chars().all(|c| c.is_lowercase()) {
+            string.to_mut().make_ascii_lowercase();
+        }
+
+        match string.as_ref() {
+            "monitor" => Ok(Self::Monitor),
+            "window" => Ok(Self::Window),
+            "browser" => Ok(Self::Browser),
+            _ => Err(Self::Err::UnknownValue {
+                value: s.to_owned(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_success() {
+        let scenarios = [
+            ("monitor", DisplaySurface::Monitor),
+            ("window", DisplaySurface::Window),
+            ("browser", DisplaySurface::Browser),
+        ];
+
+        for (string, expected) in scenarios {
+            let actual = DisplaySurface::from_str(string).unwrap();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn from_str_failure() {
+        let actual = DisplaySurface::from_str("INVALID");
+        let expected = Err(DisplaySurfaceParsingError::UnknownValue {
+            value: "INVALID".to_owned(),
+        });
+        assert_eq!(actual, expected);
+    }
+}
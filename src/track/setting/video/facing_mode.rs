@@ -1,5 +1,7 @@
 use std::{borrow::Cow, fmt::Debug, str::FromStr};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[derive(Error, PartialEq, Eq, Debug)]
@@ -17,6 +19,11 @@ pub enum FacingModeParsingError {
 /// # Specification
 /// - <https://www.w3.org/TR/mediacapture-streams/#dfn-facingmode>
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum FacingMode {
     /// The source is facing toward the user (a self-view camera).
     User,
@@ -0,0 +1,53 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Whether the display surface being captured is a logical surface,
+/// i.e. one that is not necessarily visible on the screen (e.g. an off-screen window
+/// or the part of a document outside of the viewport).
+///
+/// # Specification
+/// - <https://w3c.github.io/mediacapture-screen-share/#dfn-logicalsurface>
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "bool", into = "bool")
+)]
+pub enum LogicalSurface {
+    Off,
+    On,
+}
+
+impl From<bool> for LogicalSurface {
+    fn from(boolean: bool) -> Self {
+        if boolean {
+            Self::On
+        } else {
+            Self::Off
+        }
+    }
+}
+
+impl From<LogicalSurface> for bool {
+    fn from(value: LogicalSurface) -> Self {
+        value == LogicalSurface::On
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from() {
+        let scenarios = [(false, LogicalSurface::Off), (true, LogicalSurface::On)];
+
+        for (flag, expected) in scenarios {
+            let actual = LogicalSurface::from(flag);
+            assert_eq!(actual, expected);
+            assert_eq!(bool::from(actual), flag);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::{borrow::Cow, fmt::Debug, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, PartialEq, Eq, Debug)]
+pub enum MeteringModeParsingError {
+    #[error("Unknown metering mode: {value}")]
+    UnknownValue { value: String },
+}
+
+/// The modes in which a camera's white balance, exposure or focus can be controlled.
+///
+/// # Specification
+/// - <https://www.w3.org/TR/image-capture/#meteringmode-section>
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum MeteringMode {
+    /// This source does not offer focus/exposure/white balance mode.
+    None,
+
+    /// The capture device is set to manually control the lens position/exposure time/white balance,
+    /// or such a mode is requested to be configured.
+    Manual,
+
+    /// The capture device is configured for single-sweep autofocus/one-shot exposure/white balance calculation,
+    /// or such a mode is requested.
+    SingleShot,
+
+    /// The capture device is configured for continuous focusing for near-zero shutter-lag/continuous
+    /// auto exposure/white balance calculation, or such continuous focus hunting/exposure/white balance
+    /// calculation mode is requested.
+    Continuous,
+}
+
+impl FromStr for MeteringMode {
+    type Err = MeteringModeParsingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut string = Cow::from(s);
+
+        if !s.chars().all(|c| c.is_lowercase()) {
+            string.to_mut().make_ascii_lowercase();
+        }
+
+        match string.as_ref() {
+            "none" => Ok(Self::None),
+            "manual" => Ok(Self::Manual),
+            "single-shot" => Ok(Self::SingleShot),
+            "continuous" => Ok(Self::Continuous),
+            _ => Err(Self::Err::UnknownValue {
+                value: s.to_owned(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_success() {
+        let scenarios = [
+            ("none", MeteringMode::None),
+            ("manual", MeteringMode::Manual),
+            ("single-shot", MeteringMode::SingleShot),
+            ("continuous", MeteringMode::Continuous),
+        ];
+
+        for (string, expected) in scenarios {
+            let actual = MeteringMode::from_str(string).unwrap();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn from_str_failure() {
+        let actual = MeteringMode::from_str("INVALID");
+        let expected = Err(MeteringModeParsingError::UnknownValue {
+            value: "INVALID".to_owned(),
+        });
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,5 +1,7 @@
 use std::{borrow::Cow, fmt::Debug, str::FromStr};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[derive(Error, PartialEq, Eq, Debug)]
@@ -17,6 +19,11 @@ pub enum ResizeModeParsingError {
 /// # Specification
 /// - <https://www.w3.org/TR/mediacapture-streams/#dfn-resizemode>
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub enum ResizeMode {
     /// This resolution and frame rate is offered by the camera, its driver, or the OS.
     None,
@@ -0,0 +1,52 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Whether the fill light of the camera is continuously connected,
+/// i.e. whether it stays on as long as the track is active.
+///
+/// # Specification
+/// - <https://www.w3.org/TR/image-capture/#dom-mediatracksettings-torch>
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "bool", into = "bool")
+)]
+pub enum Torch {
+    Off,
+    On,
+}
+
+impl From<bool> for Torch {
+    fn from(boolean: bool) -> Self {
+        if boolean {
+            Self::On
+        } else {
+            Self::Off
+        }
+    }
+}
+
+impl From<Torch> for bool {
+    fn from(value: Torch) -> Self {
+        value == Torch::On
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from() {
+        let scenarios = [(false, Torch::Off), (true, Torch::On)];
+
+        for (flag, expected) in scenarios {
+            let actual = Torch::from(flag);
+            assert_eq!(actual, expected);
+            assert_eq!(bool::from(actual), flag);
+        }
+    }
+}